@@ -0,0 +1,80 @@
+//! Scaling benchmarks for large generated schemas
+//!
+//! Each benchmark compares two versions of a schema with `n` tables or
+//! messages, where every tenth element changed and a few were added or
+//! removed. Throughput is reported per element, so a flat time per element
+//! across sizes shows that analysis scales linearly.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rusty_schema_diff::{ProtobufAnalyzer, Schema, SchemaAnalyzer, SchemaFormat, SqlAnalyzer};
+use semver::Version;
+use std::fmt::Write;
+use std::hint::black_box;
+
+const SIZES: [usize; 4] = [500, 1_000, 2_000, 5_000];
+
+/// Generates a DDL script with `tables` tables of eight columns each
+fn sql_schema(tables: usize, changed: bool) -> String {
+    let mut sql = String::new();
+    for table in 0..tables {
+        if changed && table % 100 == 1 {
+            continue;
+        }
+        let name = if changed && table % 100 == 2 { format!("archive_{}", table) } else { format!("table_{}", table) };
+        writeln!(sql, "CREATE TABLE {} (", name).unwrap();
+        writeln!(sql, "  id BIGINT PRIMARY KEY,").unwrap();
+        for column in 0..6 {
+            writeln!(sql, "  column_{} VARCHAR(64),", column).unwrap();
+        }
+        if changed && table % 10 == 0 {
+            writeln!(sql, "  column_6 VARCHAR(32),\n  created_at TIMESTAMP NOT NULL").unwrap();
+        } else {
+            writeln!(sql, "  column_6 VARCHAR(64)").unwrap();
+        }
+        writeln!(sql, ");").unwrap();
+    }
+    sql
+}
+
+/// Generates a text-format descriptor with `messages` messages of eight fields each
+fn proto_schema(messages: usize, changed: bool) -> String {
+    let mut proto = String::from("package: \"bench.v1\"\n");
+    for message in 0..messages {
+        if changed && message % 100 == 1 {
+            continue;
+        }
+        writeln!(proto, "message_type {{\n  name: \"Message{}\"", message).unwrap();
+        for field in 1..=8 {
+            let type_ = if changed && message % 10 == 0 && field == 8 { "TYPE_INT64" } else { "TYPE_STRING" };
+            writeln!(proto, "  field {{ name: \"field_{}\" number: {} type: {} }}", field, field, type_).unwrap();
+        }
+        writeln!(proto, "}}").unwrap();
+    }
+    proto
+}
+
+fn bench_analyzer(c: &mut Criterion, group_name: &str, analyzer: &dyn SchemaAnalyzer, format: SchemaFormat, generate: fn(usize, bool) -> String) {
+    let mut group = c.benchmark_group(group_name);
+    group.sample_size(10);
+    for size in SIZES {
+        let old = Schema::new(format, generate(size, false), Version::new(1, 0, 0));
+        let new = Schema::new(format, generate(size, true), Version::new(2, 0, 0));
+
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &(old, new), |b, (old, new)| {
+            b.iter(|| analyzer.analyze_compatibility(black_box(old), black_box(new)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn sql_tables(c: &mut Criterion) {
+    bench_analyzer(c, "sql_tables", &SqlAnalyzer, SchemaFormat::SqlDDL, sql_schema);
+}
+
+fn protobuf_messages(c: &mut Criterion) {
+    bench_analyzer(c, "protobuf_messages", &ProtobufAnalyzer, SchemaFormat::Protobuf, proto_schema);
+}
+
+criterion_group!(benches, sql_tables, protobuf_messages);
+criterion_main!(benches);
@@ -3,9 +3,20 @@
 //! This module provides the base traits and types used by all schema analyzers.
 
 use crate::{Schema, CompatibilityReport, MigrationPlan, ValidationResult, error::Result};
+use crate::location::{Location, SourceIndex};
+use crate::baseline::Baseline;
+use crate::compatibility::CompatibilityMode;
+use crate::policy::Policy;
+use crate::renames::RenameHints;
+use crate::report::{CompatibilityIssue, IssueSeverity, ValidationError};
 use serde::{Serialize, Deserialize};
 use std::collections::HashMap;
 
+mod kind;
+mod rename;
+pub use kind::ChangeKind;
+pub(crate) use rename::{declared_rename, detect_renames, rename_members};
+
 pub mod json_schema;
 pub mod protobuf;
 pub mod openapi;
@@ -26,6 +37,26 @@ pub trait SchemaAnalyzer {
     /// A compatibility report detailing the differences and compatibility status
     fn analyze_compatibility(&self, old: &Schema, new: &Schema) -> Result<CompatibilityReport>;
 
+    /// Analyzes compatibility between two schema versions under the given options
+    ///
+    /// The default implementation applies the options' compatibility mode,
+    /// policy and baseline to the report returned by
+    /// [`SchemaAnalyzer::analyze_compatibility`], which analyzers produce in
+    /// `Full` mode. Analyzers that support rename hints override it to apply
+    /// the options' hints before comparing.
+    ///
+    /// # Arguments
+    /// * `old` - The original schema version
+    /// * `new` - The new schema version to compare against
+    /// * `options` - Options controlling how changes are judged
+    ///
+    /// # Returns
+    /// A compatibility report judged according to `options`
+    fn analyze_with_options(&self, old: &Schema, new: &Schema, options: &AnalysisOptions) -> Result<CompatibilityReport> {
+        let report = self.analyze_compatibility(old, new)?;
+        options.apply(report)
+    }
+
     /// Generates a migration path between schema versions
     ///
     /// # Arguments
@@ -46,13 +77,64 @@ pub trait SchemaAnalyzer {
     fn validate_changes(&self, changes: &[SchemaChange]) -> Result<ValidationResult>;
 }
 
+/// Options controlling how schema changes are judged
+#[derive(Debug, Clone, Default)]
+pub struct AnalysisOptions {
+    /// Direction in which the schemas must be compatible
+    pub mode: CompatibilityMode,
+    /// Policy applied to the detected changes
+    pub policy: Policy,
+    /// Baseline of accepted issues to suppress
+    pub baseline: Option<Baseline>,
+    /// Renames to apply before comparing
+    pub renames: RenameHints,
+}
+
+impl AnalysisOptions {
+    /// Sets the direction in which the schemas must be compatible
+    pub fn with_mode(mut self, mode: CompatibilityMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets the policy applied to the detected changes
+    pub fn with_policy(mut self, policy: Policy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Sets the baseline of accepted issues to suppress
+    pub fn with_baseline(mut self, baseline: Baseline) -> Self {
+        self.baseline = Some(baseline);
+        self
+    }
+
+    /// Sets the renames to apply before comparing
+    pub fn with_renames(mut self, renames: RenameHints) -> Self {
+        self.renames = renames;
+        self
+    }
+
+    /// Judges an analyzer's report by the mode and policy, then suppresses baselined issues
+    pub fn apply(&self, report: CompatibilityReport) -> Result<CompatibilityReport> {
+        let report = self.mode.apply(report, self.policy.threshold);
+        let report = self.policy.apply(report)?;
+        Ok(match &self.baseline {
+            Some(baseline) => baseline.apply(report, self.policy.threshold),
+            None => report,
+        })
+    }
+}
+
 /// Represents a single schema change
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SchemaChange {
     /// Type of change (Addition, Removal, etc.)
     pub change_type: ChangeType,
-    /// Path to the changed element
-    pub location: String,
+    /// Specific kind of the change, identifying the rule it falls under
+    pub kind: ChangeKind,
+    /// Location of the changed element
+    pub location: Location,
     /// Human-readable description of the change
     pub description: String,
     /// Metadata associated with the change
@@ -60,7 +142,7 @@ pub struct SchemaChange {
 }
 
 impl SchemaChange {
-    /// Creates a new SchemaChange instance
+    /// Creates a new SchemaChange instance with the generic kind for its type
     ///
     /// # Arguments
     /// * `change_type` - The type of change
@@ -72,17 +154,117 @@ impl SchemaChange {
     /// A new SchemaChange instance
     pub fn new(
         change_type: ChangeType,
-        location: impl Into<String>,
+        location: impl Into<Location>,
         description: impl Into<String>,
         metadata: HashMap<String, String>,
     ) -> Self {
         SchemaChange {
+            kind: ChangeKind::generic(&change_type),
             change_type,
             location: location.into(),
             description: description.into(),
             metadata,
         }
     }
+
+    /// Creates a new SchemaChange instance of a specific kind
+    ///
+    /// The change type is derived from the kind.
+    ///
+    /// # Arguments
+    /// * `kind` - The kind of change
+    /// * `location` - The location of the change
+    /// * `description` - The description of the change
+    /// * `metadata` - The metadata associated with the change
+    ///
+    /// # Returns
+    /// A new SchemaChange instance
+    pub fn from_kind(
+        kind: ChangeKind,
+        location: impl Into<Location>,
+        description: impl Into<String>,
+        metadata: HashMap<String, String>,
+    ) -> Self {
+        SchemaChange {
+            change_type: kind.change_type(),
+            kind,
+            location: location.into(),
+            description: description.into(),
+            metadata,
+        }
+    }
+}
+
+/// Builds a compatibility report from classified changes
+///
+/// Removals and additions that look like renames are merged first (see
+/// [`detect_renames`]). Every change whose kind is more severe than `Info`
+/// becomes an issue, and the score deducts each issue's weight from 100.
+pub(crate) fn build_report(changes: Vec<SchemaChange>, metadata: HashMap<String, String>) -> CompatibilityReport {
+    let changes = detect_renames(changes);
+    let issues = detect_issues(&changes);
+    let deductions: u32 = issues.iter().map(|issue| issue.severity.deduction()).sum();
+    let compatibility_score = 100u32.saturating_sub(deductions) as u8;
+
+    CompatibilityReport {
+        changes,
+        compatibility_score,
+        is_compatible: compatibility_score >= Policy::DEFAULT_THRESHOLD,
+        issues,
+        metadata,
+    }
+}
+
+/// Derives compatibility issues from the severity of each change's kind
+pub(crate) fn detect_issues(changes: &[SchemaChange]) -> Vec<CompatibilityIssue> {
+    changes
+        .iter()
+        .filter_map(|change| issue_for(change, change.kind.default_severity()))
+        .collect()
+}
+
+/// Builds the issue for a change of the given severity, or `None` for `Info`
+pub(crate) fn issue_for(change: &SchemaChange, severity: IssueSeverity) -> Option<CompatibilityIssue> {
+    let prefix = match severity {
+        IssueSeverity::Error => "Breaking change",
+        IssueSeverity::Warning => "Potential compatibility issue",
+        IssueSeverity::Info => return None,
+    };
+
+    Some(CompatibilityIssue {
+        severity,
+        kind: change.kind,
+        description: format!("{}: {}", prefix, change.description),
+        location: change.location.clone(),
+        suppression: None,
+    })
+}
+
+/// Converts the issues of a set of changes into validation errors keyed by rule ID
+pub(crate) fn validation_errors(changes: &[SchemaChange]) -> Vec<ValidationError> {
+    detect_issues(changes)
+        .into_iter()
+        .map(|issue| ValidationError {
+            message: issue.description,
+            path: issue.location,
+            code: issue.kind.rule_id().to_string(),
+        })
+        .collect()
+}
+
+/// Resolves the source positions of detected changes
+///
+/// Removed elements are looked up in the old document, all other changes in
+/// the new one. See [`Location::resolve_position`].
+pub(crate) fn resolve_positions(changes: &mut [SchemaChange], old: &str, new: &str) {
+    let (old, new) = (SourceIndex::new(old), SourceIndex::new(new));
+    for change in changes {
+        let source = match change.change_type {
+            ChangeType::Removal => &old,
+            _ => &new,
+        };
+        change.location.resolve_indexed(source);
+    }
 }
 
 /// Types of schema changes that can occur
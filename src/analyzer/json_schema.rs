@@ -3,11 +3,54 @@
 //! This module provides functionality for analyzing JSON Schema changes and
 //! generating compatibility reports and migration paths.
 
-use crate::analyzer::{SchemaAnalyzer, SchemaChange, ChangeType};
-use crate::{Schema, CompatibilityReport, MigrationPlan, ValidationResult};
+use crate::analyzer::{SchemaAnalyzer, AnalysisOptions, SchemaChange, ChangeKind, build_report, rename_members, resolve_positions, validation_errors};
+use crate::location::Location;
+use crate::{Schema, CompatibilityReport, MigrationPlan, RenameHints, ValidationResult};
 use crate::error::Result;
-use serde_json::Value;
-use std::collections::HashMap;
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+mod constraints;
+mod draft;
+mod refs;
+
+use constraints::CONSTRAINT_KEYWORDS;
+use draft::Draft;
+use refs::{Document, Keywords, Members};
+
+/// Keywords holding definitions, which are compared where they are referenced
+const DEFINITION_KEYWORDS: [&str; 2] = ["$defs", "definitions"];
+
+/// Keywords compared by dedicated rules before all others, or not at all
+///
+/// `$schema` only selects the draft, whose differences are accounted for
+/// when both documents are converted to the same draft.
+const SEMANTIC_KEYWORDS: [&str; 6] = ["properties", "required", "type", "$defs", "definitions", "$schema"];
+
+/// Keywords holding a map of named subschemas, other than `properties` and definitions
+const SCHEMA_MAP_KEYWORDS: [&str; 3] = ["patternProperties", "dependentSchemas", "dependencies"];
+
+/// Keywords combining an unordered array of subschemas
+const APPLICATOR_KEYWORDS: [&str; 3] = ["allOf", "anyOf", "oneOf"];
+
+/// Keywords holding a subschema or an array of subschemas
+const SUBSCHEMA_KEYWORDS: [&str; 15] = [
+    "items",
+    "additionalItems",
+    "prefixItems",
+    "additionalProperties",
+    "unevaluatedItems",
+    "unevaluatedProperties",
+    "propertyNames",
+    "contains",
+    "not",
+    "if",
+    "then",
+    "else",
+    "allOf",
+    "anyOf",
+    "oneOf",
+];
 
 /// Analyzes JSON Schema changes and generates compatibility reports.
 pub struct JsonSchemaAnalyzer;
@@ -24,22 +67,13 @@ impl SchemaAnalyzer for JsonSchemaAnalyzer {
     ///
     /// A `CompatibilityReport` detailing the differences and compatibility status.
     fn analyze_compatibility(&self, old: &Schema, new: &Schema) -> Result<CompatibilityReport> {
-        let old_schema: Value = serde_json::from_str(&old.content)?;
-        let new_schema: Value = serde_json::from_str(&new.content)?;
-
-        let mut changes = Vec::new();
-        self.compare_schemas(&old_schema, &new_schema, "", &mut changes);
-
-        let compatibility_score = self.calculate_compatibility_score(&changes);
-        let is_compatible = compatibility_score >= 80;
+        self.analyze_renamed(old, new, &RenameHints::default())
+    }
 
-        Ok(CompatibilityReport {
-            changes,
-            compatibility_score,
-            is_compatible,
-            issues: vec![],  // TODO: Implement issue detection
-            metadata: Default::default(),
-        })
+    /// Analyzes compatibility between two JSON Schema versions, applying the options' rename hints.
+    fn analyze_with_options(&self, old: &Schema, new: &Schema, options: &AnalysisOptions) -> Result<CompatibilityReport> {
+        let report = self.analyze_renamed(old, new, &options.renames)?;
+        options.apply(report)
     }
 
     /// Generates a migration path between JSON Schema versions.
@@ -53,11 +87,7 @@ impl SchemaAnalyzer for JsonSchemaAnalyzer {
     ///
     /// A `MigrationPlan` detailing the required changes.
     fn generate_migration_path(&self, old: &Schema, new: &Schema) -> Result<MigrationPlan> {
-        let mut changes = Vec::new();
-        let old_schema: Value = serde_json::from_str(&old.content)?;
-        let new_schema: Value = serde_json::from_str(&new.content)?;
-
-        self.compare_schemas(&old_schema, &new_schema, "", &mut changes);
+        let changes = self.detect_changes(old, new, &RenameHints::default())?;
 
         Ok(MigrationPlan::new(
             old.version.to_string(),
@@ -66,33 +96,124 @@ impl SchemaAnalyzer for JsonSchemaAnalyzer {
         ))
     }
 
-    fn validate_changes(&self, _changes: &[SchemaChange]) -> Result<ValidationResult> {
+    fn validate_changes(&self, changes: &[SchemaChange]) -> Result<ValidationResult> {
+        let errors = validation_errors(changes);
+
         Ok(ValidationResult {
-            is_valid: true,
-            errors: Vec::new(),
+            is_valid: errors.is_empty(),
+            errors,
             context: HashMap::new(),
         })
     }
 }
 
 impl JsonSchemaAnalyzer {
+    /// Analyzes compatibility with declared renames applied
+    fn analyze_renamed(&self, old: &Schema, new: &Schema, hints: &RenameHints) -> Result<CompatibilityReport> {
+        let mut changes = self.detect_changes(old, new, hints)?;
+        resolve_positions(&mut changes, &old.content, &new.content);
+
+        Ok(build_report(changes, Default::default()))
+    }
+
+    /// Parses both schemas, applies declared renames and collects the changes
+    ///
+    /// The old schema is converted to the draft of the new one first. A
+    /// schema without a known `$schema` is assumed to use the draft of the
+    /// other, or 2020-12 if neither declares one.
+    fn detect_changes(&self, old: &Schema, new: &Schema, hints: &RenameHints) -> Result<Vec<SchemaChange>> {
+        let mut old_schema: Value = serde_json::from_str(&old.content)?;
+        let mut new_schema: Value = serde_json::from_str(&new.content)?;
+
+        let (old_draft, new_draft) = (Draft::detect(&old_schema), Draft::detect(&new_schema));
+        let draft = new_draft.or(old_draft).unwrap_or(Draft::Draft2020_12);
+        draft::convert(&mut old_schema, old_draft.unwrap_or(draft), draft);
+        draft::convert(&mut new_schema, draft, draft);
+
+        let mut changes = rename_members(&mut old_schema, &mut new_schema, hints);
+        let mut comparison = Comparison::new(&old_schema, &new_schema, draft);
+        comparison.compare_schemas(&old_schema, &new_schema, &Location::root());
+        changes.extend(comparison.changes);
+        Ok(changes)
+    }
+}
+
+/// State of the comparison of two JSON Schema documents
+///
+/// Schemas holding a `$ref` are compared through their targets, so a change
+/// to a definition is reported at every location that references it.
+struct Comparison<'a> {
+    old: Document<'a>,
+    new: Document<'a>,
+    /// Pairs of schemas being compared through references, outermost first
+    ///
+    /// A reference back to a pair already being compared is not followed
+    /// again, which ends the comparison of recursive schemas.
+    active: Vec<(*const Value, *const Value)>,
+    changes: Vec<SchemaChange>,
+}
+
+impl<'a> Comparison<'a> {
+    fn new(old: &'a Value, new: &'a Value, draft: Draft) -> Self {
+        Self {
+            old: Document::new(old, draft.id_keyword()),
+            new: Document::new(new, draft.id_keyword()),
+            active: vec![(old as *const Value, new as *const Value)],
+            changes: Vec::new(),
+        }
+    }
+
     /// Compares two JSON schemas and collects changes
-    fn compare_schemas(&self, old: &Value, new: &Value, path: &str, changes: &mut Vec<SchemaChange>) {
+    ///
+    /// Keywords with known semantics are compared by meaning: `properties`
+    /// and `required` per property, `type` by the values it accepts, `enum`
+    /// per value, `const` as a whole and validation constraints by whether
+    /// they were tightened or loosened. Subschemas are compared
+    /// recursively: those of `allOf`, `anyOf` and `oneOf` regardless of
+    /// order, and tuples such as `prefixItems` by index. Any other keyword is
+    /// diffed as plain JSON.
+    fn compare_schemas(&mut self, old: &'a Value, new: &'a Value, path: &Location) {
+        match (old, new) {
+            (Value::Object(_), Value::Object(_)) => {
+                let (old_keywords, old_target) = self.old.keywords(old);
+                let (new_keywords, new_target) = self.new.keywords(new);
+                if old_target.is_none() && new_target.is_none() {
+                    self.compare_keywords(&old_keywords, &new_keywords, path);
+                    return;
+                }
+
+                let pair = (old_target.unwrap_or(old) as *const Value, new_target.unwrap_or(new) as *const Value);
+                if self.active.contains(&pair) {
+                    return;
+                }
+                self.active.push(pair);
+                self.compare_keywords(&old_keywords, &new_keywords, path);
+                self.active.pop();
+            }
+            (Value::Array(old_arr), Value::Array(new_arr)) => {
+                self.compare_arrays(old_arr, new_arr, path, Self::compare_schemas);
+            }
+            _ => self.compare_values(old, new, path),
+        }
+    }
+
+    /// Compares two JSON values without schema semantics
+    fn compare_values(&mut self, old: &'a Value, new: &'a Value, path: &Location) {
         match (old, new) {
             (Value::Object(old_obj), Value::Object(new_obj)) => {
-                self.compare_objects(old_obj, new_obj, path, changes);
+                self.compare_objects(old_obj, new_obj, path);
             }
             (Value::Array(old_arr), Value::Array(new_arr)) => {
-                self.compare_arrays(old_arr, new_arr, path, changes);
+                self.compare_arrays(old_arr, new_arr, path, Self::compare_values);
             }
             _ if old != new => {
                 let mut metadata = HashMap::new();
                 metadata.insert("old_value".to_string(), old.to_string());
                 metadata.insert("new_value".to_string(), new.to_string());
                 
-                changes.push(SchemaChange::new(
-                    ChangeType::Modification,
-                    path.to_string(),
+                self.changes.push(SchemaChange::from_kind(
+                    ChangeKind::ValueChanged,
+                    path.clone(),
                     format!("Value changed from {:?} to {:?}", old, new),
                     metadata,
                 ));
@@ -101,134 +222,419 @@ impl JsonSchemaAnalyzer {
         }
     }
 
-    fn calculate_compatibility_score(&self, changes: &[SchemaChange]) -> u8 {
-        let base_score: u8 = 100;
-        let mut deductions: u8 = 0;
-        
-        for change in changes {
-            match change.change_type {
-                ChangeType::Addition => deductions = deductions.saturating_add(5),
-                ChangeType::Removal => deductions = deductions.saturating_add(20),
-                ChangeType::Modification => deductions = deductions.saturating_add(10),
-                ChangeType::Rename => deductions = deductions.saturating_add(8),
+    /// Compares the keywords of two schema objects
+    fn compare_keywords(&mut self, old: &Keywords<'a>, new: &Keywords<'a>, path: &Location) {
+        self.compare_properties(old, new, path);
+        self.compare_required(old, new, path);
+        self.compare_types(old.get("type").copied(), new.get("type").copied(), &path.child("type"));
+        self.compare_definitions(old, new, path);
+        self.compare_constraints(old, new, path);
+
+        for (&key, &old_value) in old.iter() {
+            if SEMANTIC_KEYWORDS.contains(&key) || CONSTRAINT_KEYWORDS.contains(&key) {
+                continue;
             }
-        }
-        
-        base_score.saturating_sub(deductions)
-    }
+            let location = path.child(key);
+            match (key, new.get(key).copied()) {
+                ("enum", Some(new_value)) => self.compare_enums(old_value, new_value, &location),
+                ("const", Some(new_value)) if old_value != new_value => {
+                    let mut metadata = HashMap::new();
+                    metadata.insert("old_value".to_string(), old_value.to_string());
+                    metadata.insert("new_value".to_string(), new_value.to_string());
 
-    #[allow(dead_code)]
-    fn detect_schema_changes(&self, path: &str, old_schema: &Value, new_schema: &Value, changes: &mut Vec<SchemaChange>) {
-        match (old_schema, new_schema) {
-            (Value::Object(old_obj), Value::Object(new_obj)) => {
-                // Compare properties
-                for (key, old_value) in old_obj {
-                    if let Some(new_value) = new_obj.get(key) {
-                        if old_value != new_value {
-                            let mut metadata = HashMap::new();
-                            metadata.insert("property".to_string(), key.clone());
-                            
-                            changes.push(SchemaChange::new(
-                                ChangeType::Modification,
-                                format!("{}/{}", path, key),
-                                format!("Property '{}' was modified", key),
-                                metadata,
-                            ));
-                        }
-                    } else {
-                        let mut metadata = HashMap::new();
-                        metadata.insert("property".to_string(), key.clone());
-                        
-                        changes.push(SchemaChange::new(
-                            ChangeType::Removal,
-                            format!("{}/{}", path, key),
-                            format!("Property '{}' was removed", key),
-                            metadata,
-                        ));
+                    self.changes.push(SchemaChange::from_kind(
+                        ChangeKind::ConstChanged,
+                        location,
+                        format!("Constant changed from {} to {}", old_value, new_value),
+                        metadata,
+                    ));
+                }
+                ("const", Some(_)) => {}
+                (key, Some(new_value)) if SCHEMA_MAP_KEYWORDS.contains(&key) => match (old.members(key), new.members(key)) {
+                    (Some(old_map), Some(new_map)) => {
+                        self.compare_members(&old_map, &new_map, &location);
+                    }
+                    _ => self.compare_values(old_value, new_value, &location),
+                },
+                (key, Some(new_value)) if APPLICATOR_KEYWORDS.contains(&key) => match (old_value, new_value) {
+                    (Value::Array(old_schemas), Value::Array(new_schemas)) => {
+                        self.compare_applicators(key, old_schemas, new_schemas, &location);
                     }
+                    _ => self.compare_schemas(old_value, new_value, &location),
+                },
+                (key, Some(new_value)) if SUBSCHEMA_KEYWORDS.contains(&key) => {
+                    self.compare_schemas(old_value, new_value, &location);
                 }
+                (_, Some(new_value)) => self.compare_values(old_value, new_value, &location),
+                (_, None) => self.changes.push(SchemaChange::from_kind(
+                    ChangeKind::KeywordRemoved,
+                    location,
+                    format!("Property '{}' was removed", key),
+                    element_metadata(key, old_value),
+                )),
+            }
+        }
 
-                // Check for new properties
-                for key in new_obj.keys() {
-                    if !old_obj.contains_key(key) {
-                        let mut metadata = HashMap::new();
-                        metadata.insert("property".to_string(), key.clone());
-                        
-                        changes.push(SchemaChange::new(
-                            ChangeType::Addition,
-                            format!("{}/{}", path, key),
-                            format!("New property '{}' was added", key),
-                            metadata,
-                        ));
-                    }
+        for (&key, &new_value) in new.iter() {
+            if !old.contains_key(key) && !SEMANTIC_KEYWORDS.contains(&key) && !CONSTRAINT_KEYWORDS.contains(&key) {
+                self.changes.push(SchemaChange::from_kind(
+                    ChangeKind::KeywordAdded,
+                    path.child(key),
+                    format!("New property '{}' was added", key),
+                    element_metadata(key, new_value),
+                ));
+            }
+        }
+    }
+
+    /// Compares the `properties` of two schema objects
+    ///
+    /// A missing `properties` keyword counts as declaring no properties.
+    /// Properties added as required are reported as `RequiredPropertyAdded`.
+    fn compare_properties(&mut self, old: &Keywords<'a>, new: &Keywords<'a>, path: &Location) {
+        let (old_props, new_props) = (properties(old), properties(new));
+        let required = required(new);
+        let path = path.child("properties");
+
+        for (&name, &old_value) in old_props.iter().flatten() {
+            match new_props.as_ref().and_then(|props| props.get(name)) {
+                Some(&new_value) => self.compare_schemas(old_value, new_value, &path.child(name)),
+                None => self.changes.push(SchemaChange::from_kind(
+                    ChangeKind::PropertyRemoved,
+                    path.child(name),
+                    format!("Property '{}' was removed", name),
+                    element_metadata(name, old_value),
+                )),
+            }
+        }
+
+        for (&name, &new_value) in new_props.iter().flatten() {
+            if old_props.as_ref().is_some_and(|props| props.contains_key(name)) {
+                continue;
+            }
+            let (kind, description) = if required.contains(name) {
+                (ChangeKind::RequiredPropertyAdded, format!("New required property '{}' was added", name))
+            } else {
+                (ChangeKind::PropertyAdded, format!("New property '{}' was added", name))
+            };
+            self.changes.push(SchemaChange::from_kind(kind, path.child(name), description, element_metadata(name, new_value)));
+        }
+    }
+
+    /// Compares which properties two schema objects require
+    ///
+    /// Properties that were added or removed are left to
+    /// [`Comparison::compare_properties`]. Changes are located at the
+    /// property, or at `required` if it is not declared in `properties`.
+    fn compare_required(&mut self, old: &Keywords<'a>, new: &Keywords<'a>, path: &Location) {
+        let (old_required, new_required) = (required(old), required(new));
+        let declared = |keywords: &Keywords<'a>, name: &str| properties(keywords).is_some_and(|props| props.contains_key(name));
+
+        let became_required = new_required.difference(&old_required).map(|name| (name, ChangeKind::PropertyBecameRequired, "required"));
+        let became_optional = old_required.difference(&new_required).map(|name| (name, ChangeKind::PropertyBecameOptional, "optional"));
+        for (name, kind, status) in became_required.chain(became_optional) {
+            let (in_old, in_new) = (declared(old, name), declared(new, name));
+            if in_old != in_new {
+                continue;
+            }
+
+            let location = if in_new { path.child("properties").child(*name) } else { path.child("required") };
+            let mut metadata = HashMap::new();
+            metadata.insert("property".to_string(), name.to_string());
+
+            self.changes.push(SchemaChange::from_kind(
+                kind,
+                location,
+                format!("Property '{}' became {}", name, status),
+                metadata,
+            ));
+        }
+    }
+
+    /// Compares the `type` keywords of two schemas by the values they accept
+    ///
+    /// A missing `type` accepts every value, and `number` includes `integer`,
+    /// so e.g. `["integer", "number"]` and `"number"` are not a change.
+    fn compare_types(&mut self, old: Option<&Value>, new: Option<&Value>, location: &Location) {
+        let (old_types, new_types) = (types(old), types(new));
+        let narrowed = accepts_all(old_types.as_ref(), new_types.as_ref());
+        let widened = accepts_all(new_types.as_ref(), old_types.as_ref());
+        if narrowed && widened {
+            return;
+        }
+
+        let kind = if narrowed {
+            ChangeKind::TypeNarrowed
+        } else if widened {
+            ChangeKind::TypeWidened
+        } else {
+            ChangeKind::TypeChanged
+        };
+        let (old_name, new_name) = (type_name(old_types.as_ref()), type_name(new_types.as_ref()));
+
+        let mut metadata = HashMap::new();
+        metadata.insert("old_type".to_string(), old_name.clone());
+        metadata.insert("new_type".to_string(), new_name.clone());
+
+        let verb = match kind {
+            ChangeKind::TypeNarrowed => "narrowed",
+            ChangeKind::TypeWidened => "widened",
+            _ => "changed",
+        };
+        self.changes.push(SchemaChange::from_kind(
+            kind,
+            location.clone(),
+            format!("Type {} from {} to {}", verb, old_name, new_name),
+            metadata,
+        ));
+    }
+
+    /// Compares two `enum` keywords value by value
+    fn compare_enums(&mut self, old: &'a Value, new: &'a Value, location: &Location) {
+        let (Value::Array(old_values), Value::Array(new_values)) = (old, new) else {
+            self.compare_values(old, new, location);
+            return;
+        };
+
+        let canonical = |values: &[Value]| values.iter().map(Value::to_string).collect::<HashSet<_>>();
+        let (old_set, new_set) = (canonical(old_values), canonical(new_values));
+        let removed = old_values.iter().filter(|value| !new_set.contains(&value.to_string())).map(|value| (value, ChangeKind::EnumValueRemoved, "removed"));
+        let added = new_values.iter().filter(|value| !old_set.contains(&value.to_string())).map(|value| (value, ChangeKind::EnumValueAdded, "added"));
+        for (value, kind, status) in removed.chain(added) {
+            let mut metadata = HashMap::new();
+            metadata.insert("value".to_string(), value.to_string());
+
+            self.changes.push(SchemaChange::from_kind(
+                kind,
+                location.clone(),
+                format!("Enum value {} was {}", value, status),
+                metadata,
+            ));
+        }
+    }
+
+    /// Compares the `$defs` and `definitions` of two schema objects
+    ///
+    /// Referenced definitions are compared where they are referenced, so only
+    /// definitions no reference points into are compared, added or removed
+    /// here. Definitions are matched by name whichever of the two keywords
+    /// declares them, so moving them from `definitions` to `$defs` is not a
+    /// change.
+    fn compare_definitions(&mut self, old: &Keywords<'a>, new: &Keywords<'a>, path: &Location) {
+        let (old_defs, new_defs) = (definitions(old), definitions(new));
+
+        for (name, &(keyword, old_def)) in &old_defs {
+            match new_defs.get(name) {
+                Some(&(keyword, new_def)) if !self.old.is_referenced(old_def) && !self.new.is_referenced(new_def) => {
+                    self.compare_schemas(old_def, new_def, &path.child(keyword).child(*name));
                 }
+                None if !self.old.is_referenced(old_def) => self.changes.push(SchemaChange::from_kind(
+                    ChangeKind::DefinitionRemoved,
+                    path.child(keyword).child(*name),
+                    format!("Definition '{}' was removed", name),
+                    element_metadata(name, old_def),
+                )),
+                _ => {}
             }
-            (old_val, new_val) if old_val != new_val => {
-                let mut metadata = HashMap::new();
-                metadata.insert("old_value".to_string(), old_val.to_string());
-                metadata.insert("new_value".to_string(), new_val.to_string());
-                
-                changes.push(SchemaChange::new(
-                    ChangeType::Modification,
-                    path.to_string(),
-                    format!("Value changed from {:?} to {:?}", old_val, new_val),
-                    metadata,
+        }
+
+        for (name, &(keyword, new_def)) in &new_defs {
+            if !old_defs.contains_key(name) && !self.new.is_referenced(new_def) {
+                self.changes.push(SchemaChange::from_kind(
+                    ChangeKind::DefinitionAdded,
+                    path.child(keyword).child(*name),
+                    format!("Definition '{}' was added", name),
+                    element_metadata(name, new_def),
                 ));
             }
-            _ => {}
         }
     }
 
-    fn compare_objects(&self, old_obj: &serde_json::Map<String, Value>, new_obj: &serde_json::Map<String, Value>, path: &str, changes: &mut Vec<SchemaChange>) {
+    /// Compares two `allOf`, `anyOf` or `oneOf` keywords regardless of order
+    ///
+    /// Identical subschemas are matched first, and the remaining ones are
+    /// compared pairwise in order. Each extra subschema tightens an `allOf`
+    /// and loosens an `anyOf`, and each missing one the reverse. A `oneOf`
+    /// rejects values matching several subschemas, so both its extra and its
+    /// missing subschemas are changes in either direction.
+    fn compare_applicators(&mut self, keyword: &str, old_schemas: &'a [Value], new_schemas: &'a [Value], path: &Location) {
+        let mut unmatched: Vec<(usize, &'a Value)> = new_schemas.iter().enumerate().collect();
+        let mut pairs = Vec::new();
+        let mut removed = Vec::new();
+        for (index, old_schema) in old_schemas.iter().enumerate() {
+            match unmatched.iter().position(|(_, new_schema)| *new_schema == old_schema) {
+                Some(position) => pairs.push((old_schema, unmatched.remove(position))),
+                None => removed.push((index, old_schema)),
+            }
+        }
+        let paired = removed.len().min(unmatched.len());
+        pairs.extend(removed.iter().map(|(_, old_schema)| *old_schema).zip(unmatched.drain(..paired)));
+
+        // Identical subschemas can still differ through the targets of their references
+        for (old_schema, (index, new_schema)) in pairs {
+            self.compare_schemas(old_schema, new_schema, &path.child(index.to_string()));
+        }
+
+        let (added_kind, removed_kind) = match keyword {
+            "allOf" => (ChangeKind::ConstraintTightened, ChangeKind::ConstraintLoosened),
+            "anyOf" => (ChangeKind::ConstraintLoosened, ChangeKind::ConstraintTightened),
+            _ => (ChangeKind::ConstraintChanged, ChangeKind::ConstraintChanged),
+        };
+        let removed = removed[paired..].iter().map(|&(index, schema)| (index, schema, removed_kind, "removed from"));
+        let added = unmatched.into_iter().map(|(index, schema)| (index, schema, added_kind, "added to"));
+        for (index, schema, kind, status) in removed.chain(added) {
+            let mut metadata = HashMap::new();
+            metadata.insert("keyword".to_string(), keyword.to_string());
+            metadata.insert("definition".to_string(), schema.to_string());
+
+            self.changes.push(SchemaChange::from_kind(
+                kind,
+                path.child(index.to_string()),
+                format!("Subschema was {} {}", status, keyword),
+                metadata,
+            ));
+        }
+    }
+
+    /// Compares two maps of named subschemas such as `patternProperties`
+    fn compare_members(&mut self, old_map: &Members<'a>, new_map: &Members<'a>, path: &Location) {
+        for (&name, &old_value) in old_map {
+            match new_map.get(name) {
+                Some(&new_value) => self.compare_schemas(old_value, new_value, &path.child(name)),
+                None => self.changes.push(SchemaChange::from_kind(
+                    ChangeKind::KeywordRemoved,
+                    path.child(name),
+                    format!("Property '{}' was removed", name),
+                    element_metadata(name, old_value),
+                )),
+            }
+        }
+
+        for (&name, &new_value) in new_map {
+            if !old_map.contains_key(name) {
+                self.changes.push(SchemaChange::from_kind(
+                    ChangeKind::KeywordAdded,
+                    path.child(name),
+                    format!("New property '{}' was added", name),
+                    element_metadata(name, new_value),
+                ));
+            }
+        }
+    }
+
+    fn compare_objects(&mut self, old_obj: &'a Map<String, Value>, new_obj: &'a Map<String, Value>, path: &Location) {
         // Compare properties
         for (key, old_value) in old_obj {
             if let Some(new_value) = new_obj.get(key) {
-                self.compare_schemas(old_value, new_value, &format!("{}/{}", path, key), changes);
+                self.compare_values(old_value, new_value, &path.child(key.as_str()));
             } else {
-                let mut metadata = HashMap::new();
-                metadata.insert("property".to_string(), key.clone());
-                
-                changes.push(SchemaChange::new(
-                    ChangeType::Removal,
-                    format!("{}/{}", path, key),
+                self.changes.push(SchemaChange::from_kind(
+                    ChangeKind::KeywordRemoved,
+                    path.child(key.as_str()),
                     format!("Property '{}' was removed", key),
-                    metadata,
+                    element_metadata(key, old_value),
                 ));
             }
         }
 
         // Check for new properties
-        for key in new_obj.keys() {
+        for (key, new_value) in new_obj {
             if !old_obj.contains_key(key) {
-                let mut metadata = HashMap::new();
-                metadata.insert("property".to_string(), key.clone());
-                
-                changes.push(SchemaChange::new(
-                    ChangeType::Addition,
-                    format!("{}/{}", path, key),
+                self.changes.push(SchemaChange::from_kind(
+                    ChangeKind::KeywordAdded,
+                    path.child(key.as_str()),
                     format!("New property '{}' was added", key),
-                    metadata,
+                    element_metadata(key, new_value),
                 ));
             }
         }
     }
 
-    fn compare_arrays(&self, old_arr: &[Value], new_arr: &[Value], path: &str, changes: &mut Vec<SchemaChange>) {
+    fn compare_arrays(&mut self, old_arr: &'a [Value], new_arr: &'a [Value], path: &Location, compare: fn(&mut Self, &'a Value, &'a Value, &Location)) {
         if old_arr.len() != new_arr.len() {
             let mut metadata = HashMap::new();
             metadata.insert("old_length".to_string(), old_arr.len().to_string());
             metadata.insert("new_length".to_string(), new_arr.len().to_string());
             
-            changes.push(SchemaChange::new(
-                ChangeType::Modification,
-                path.to_string(),
+            self.changes.push(SchemaChange::from_kind(
+                ChangeKind::ArrayLengthChanged,
+                path.clone(),
                 format!("Array length changed from {} to {}", old_arr.len(), new_arr.len()),
                 metadata,
             ));
         }
 
         for (i, (old_value, new_value)) in old_arr.iter().zip(new_arr.iter()).enumerate() {
-            self.compare_schemas(old_value, new_value, &format!("{}/{}", path, i), changes);
+            compare(self, old_value, new_value, &path.child(i.to_string()));
         }
     }
-} 
\ No newline at end of file
+}
+
+/// Returns the `properties` of a schema, if it declares any
+fn properties<'a>(keywords: &Keywords<'a>) -> Option<Members<'a>> {
+    keywords.members("properties")
+}
+
+/// Returns the definitions of a schema by name, with the keyword declaring each
+///
+/// `$defs` takes precedence over `definitions` for names declared in both.
+fn definitions<'a>(keywords: &Keywords<'a>) -> BTreeMap<&'a str, (&'static str, &'a Value)> {
+    let mut definitions = BTreeMap::new();
+    for keyword in DEFINITION_KEYWORDS {
+        for (name, definition) in keywords.members(keyword).into_iter().flatten() {
+            definitions.entry(name).or_insert((keyword, definition));
+        }
+    }
+    definitions
+}
+
+/// Returns the names listed in the `required` keyword of a schema
+fn required<'a>(keywords: &Keywords<'a>) -> BTreeSet<&'a str> {
+    keywords.names("required")
+}
+
+/// Returns the types a `type` keyword allows, or `None` for any type
+fn types(value: Option<&Value>) -> Option<BTreeSet<&str>> {
+    match value? {
+        Value::String(name) => Some(BTreeSet::from([name.as_str()])),
+        Value::Array(names) => Some(names.iter().filter_map(Value::as_str).collect()),
+        _ => None,
+    }
+}
+
+/// Checks whether every value of the `inner` types is one of the `outer` types
+fn accepts_all(outer: Option<&BTreeSet<&str>>, inner: Option<&BTreeSet<&str>>) -> bool {
+    match (outer, inner) {
+        (None, _) => true,
+        (Some(_), None) => false,
+        (Some(outer), Some(inner)) => inner
+            .iter()
+            .all(|name| outer.contains(name) || (*name == "integer" && outer.contains("number"))),
+    }
+}
+
+/// Renders allowed types for descriptions, e.g. `integer` or `[null, string]`
+fn type_name(types: Option<&BTreeSet<&str>>) -> String {
+    match types {
+        None => "any".to_string(),
+        Some(types) if types.len() == 1 => types.iter().next().unwrap_or(&"any").to_string(),
+        Some(types) => format!("[{}]", types.iter().copied().collect::<Vec<_>>().join(", ")),
+    }
+}
+
+/// Builds the metadata describing an added or removed member
+///
+/// Records the member's `type` keyword, if any, and its canonical JSON
+/// `definition` so renamed members can be recognized.
+fn element_metadata(key: &str, value: &Value) -> HashMap<String, String> {
+    let mut metadata = HashMap::new();
+    metadata.insert("property".to_string(), key.to_string());
+    metadata.insert("definition".to_string(), value.to_string());
+    if let Some(schema_type) = value.get("type") {
+        metadata.insert("type".to_string(), schema_type.to_string());
+    }
+    metadata
+}
+
+#[cfg(test)]
+mod tests;
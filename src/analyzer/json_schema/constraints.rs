@@ -0,0 +1,227 @@
+//! Comparison of JSON Schema validation constraints
+//!
+//! Constraints such as `maxLength` or `minimum` are classified by whether the
+//! new schema accepts fewer values (tightened), more values (loosened), or a
+//! different set of values (changed). A missing constraint accepts every
+//! value, so adding one tightens and removing one loosens.
+
+use super::Comparison;
+use super::refs::Keywords;
+use crate::analyzer::{ChangeKind, SchemaChange};
+use crate::location::Location;
+use serde_json::Value;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+/// Keywords compared as constraints
+pub(super) const CONSTRAINT_KEYWORDS: [&str; 13] = [
+    "minimum",
+    "maximum",
+    "exclusiveMinimum",
+    "exclusiveMaximum",
+    "multipleOf",
+    "minLength",
+    "maxLength",
+    "pattern",
+    "minItems",
+    "maxItems",
+    "uniqueItems",
+    "minProperties",
+    "maxProperties",
+];
+
+/// Numeric bounds as inclusive keyword, exclusive keyword and whether they are upper bounds
+const NUMERIC_BOUNDS: [(&str, &str, bool); 2] = [("minimum", "exclusiveMinimum", false), ("maximum", "exclusiveMaximum", true)];
+
+/// Length and count limits and whether they are upper limits
+const COUNT_LIMITS: [(&str, bool); 6] = [
+    ("minLength", false),
+    ("maxLength", true),
+    ("minItems", false),
+    ("maxItems", true),
+    ("minProperties", false),
+    ("maxProperties", true),
+];
+
+/// Effective bound of a schema in one direction
+#[derive(Debug, Clone, Copy)]
+struct Bound {
+    keyword: &'static str,
+    value: f64,
+    exclusive: bool,
+}
+
+impl Bound {
+    /// Orders two bounds by how many values they accept, fewest first
+    fn tightness(&self, other: &Bound, upper: bool) -> Ordering {
+        let by_value = if upper { self.value.total_cmp(&other.value) } else { other.value.total_cmp(&self.value) };
+        by_value.then(other.exclusive.cmp(&self.exclusive))
+    }
+
+    fn render(bound: Option<&Bound>) -> String {
+        match bound {
+            None => "none".to_string(),
+            Some(bound) if bound.exclusive => format!("{} (exclusive)", bound.value),
+            Some(bound) => bound.value.to_string(),
+        }
+    }
+}
+
+impl<'a> Comparison<'a> {
+    /// Compares the constraints of two schema objects
+    pub(super) fn compare_constraints(&mut self, old: &Keywords<'a>, new: &Keywords<'a>, path: &Location) {
+        for (inclusive, exclusive, upper) in NUMERIC_BOUNDS {
+            let (old_bound, new_bound) = (numeric_bound(old, inclusive, exclusive, upper), numeric_bound(new, inclusive, exclusive, upper));
+            self.compare_bounds(old_bound, new_bound, upper, path);
+        }
+
+        for (keyword, upper) in COUNT_LIMITS {
+            let (old_bound, new_bound) = (count_limit(old, keyword, upper), count_limit(new, keyword, upper));
+            self.compare_bounds(old_bound, new_bound, upper, path);
+        }
+
+        if let Some(kind) = multiple_of_change(number(old, "multipleOf"), number(new, "multipleOf")) {
+            self.push_constraint(kind, "multipleOf", old, new, path);
+        }
+
+        let (old_pattern, new_pattern) = (old.get("pattern").copied(), new.get("pattern").copied());
+        let pattern_change = match (old_pattern, new_pattern) {
+            (None, Some(_)) => Some(ChangeKind::ConstraintTightened),
+            (Some(_), None) => Some(ChangeKind::ConstraintLoosened),
+            (Some(old_pattern), Some(new_pattern)) if old_pattern != new_pattern => Some(ChangeKind::ConstraintChanged),
+            _ => None,
+        };
+        if let Some(kind) = pattern_change {
+            self.push_constraint(kind, "pattern", old, new, path);
+        }
+
+        let unique = |keywords: &Keywords<'a>| keywords.get("uniqueItems").and_then(|value| value.as_bool()).unwrap_or(false);
+        match (unique(old), unique(new)) {
+            (false, true) => self.push_constraint(ChangeKind::ConstraintTightened, "uniqueItems", old, new, path),
+            (true, false) => self.push_constraint(ChangeKind::ConstraintLoosened, "uniqueItems", old, new, path),
+            _ => {}
+        }
+    }
+
+    /// Classifies and records the change between two bounds
+    fn compare_bounds(&mut self, old: Option<Bound>, new: Option<Bound>, upper: bool, path: &Location) {
+        let kind = match (&old, &new) {
+            (None, None) => return,
+            (None, Some(_)) => ChangeKind::ConstraintTightened,
+            (Some(_), None) => ChangeKind::ConstraintLoosened,
+            (Some(old), Some(new)) => match new.tightness(old, upper) {
+                Ordering::Less => ChangeKind::ConstraintTightened,
+                Ordering::Greater => ChangeKind::ConstraintLoosened,
+                Ordering::Equal => return,
+            },
+        };
+        let Some(keyword) = new.or(old).map(|bound| bound.keyword) else {
+            return;
+        };
+        let (old_value, new_value) = (Bound::render(old.as_ref()), Bound::render(new.as_ref()));
+
+        let mut metadata = HashMap::new();
+        metadata.insert("keyword".to_string(), keyword.to_string());
+        metadata.insert("old_value".to_string(), old_value.clone());
+        metadata.insert("new_value".to_string(), new_value.clone());
+
+        self.changes.push(SchemaChange::from_kind(
+            kind,
+            path.child(keyword),
+            format!("Constraint {} {} from {} to {}", keyword, verb(kind), old_value, new_value),
+            metadata,
+        ));
+    }
+
+    /// Records a change to a constraint whose values are compared as a whole
+    fn push_constraint(&mut self, kind: ChangeKind, keyword: &str, old: &Keywords<'a>, new: &Keywords<'a>, path: &Location) {
+        let render = |keywords: &Keywords<'a>| keywords.get(keyword).map_or("none".to_string(), |value| value.to_string());
+        let (old_value, new_value) = (render(old), render(new));
+
+        let mut metadata = HashMap::new();
+        metadata.insert("keyword".to_string(), keyword.to_string());
+        metadata.insert("old_value".to_string(), old_value.clone());
+        metadata.insert("new_value".to_string(), new_value.clone());
+
+        self.changes.push(SchemaChange::from_kind(
+            kind,
+            path.child(keyword),
+            format!("Constraint {} {} from {} to {}", keyword, verb(kind), old_value, new_value),
+            metadata,
+        ));
+    }
+}
+
+/// Returns the effective numeric bound of a schema in one direction
+///
+/// When both the inclusive and exclusive keyword are present, the tighter
+/// one applies. A boolean exclusive keyword (draft-04) makes the inclusive
+/// keyword exclusive.
+fn numeric_bound(keywords: &Keywords<'_>, inclusive: &'static str, exclusive: &'static str, upper: bool) -> Option<Bound> {
+    let (limit, exclusive_limit) = (number(keywords, inclusive), keywords.get(exclusive).copied());
+    let inclusive_bound = limit.map(|value| Bound {
+        keyword: inclusive,
+        value,
+        exclusive: exclusive_limit == Some(&Value::Bool(true)),
+    });
+    let exclusive_bound = exclusive_limit.and_then(Value::as_f64).map(|value| Bound { keyword: exclusive, value, exclusive: true });
+
+    match (inclusive_bound, exclusive_bound) {
+        (Some(a), Some(b)) if b.tightness(&a, upper) == Ordering::Less => Some(b),
+        (Some(a), _) => Some(a),
+        (None, b) => b,
+    }
+}
+
+/// Returns the length or count limit of a schema, treating a minimum of zero as none
+fn count_limit(keywords: &Keywords<'_>, keyword: &'static str, upper: bool) -> Option<Bound> {
+    number(keywords, keyword)
+        .filter(|value| upper || *value > 0.0)
+        .map(|value| Bound { keyword, value, exclusive: false })
+}
+
+/// Classifies a `multipleOf` change by whether one divisor is a multiple of the other
+fn multiple_of_change(old: Option<f64>, new: Option<f64>) -> Option<ChangeKind> {
+    let is_multiple = |value: f64, divisor: f64| {
+        let ratio = value / divisor;
+        (ratio - ratio.round()).abs() < 1e-9
+    };
+    match (old, new) {
+        (None, Some(_)) => Some(ChangeKind::ConstraintTightened),
+        (Some(_), None) => Some(ChangeKind::ConstraintLoosened),
+        (Some(old), Some(new)) if old == new => None,
+        (Some(old), Some(new)) if is_multiple(new, old) => Some(ChangeKind::ConstraintTightened),
+        (Some(old), Some(new)) if is_multiple(old, new) => Some(ChangeKind::ConstraintLoosened),
+        (Some(_), Some(_)) => Some(ChangeKind::ConstraintChanged),
+        (None, None) => None,
+    }
+}
+
+/// Picks the tighter of two values of a bound or limit that both apply to a schema
+///
+/// # Returns
+/// The value accepting fewer values, or `None` if `keyword` is not a numeric
+/// bound or a count limit or either value is not a number
+pub(super) fn tighter<'v>(keyword: &str, first: &'v Value, second: &'v Value) -> Option<&'v Value> {
+    let upper = NUMERIC_BOUNDS
+        .iter()
+        .find(|(inclusive, exclusive, _)| keyword == *inclusive || keyword == *exclusive)
+        .map(|&(_, _, upper)| upper)
+        .or_else(|| COUNT_LIMITS.iter().find(|(limit, _)| keyword == *limit).map(|&(_, upper)| upper))?;
+    let (first_value, second_value) = (first.as_f64()?, second.as_f64()?);
+
+    let second_is_tighter = if upper { second_value < first_value } else { second_value > first_value };
+    Some(if second_is_tighter { second } else { first })
+}
+
+fn number(keywords: &Keywords<'_>, keyword: &str) -> Option<f64> {
+    keywords.get(keyword).and_then(|value| value.as_f64())
+}
+
+fn verb(kind: ChangeKind) -> &'static str {
+    match kind {
+        ChangeKind::ConstraintTightened => "tightened",
+        ChangeKind::ConstraintLoosened => "loosened",
+        _ => "changed",
+    }
+}
@@ -0,0 +1,193 @@
+//! JSON Schema drafts and conversion between their keyword forms
+//!
+//! Drafts spell some constraints differently: draft-04 marks exclusive
+//! bounds with booleans and identifies resources with `id`, drafts before
+//! 2020-12 declare tuples with an `items` array, and drafts before 2019-09
+//! combine `dependentRequired` and `dependentSchemas` into `dependencies`.
+//! [`convert`] rewrites a document into the forms of another draft, so two
+//! documents can be compared keyword by keyword across a draft upgrade.
+
+use super::{DEFINITION_KEYWORDS, SCHEMA_MAP_KEYWORDS, SUBSCHEMA_KEYWORDS};
+use serde_json::{Map, Value};
+
+/// Inclusive bound keywords and their exclusive counterparts
+const BOUNDS: [(&str, &str); 2] = [("maximum", "exclusiveMaximum"), ("minimum", "exclusiveMinimum")];
+
+/// A JSON Schema specification draft
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(super) enum Draft {
+    Draft4,
+    Draft6,
+    Draft7,
+    Draft2019_09,
+    Draft2020_12,
+}
+
+impl Draft {
+    /// Detects the draft a document declares in its `$schema` keyword
+    ///
+    /// # Returns
+    /// The declared draft, or `None` if `$schema` is missing or unknown
+    pub(super) fn detect(document: &Value) -> Option<Self> {
+        let uri = document.get("$schema")?.as_str()?;
+        [
+            ("draft-04", Draft::Draft4),
+            ("draft-06", Draft::Draft6),
+            ("draft-07", Draft::Draft7),
+            ("2019-09", Draft::Draft2019_09),
+            ("2020-12", Draft::Draft2020_12),
+        ]
+        .into_iter()
+        .find(|(name, _)| uri.contains(name))
+        .map(|(_, draft)| draft)
+    }
+
+    /// Returns the keyword identifying a schema resource
+    pub(super) fn id_keyword(self) -> &'static str {
+        if self == Draft::Draft4 { "id" } else { "$id" }
+    }
+
+    /// Checks whether keywords next to a `$ref` are ignored
+    fn ref_overrides_siblings(self) -> bool {
+        self <= Draft::Draft7
+    }
+
+    /// Checks whether tuples are declared with an `items` array
+    fn has_tuple_items(self) -> bool {
+        self <= Draft::Draft2019_09
+    }
+
+    /// Checks whether `dependencies` is split into two keywords
+    fn has_split_dependencies(self) -> bool {
+        self >= Draft::Draft2019_09
+    }
+}
+
+/// Rewrites a document written for one draft into the forms of another
+///
+/// Keywords ignored by the source draft, such as siblings of a draft-07
+/// `$ref`, are dropped. Forms that have no equivalent in the target draft
+/// are left unchanged.
+pub(super) fn convert(document: &mut Value, from: Draft, to: Draft) {
+    let Value::Object(schema) = document else {
+        return;
+    };
+
+    if from.ref_overrides_siblings() && schema.contains_key("$ref") {
+        schema.retain(|key, _| key == "$ref" || key == "definitions" || key == "$defs");
+    }
+    rename(schema, from.id_keyword(), to.id_keyword());
+    convert_bounds(schema, from, to);
+    convert_tuple(schema, from, to);
+    convert_dependencies(schema, from, to);
+
+    for (key, value) in schema.iter_mut() {
+        if SUBSCHEMA_KEYWORDS.contains(&key.as_str()) {
+            match value {
+                Value::Array(items) => items.iter_mut().for_each(|item| convert(item, from, to)),
+                value => convert(value, from, to),
+            }
+        } else if key == "properties" || DEFINITION_KEYWORDS.contains(&key.as_str()) || SCHEMA_MAP_KEYWORDS.contains(&key.as_str()) {
+            if let Value::Object(members) = value {
+                // `dependencies` also lists required property names
+                members.values_mut().filter(|member| !member.is_array()).for_each(|member| convert(member, from, to));
+            }
+        }
+    }
+}
+
+/// Moves a keyword to a new name, unless the new name is already taken
+fn rename(schema: &mut Map<String, Value>, from: &str, to: &str) {
+    if from != to && !schema.contains_key(to) {
+        if let Some(value) = schema.remove(from) {
+            schema.insert(to.to_string(), value);
+        }
+    }
+}
+
+/// Converts between boolean (draft-04) and numeric exclusive bounds
+fn convert_bounds(schema: &mut Map<String, Value>, from: Draft, to: Draft) {
+    for (bound, exclusive) in BOUNDS {
+        match (from == Draft::Draft4, to == Draft::Draft4) {
+            (true, false) => match schema.get(exclusive) {
+                Some(Value::Bool(true)) => {
+                    if let Some(limit) = schema.remove(bound) {
+                        schema.insert(exclusive.to_string(), limit);
+                    }
+                }
+                Some(Value::Bool(false)) => {
+                    schema.remove(exclusive);
+                }
+                _ => {}
+            },
+            (false, true) if schema.get(exclusive).is_some_and(Value::is_number) && !schema.contains_key(bound) => {
+                if let Some(limit) = schema.insert(exclusive.to_string(), Value::Bool(true)) {
+                    schema.insert(bound.to_string(), limit);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Converts between `items` array tuples and `prefixItems`
+fn convert_tuple(schema: &mut Map<String, Value>, from: Draft, to: Draft) {
+    match (from.has_tuple_items(), to.has_tuple_items()) {
+        (true, false) => {
+            if !schema.get("items").is_some_and(Value::is_array) {
+                // `additionalItems` only applies after an `items` array
+                schema.remove("additionalItems");
+                return;
+            }
+            if let Some(prefix) = schema.remove("items") {
+                schema.insert("prefixItems".to_string(), prefix);
+            }
+            if let Some(rest) = schema.remove("additionalItems") {
+                schema.insert("items".to_string(), rest);
+            }
+        }
+        (false, true) => {
+            let Some(prefix) = schema.remove("prefixItems") else {
+                return;
+            };
+            if let Some(rest) = schema.insert("items".to_string(), prefix) {
+                schema.insert("additionalItems".to_string(), rest);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Converts between `dependencies` and `dependentRequired`/`dependentSchemas`
+fn convert_dependencies(schema: &mut Map<String, Value>, from: Draft, to: Draft) {
+    match (from.has_split_dependencies(), to.has_split_dependencies()) {
+        (false, true) => {
+            let Some(Value::Object(dependencies)) = schema.remove("dependencies") else {
+                return;
+            };
+            for (name, dependency) in dependencies {
+                let keyword = if dependency.is_array() { "dependentRequired" } else { "dependentSchemas" };
+                if let Value::Object(split) = schema.entry(keyword).or_insert_with(|| Value::Object(Map::new())) {
+                    split.entry(name).or_insert(dependency);
+                }
+            }
+        }
+        (true, false) => {
+            let mut dependencies = Map::new();
+            for keyword in ["dependentRequired", "dependentSchemas"] {
+                if let Some(Value::Object(split)) = schema.remove(keyword) {
+                    dependencies.extend(split);
+                }
+            }
+            if dependencies.is_empty() {
+                return;
+            }
+            if let Value::Object(existing) = schema.entry("dependencies").or_insert_with(|| Value::Object(Map::new())) {
+                for (name, dependency) in dependencies {
+                    existing.entry(name).or_insert(dependency);
+                }
+            }
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,267 @@
+//! Local `$ref` resolution for JSON Schema documents
+//!
+//! [`Document`] indexes the schema resources (`$id`, or `id` in draft-04),
+//! anchors (`$anchor` and plain-name id fragments) and references of a document once, so the
+//! analyzer can follow references while comparing without re-walking it.
+//! References are resolved against the base URI of the subschema they appear
+//! in; references to other documents are left unresolved.
+
+use super::constraints::tighter;
+use serde_json::Value;
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::ops::Deref;
+
+/// Keywords that belong to a reference target's resource rather than its schema
+const RESOURCE_KEYWORDS: [&str; 6] = ["$id", "id", "$anchor", "$schema", "$defs", "definitions"];
+
+/// Subschemas of a keyword such as `properties`, keyed by name
+pub(super) type Members<'a> = BTreeMap<&'a str, &'a Value>;
+
+/// Keywords of a schema with its references followed
+///
+/// Dereferences to the value of each keyword. Keywords holding a map of
+/// subschemas or a list of names are also kept as declared along the `$ref`
+/// chain, so they can be merged by [`Keywords::members`] and
+/// [`Keywords::names`].
+#[derive(Default)]
+pub(super) struct Keywords<'a> {
+    /// Value of each keyword
+    values: BTreeMap<&'a str, &'a Value>,
+    /// Every declaration of each keyword, starting next to the `$ref`
+    declarations: BTreeMap<&'a str, Vec<&'a Value>>,
+}
+
+impl<'a> Keywords<'a> {
+    /// Returns the members of an object-valued keyword such as `properties`
+    ///
+    /// Members declared next to a `$ref` and in its target are merged, and of
+    /// a member declared on both the one next to the `$ref` is kept.
+    ///
+    /// # Returns
+    /// The members by name, or `None` if no declaration is an object
+    pub(super) fn members(&self, keyword: &str) -> Option<Members<'a>> {
+        let mut maps = self.declarations.get(keyword)?.iter().filter_map(|value| value.as_object()).peekable();
+        maps.peek()?;
+        let mut members = Members::new();
+        for map in maps {
+            for (name, value) in map {
+                members.entry(name.as_str()).or_insert(value);
+            }
+        }
+        Some(members)
+    }
+
+    /// Returns the union of the names listed by a keyword such as `required`
+    pub(super) fn names(&self, keyword: &str) -> BTreeSet<&'a str> {
+        self.declarations
+            .get(keyword)
+            .into_iter()
+            .flatten()
+            .filter_map(|value| value.as_array())
+            .flatten()
+            .filter_map(Value::as_str)
+            .collect()
+    }
+}
+
+impl<'a> Deref for Keywords<'a> {
+    type Target = BTreeMap<&'a str, &'a Value>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.values
+    }
+}
+
+/// Reference index of a JSON Schema document
+pub(super) struct Document<'a> {
+    /// Target of every resolvable `$ref`, keyed by the address of the object holding it
+    targets: HashMap<*const Value, &'a Value>,
+    /// Addresses of every reference target and all its enclosing values
+    referenced: HashSet<*const Value>,
+}
+
+impl<'a> Document<'a> {
+    /// Indexes the references of a document
+    ///
+    /// # Arguments
+    /// * `root` - The document to index
+    /// * `id_keyword` - The keyword identifying schema resources in the document's draft
+    pub(super) fn new(root: &'a Value, id_keyword: &'static str) -> Self {
+        let mut index = Index {
+            id_keyword,
+            ..Index::default()
+        };
+        index.resources.insert(String::new(), String::new());
+        index.walk(root, String::new(), String::new());
+
+        let mut document = Self {
+            targets: HashMap::new(),
+            referenced: HashSet::new(),
+        };
+        for (holder, base, reference) in &index.references {
+            let Some(pointer) = index.resolve(base, reference) else {
+                continue;
+            };
+            let Some(target) = root.pointer(&pointer) else {
+                continue;
+            };
+
+            document.targets.insert(*holder, target);
+            let mut value = root;
+            document.referenced.insert(value);
+            for token in pointer.split('/').skip(1) {
+                let token = token.replace("~1", "/").replace("~0", "~");
+                let child = match value {
+                    Value::Object(object) => object.get(&token),
+                    Value::Array(items) => token.parse::<usize>().ok().and_then(|index| items.get(index)),
+                    _ => None,
+                };
+                let Some(child) = child else {
+                    break;
+                };
+                value = child;
+                document.referenced.insert(value);
+            }
+        }
+        document
+    }
+
+    /// Returns the target of a schema's `$ref`, if it resolves within the document
+    pub(super) fn target(&self, schema: &Value) -> Option<&'a Value> {
+        self.targets.get(&(schema as *const Value)).copied()
+    }
+
+    /// Checks whether a reference points at or into a value of the document
+    pub(super) fn is_referenced(&self, value: &Value) -> bool {
+        self.referenced.contains(&(value as *const Value))
+    }
+
+    /// Collects the keywords of a schema object, following its references
+    ///
+    /// Keywords next to a `$ref` apply together with those of its target, as
+    /// in draft 2019-09 and later: of a bound or limit declared on both, such
+    /// as `maxLength`, the tighter value is kept, maps of subschemas such as
+    /// `properties` are merged and the names listed by `required` are united,
+    /// and of other keywords the one next to the `$ref` is kept. A `$ref` that
+    /// cannot be resolved is kept as a plain keyword.
+    ///
+    /// # Returns
+    /// The keywords and the first followed target, if any
+    pub(super) fn keywords(&self, schema: &'a Value) -> (Keywords<'a>, Option<&'a Value>) {
+        let mut keywords = Keywords::default();
+        let mut followed: Vec<&'a Value> = Vec::new();
+        let mut current = schema;
+
+        while let Value::Object(object) = current {
+            let target = self.target(current);
+            for (key, value) in object {
+                if (key == "$ref" && target.is_some()) || (!followed.is_empty() && RESOURCE_KEYWORDS.contains(&key.as_str())) {
+                    continue;
+                }
+                keywords.declarations.entry(key.as_str()).or_default().push(value);
+                match keywords.values.entry(key.as_str()) {
+                    Entry::Vacant(entry) => {
+                        entry.insert(value);
+                    }
+                    Entry::Occupied(mut entry) => {
+                        if let Some(tighter) = tighter(key, entry.get(), value) {
+                            entry.insert(tighter);
+                        }
+                    }
+                }
+            }
+
+            match target {
+                Some(target) if !std::ptr::eq(target, schema) && !followed.iter().any(|value| std::ptr::eq(*value, target)) => {
+                    followed.push(target);
+                    current = target;
+                }
+                _ => break,
+            }
+        }
+        (keywords, followed.first().copied())
+    }
+}
+
+/// Resources, anchors and references collected while walking a document
+#[derive(Default)]
+struct Index {
+    /// Keyword identifying schema resources
+    id_keyword: &'static str,
+    /// JSON pointer of each schema resource, keyed by its base URI
+    resources: HashMap<String, String>,
+    /// JSON pointer of each anchor, keyed by base URI and anchor name
+    anchors: HashMap<(String, String), String>,
+    /// Address, base URI and value of each `$ref`
+    references: Vec<(*const Value, String, String)>,
+}
+
+impl Index {
+    fn walk(&mut self, value: &Value, pointer: String, mut base: String) {
+        match value {
+            Value::Object(object) => {
+                if let Some(Value::String(id)) = object.get(self.id_keyword) {
+                    let (uri, fragment) = id.split_once('#').unwrap_or((id, ""));
+                    if !uri.is_empty() {
+                        base = join(&base, uri);
+                        self.resources.insert(base.clone(), pointer.clone());
+                    }
+                    if !fragment.is_empty() {
+                        self.anchors.insert((base.clone(), fragment.to_string()), pointer.clone());
+                    }
+                }
+                if let Some(Value::String(anchor)) = object.get("$anchor") {
+                    self.anchors.insert((base.clone(), anchor.clone()), pointer.clone());
+                }
+                if let Some(Value::String(reference)) = object.get("$ref") {
+                    self.references.push((value as *const Value, base.clone(), reference.clone()));
+                }
+
+                for (key, child) in object {
+                    let token = key.replace('~', "~0").replace('/', "~1");
+                    self.walk(child, format!("{}/{}", pointer, token), base.clone());
+                }
+            }
+            Value::Array(items) => {
+                for (index, item) in items.iter().enumerate() {
+                    self.walk(item, format!("{}/{}", pointer, index), base.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Resolves a reference against a base URI to a JSON pointer into the document
+    fn resolve(&self, base: &str, reference: &str) -> Option<String> {
+        let (uri, fragment) = reference.split_once('#').unwrap_or((reference, ""));
+        let base = if uri.is_empty() { base.to_string() } else { join(base, uri) };
+        let resource = self.resources.get(&base)?;
+
+        if fragment.is_empty() {
+            Some(resource.clone())
+        } else if fragment.starts_with('/') {
+            Some(format!("{}{}", resource, fragment))
+        } else {
+            self.anchors.get(&(base, fragment.to_string())).cloned()
+        }
+    }
+}
+
+/// Resolves a URI reference against a base URI
+fn join(base: &str, reference: &str) -> String {
+    if reference.contains("://") || reference.starts_with("urn:") || base.is_empty() {
+        return reference.to_string();
+    }
+    if reference.starts_with('/') {
+        if let Some(scheme_end) = base.find("://") {
+            let authority_end = base[scheme_end + 3..].find('/').map_or(base.len(), |end| scheme_end + 3 + end);
+            return format!("{}{}", &base[..authority_end], reference);
+        }
+        return reference.to_string();
+    }
+    match base.rfind('/') {
+        Some(end) => format!("{}{}", &base[..=end], reference),
+        None => reference.to_string(),
+    }
+}
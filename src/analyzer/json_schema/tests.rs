@@ -1,41 +1,367 @@
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::Schema;
-    use semver::Version;
-
-    fn create_schema(content: &str, version: &str) -> Schema {
-        Schema::new(
-            crate::SchemaFormat::JsonSchema,
-            content.to_string(),
-            Version::parse(version).unwrap(),
+use super::*;
+use crate::{CompatibilityMode, IssueSeverity, Schema};
+use semver::Version;
+
+fn create_schema(content: &str, version: &str) -> Schema {
+    Schema::new(
+        crate::SchemaFormat::JsonSchema,
+        content.to_string(),
+        Version::parse(version).unwrap(),
+    )
+}
+
+fn analyze(old: &str, new: &str) -> CompatibilityReport {
+    JsonSchemaAnalyzer
+        .analyze_compatibility(&create_schema(old, "1.0.0"), &create_schema(new, "1.1.0"))
+        .unwrap()
+}
+
+fn kinds(report: &CompatibilityReport) -> Vec<ChangeKind> {
+    report.changes.iter().map(|c| c.kind).collect()
+}
+
+#[test]
+fn test_property_changes() {
+    let old_schema = r#"{
+        "type": "object",
+        "properties": {
+            "name": { "type": "string" }
+        }
+    }"#;
+
+    let new_schema = r#"{
+        "type": "object",
+        "properties": {
+            "name": { "type": "string" },
+            "age": { "type": "integer" }
+        }
+    }"#;
+
+    let result = analyze(old_schema, new_schema);
+
+    assert!(result.is_compatible);
+    assert_eq!(kinds(&result), [ChangeKind::PropertyAdded]);
+    assert!(result.issues.is_empty());
+}
+
+#[test]
+fn test_required_changes_are_reported_per_property() {
+    let report = analyze(
+        r#"{"properties": {"id": {}, "name": {}, "email": {}}, "required": ["id", "email"]}"#,
+        r#"{"properties": {"id": {}, "name": {}, "email": {}, "age": {}}, "required": ["name", "age", "id"]}"#,
+    );
+
+    assert_eq!(
+        kinds(&report),
+        [ChangeKind::RequiredPropertyAdded, ChangeKind::PropertyBecameRequired, ChangeKind::PropertyBecameOptional]
+    );
+    assert_eq!(report.changes[1].location, "/properties/name");
+    assert_eq!(report.changes[1].description, "Property 'name' became required");
+    assert_eq!(report.changes[2].location, "/properties/email");
+
+    let issue = report.issues.iter().find(|i| i.kind == ChangeKind::PropertyBecameRequired).unwrap();
+    assert_eq!(issue.severity, IssueSeverity::Error);
+    assert!(!report.is_compatible);
+}
+
+#[test]
+fn test_reordering_required_is_not_a_change() {
+    let report = analyze(r#"{"required": ["a", "b"]}"#, r#"{"required": ["b", "a"]}"#);
+
+    assert!(report.changes.is_empty());
+}
+
+#[test]
+fn test_type_changes_are_classified() {
+    let type_change = |old: &str, new: &str| {
+        let report = analyze(
+            &format!(r#"{{"properties": {{"n": {{"type": {}}}}}}}"#, old),
+            &format!(r#"{{"properties": {{"n": {{"type": {}}}}}}}"#, new),
+        );
+        assert_eq!(report.changes.len(), 1);
+        (report.changes[0].kind, report.changes[0].description.clone())
+    };
+
+    assert_eq!(type_change(r#""number""#, r#""integer""#), (ChangeKind::TypeNarrowed, "Type narrowed from number to integer".to_string()));
+    assert_eq!(type_change(r#""integer""#, r#"["integer", "null"]"#).0, ChangeKind::TypeWidened);
+    assert_eq!(type_change(r#""string""#, r#""integer""#).0, ChangeKind::TypeChanged);
+    assert_eq!(type_change(r#""boolean""#, r#"["string", "null"]"#).0, ChangeKind::TypeChanged);
+}
+
+#[test]
+fn test_equivalent_type_sets_are_not_changes() {
+    let report = analyze(
+        r#"{"properties": {"n": {"type": ["integer", "number"]}}}"#,
+        r#"{"properties": {"n": {"type": "number"}}}"#,
+    );
+    assert!(report.changes.is_empty());
+}
+
+#[test]
+fn test_type_added_narrows_and_items_are_compared_as_schemas() {
+    let report = analyze(
+        r#"{"properties": {"tags": {"type": "array", "items": {}}}}"#,
+        r#"{"properties": {"tags": {"type": "array", "items": {"type": "string"}}}}"#,
+    );
+
+    assert_eq!(kinds(&report), [ChangeKind::TypeNarrowed]);
+    assert_eq!(report.changes[0].location, "/properties/tags/items/type");
+    assert_eq!(report.changes[0].description, "Type narrowed from any to string");
+}
+
+#[test]
+fn test_enum_and_const_changes() {
+    let report = analyze(
+        r#"{"properties": {"status": {"enum": ["active", "inactive"]}, "kind": {"const": "user"}}}"#,
+        r#"{"properties": {"status": {"enum": ["pending", "active"]}, "kind": {"const": "admin"}}}"#,
+    );
+
+    assert_eq!(kinds(&report), [ChangeKind::ConstChanged, ChangeKind::EnumValueRemoved, ChangeKind::EnumValueAdded]);
+    assert_eq!(report.changes[1].metadata["value"], "\"inactive\"");
+    assert_eq!(report.changes[1].description, "Enum value \"inactive\" was removed");
+    assert_eq!(report.changes[2].location, "/properties/status/enum");
+}
+
+#[test]
+fn test_data_keywords_are_not_interpreted_as_schemas() {
+    let report = analyze(
+        r#"{"default": {"type": "string"}, "$defs": {"type": {"type": "string"}}}"#,
+        r#"{"default": {"type": "integer"}, "$defs": {"type": {"type": "number"}}}"#,
+    );
+
+    assert_eq!(kinds(&report), [ChangeKind::TypeChanged, ChangeKind::ValueChanged]);
+    assert_eq!(report.changes[0].location, "/$defs/type/type");
+    assert_eq!(report.changes[1].location, "/default/type");
+}
+
+#[test]
+fn test_referenced_definition_changes_are_reported_at_each_reference() {
+    let old_defs = r#""$defs": {"Address": {"properties": {"zip": {"type": "string"}}}}"#;
+    let new_defs = r#""$defs": {"Address": {"properties": {"zip": {"type": "integer"}}}}"#;
+    let properties = r##""properties": {"billing": {"$ref": "#/$defs/Address"}, "shipping": {"$ref": "#/$defs/Address"}}"##;
+    let report = analyze(&format!("{{{}, {}}}", properties, old_defs), &format!("{{{}, {}}}", properties, new_defs));
+
+    let locations: Vec<String> = report.changes.iter().map(|c| c.location.to_string()).collect();
+    assert_eq!(kinds(&report), [ChangeKind::TypeChanged, ChangeKind::TypeChanged]);
+    assert_eq!(locations, ["/properties/billing/properties/zip/type", "/properties/shipping/properties/zip/type"]);
+}
+
+#[test]
+fn test_moving_a_schema_into_definitions_is_not_a_change() {
+    let report = analyze(
+        r#"{"properties": {"tag": {"type": "string", "maxLength": 8}}}"#,
+        r##"{"properties": {"tag": {"$ref": "#/definitions/Tag"}}, "definitions": {"Tag": {"type": "string", "maxLength": 8}}}"##,
+    );
+
+    assert!(report.changes.is_empty());
+}
+
+#[test]
+fn test_recursive_references_terminate() {
+    let old_schema = r##"{"$defs": {"Node": {"properties": {"children": {"items": {"$ref": "#/$defs/Node"}}}}}, "$ref": "#/$defs/Node"}"##;
+    let new_schema = r##"{"$defs": {"Node": {"properties": {"children": {"items": {"$ref": "#/$defs/Node"}}, "label": {}}}}, "$ref": "#/$defs/Node"}"##;
+    let report = analyze(old_schema, new_schema);
+
+    assert_eq!(kinds(&report), [ChangeKind::PropertyAdded]);
+    assert_eq!(report.changes[0].location, "/properties/label");
+}
+
+#[test]
+fn test_anchor_and_id_references_resolve() {
+    let schema = |id_type: &str, name_type: &str| {
+        format!(
+            r##"{{"$id": "https://example.com/root.json", "properties": {{"id": {{"$ref": "item.json"}}, "name": {{"$ref": "#name"}}}},
+                "$defs": {{"item": {{"$id": "item.json", "type": "{}"}}, "name": {{"$anchor": "name", "type": "{}"}}}}}}"##,
+            id_type, name_type
         )
-    }
-
-    #[test]
-    fn test_property_changes() {
-        let old_schema = r#"{
-            "type": "object",
-            "properties": {
-                "name": { "type": "string" }
-            }
-        }"#;
-
-        let new_schema = r#"{
-            "type": "object",
-            "properties": {
-                "name": { "type": "string" },
-                "age": { "type": "integer" }
-            }
-        }"#;
-
-        let analyzer = JsonSchemaAnalyzer;
-        let result = analyzer.analyze_compatibility(
-            &create_schema(old_schema, "1.0.0"),
-            &create_schema(new_schema, "1.1.0")
-        ).unwrap();
-
-        assert!(result.is_compatible);
-        assert!(result.changes.iter().any(|c| matches!(c.change_type, ChangeType::Addition)));
-    }
-} 
\ No newline at end of file
+    };
+    let report = analyze(&schema("integer", "string"), &schema("number", "null"));
+
+    assert_eq!(kinds(&report), [ChangeKind::TypeWidened, ChangeKind::TypeChanged]);
+    assert_eq!(report.changes[0].location, "/properties/id/type");
+    assert_eq!(report.changes[1].location, "/properties/name/type");
+}
+
+#[test]
+fn test_ref_siblings_combine_with_the_target() {
+    let schema = |siblings: &str| {
+        format!(
+            r##"{{"properties": {{"code": {{"$ref": "#/$defs/code"{}}}}}, "$defs": {{"code": {{"type": "string", "maxLength": 5}}}}}}"##,
+            siblings
+        )
+    };
+
+    assert!(analyze(&schema(""), &schema(r#", "maxLength": 10"#)).changes.is_empty());
+
+    let report = analyze(&schema(r#", "maxLength": 10"#), &schema(r#", "maxLength": 3"#));
+    assert_eq!(kinds(&report), [ChangeKind::ConstraintTightened]);
+    assert_eq!(report.changes[0].description, "Constraint maxLength tightened from 5 to 3");
+}
+
+#[test]
+fn test_ref_siblings_merge_properties_with_the_target() {
+    let schema = |zip: &str, required: &str| {
+        format!(
+            r##"{{"properties": {{"address": {{"$ref": "#/$defs/Address", "properties": {{"note": {{}}}}, "required": [{}]}}}},
+                "$defs": {{"Address": {{"properties": {{"zip": {{"type": "{}"}}}}, "required": ["zip"]}}}}}}"##,
+            required, zip
+        )
+    };
+
+    let report = analyze(&schema("string", ""), &schema("integer", ""));
+    assert_eq!(kinds(&report), [ChangeKind::TypeChanged]);
+    assert_eq!(report.changes[0].location, "/properties/address/properties/zip/type");
+
+    assert!(analyze(&schema("string", ""), &schema("string", r#""zip""#)).changes.is_empty());
+    let report = analyze(&schema("string", ""), &schema("string", r#""note""#));
+    assert_eq!(kinds(&report), [ChangeKind::PropertyBecameRequired]);
+    assert_eq!(report.changes[0].location, "/properties/address/properties/note");
+}
+
+#[test]
+fn test_unreferenced_definitions_are_compared_directly() {
+    let report = analyze(
+        r#"{"$defs": {"Old": {}, "Kept": {"type": "string"}}}"#,
+        r#"{"$defs": {"Kept": {"type": ["string", "null"]}, "New": {}}}"#,
+    );
+
+    assert_eq!(kinds(&report), [ChangeKind::TypeWidened, ChangeKind::DefinitionRemoved, ChangeKind::DefinitionAdded]);
+    assert_eq!(report.changes[0].location, "/$defs/Kept/type");
+    assert_eq!(report.changes[1].location, "/$defs/Old");
+}
+
+#[test]
+fn test_draft_upgrade_with_equivalent_forms_is_not_a_change() {
+    let old_schema = r##"{
+        "$schema": "http://json-schema.org/draft-04/schema#",
+        "id": "https://example.com/point.json",
+        "properties": {
+            "coordinates": {"type": "array", "items": [{"type": "number"}, {"type": "number"}], "additionalItems": false},
+            "scale": {"type": "number", "maximum": 10, "exclusiveMaximum": true, "minimum": 0, "exclusiveMinimum": false},
+            "unit": {"$ref": "#/definitions/unit", "description": "ignored next to $ref"}
+        },
+        "dependencies": {"scale": ["unit"]},
+        "definitions": {"unit": {"enum": ["m", "km"]}}
+    }"##;
+    let new_schema = r##"{
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "$id": "https://example.com/point.json",
+        "properties": {
+            "coordinates": {"type": "array", "prefixItems": [{"type": "number"}, {"type": "number"}], "items": false},
+            "scale": {"type": "number", "exclusiveMaximum": 10, "minimum": 0},
+            "unit": {"$ref": "#/$defs/unit"}
+        },
+        "dependentRequired": {"scale": ["unit"]},
+        "$defs": {"unit": {"enum": ["m", "km"]}}
+    }"##;
+
+    let report = analyze(old_schema, new_schema);
+
+    assert!(report.changes.is_empty(), "{:?}", report.changes);
+}
+
+#[test]
+fn test_changes_across_a_draft_upgrade_are_located_in_the_new_draft() {
+    let report = analyze(
+        r#"{"$schema": "http://json-schema.org/draft-07/schema#", "items": [{"type": "string"}, {"type": "integer"}]}"#,
+        r#"{"$schema": "https://json-schema.org/draft/2020-12/schema", "prefixItems": [{"type": "string"}, {"type": "number"}]}"#,
+    );
+
+    assert_eq!(kinds(&report), [ChangeKind::TypeWidened]);
+    assert_eq!(report.changes[0].location, "/prefixItems/1/type");
+}
+
+#[test]
+fn test_schemas_without_a_draft_use_the_other_schemas_draft() {
+    let old_schema = r##"{"$schema": "http://json-schema.org/draft-07/schema#", "properties": {"size": {"$ref": "#/definitions/size", "type": "string"}}, "definitions": {"size": {}}}"##;
+    let new_schema = r##"{"properties": {"size": {"$ref": "#/definitions/size", "type": "integer"}}, "definitions": {"size": {}}}"##;
+
+    assert!(analyze(old_schema, new_schema).changes.is_empty());
+    assert_eq!(kinds(&analyze(&old_schema.replace("draft-07", "draft/2020-12"), new_schema)), [ChangeKind::TypeChanged]);
+}
+
+#[test]
+fn test_constraint_changes_are_classified_by_direction() {
+    let report = analyze(
+        r#"{"properties": {"name": {"maxLength": 100, "minLength": 1, "pattern": "^[a-z]+$"}, "tags": {"maxItems": 5}, "score": {"minimum": 0, "maximum": 10, "multipleOf": 0.5}}}"#,
+        r#"{"properties": {"name": {"maxLength": 50, "pattern": "^[a-z0-9]+$"}, "tags": {"maxItems": 10, "uniqueItems": true}, "score": {"minimum": 0, "exclusiveMaximum": 10, "multipleOf": 1}}}"#,
+    );
+
+    let changes: Vec<(ChangeKind, String)> = report.changes.iter().map(|c| (c.kind, c.location.to_string())).collect();
+    assert_eq!(
+        changes,
+        [
+            (ChangeKind::ConstraintLoosened, "/properties/name/minLength".to_string()),
+            (ChangeKind::ConstraintTightened, "/properties/name/maxLength".to_string()),
+            (ChangeKind::ConstraintChanged, "/properties/name/pattern".to_string()),
+            (ChangeKind::ConstraintTightened, "/properties/score/exclusiveMaximum".to_string()),
+            (ChangeKind::ConstraintTightened, "/properties/score/multipleOf".to_string()),
+            (ChangeKind::ConstraintLoosened, "/properties/tags/maxItems".to_string()),
+            (ChangeKind::ConstraintTightened, "/properties/tags/uniqueItems".to_string()),
+        ]
+    );
+    assert_eq!(report.changes[1].description, "Constraint maxLength tightened from 100 to 50");
+    assert_eq!(report.changes[3].description, "Constraint exclusiveMaximum tightened from 10 to 10 (exclusive)");
+}
+
+#[test]
+fn test_constraint_severity_follows_the_compatibility_direction() {
+    let old = create_schema(r#"{"properties": {"code": {"maxLength": 100}}}"#, "1.0.0");
+    let tightened = create_schema(r#"{"properties": {"code": {"maxLength": 50}}}"#, "1.1.0");
+    let loosened = create_schema(r#"{"properties": {"code": {"maxLength": 200}}}"#, "1.1.0");
+    let severities = |mode, new: &Schema| {
+        let options = AnalysisOptions::default().with_mode(mode);
+        let report = JsonSchemaAnalyzer.analyze_with_options(&old, new, &options).unwrap();
+        report.issues.iter().map(|issue| issue.severity).collect::<Vec<_>>()
+    };
+
+    assert_eq!(severities(CompatibilityMode::Backward, &tightened), [IssueSeverity::Error]);
+    assert_eq!(severities(CompatibilityMode::Backward, &loosened), []);
+    assert_eq!(severities(CompatibilityMode::Forward, &tightened), []);
+    assert_eq!(severities(CompatibilityMode::Forward, &loosened), [IssueSeverity::Error]);
+    assert_eq!(severities(CompatibilityMode::Full, &tightened), [IssueSeverity::Error]);
+    assert_eq!(severities(CompatibilityMode::Full, &loosened), [IssueSeverity::Error]);
+}
+
+#[test]
+fn test_applicators_are_compared_regardless_of_order() {
+    let report = analyze(
+        r#"{"anyOf": [{"type": "string"}, {"type": "integer"}], "allOf": [{"minimum": 0}, {"maximum": 9}]}"#,
+        r#"{"anyOf": [{"type": "null"}, {"type": "integer"}, {"type": "string"}], "allOf": [{"maximum": 5}, {"minimum": 0}]}"#,
+    );
+
+    let changes: Vec<(ChangeKind, String)> = report.changes.iter().map(|c| (c.kind, c.location.to_string())).collect();
+    assert_eq!(
+        changes,
+        [
+            (ChangeKind::ConstraintTightened, "/allOf/0/maximum".to_string()),
+            (ChangeKind::ConstraintLoosened, "/anyOf/0".to_string()),
+        ]
+    );
+    assert_eq!(report.changes[1].description, "Subschema was added to anyOf");
+}
+
+#[test]
+fn test_one_of_subschemas_are_changes_in_either_direction() {
+    let (old_schema, new_schema) = (r#"{"oneOf": [{"type": "string"}, {"type": "integer"}]}"#, r#"{"oneOf": [{"type": "integer"}]}"#);
+
+    let removed = analyze(old_schema, new_schema);
+    assert_eq!(kinds(&removed), [ChangeKind::ConstraintChanged]);
+    assert_eq!(removed.changes[0].description, "Subschema was removed from oneOf");
+
+    let added = analyze(new_schema, old_schema);
+    assert_eq!(kinds(&added), [ChangeKind::ConstraintChanged]);
+    assert_eq!(added.changes[0].location.to_string(), "/oneOf/0");
+}
+
+#[test]
+fn test_prefix_items_are_compared_by_index() {
+    let report = analyze(
+        r#"{"prefixItems": [{"type": "string"}, {"type": "integer"}]}"#,
+        r#"{"prefixItems": [{"type": "integer"}, {"type": "string"}]}"#,
+    );
+
+    let locations: Vec<String> = report.changes.iter().map(|c| c.location.to_string()).collect();
+    assert_eq!(kinds(&report), [ChangeKind::TypeChanged, ChangeKind::TypeChanged]);
+    assert_eq!(locations, ["/prefixItems/0/type", "/prefixItems/1/type"]);
+}
@@ -0,0 +1,560 @@
+//! Typed classification of schema changes
+//!
+//! This module provides [`ChangeKind`], the taxonomy attached to every
+//! [`SchemaChange`](crate::SchemaChange). Each kind has a stable rule ID that
+//! policies and downstream tooling can match on, a documented default
+//! severity and the compatibility direction it breaks.
+
+use crate::analyzer::ChangeType;
+use crate::compatibility::CompatibilityMode;
+use crate::report::IssueSeverity;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Specific kind of a schema change
+///
+/// The serialized form of a kind is its rule ID, e.g. `parameter-became-required`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ChangeKind {
+    /// An element was added. Default severity: info.
+    Added,
+    /// An element was removed. Default severity: error.
+    Removed,
+    /// An element was modified. Default severity: warning.
+    Modified,
+    /// An element was renamed. Default severity: warning.
+    Renamed,
+
+    /// A JSON Schema property was added. Default severity: info.
+    PropertyAdded,
+    /// A JSON Schema property was removed. Default severity: error.
+    PropertyRemoved,
+    /// A JSON Schema property was renamed. Default severity: error.
+    PropertyRenamed,
+    /// A JSON Schema keyword was added. Default severity: warning.
+    KeywordAdded,
+    /// A JSON Schema keyword was removed. Default severity: warning.
+    KeywordRemoved,
+    /// A JSON Schema keyword value changed. Default severity: warning.
+    ValueChanged,
+    /// A JSON Schema array keyword gained or lost items. Default severity: warning.
+    ArrayLengthChanged,
+    /// A JSON Schema property was added as required. Default severity: error.
+    RequiredPropertyAdded,
+    /// An optional JSON Schema property became required. Default severity: error.
+    PropertyBecameRequired,
+    /// A required JSON Schema property became optional. Default severity: error.
+    PropertyBecameOptional,
+    /// A JSON Schema `type` accepts fewer values than before, e.g. `number`
+    /// to `integer`. Default severity: error.
+    TypeNarrowed,
+    /// A JSON Schema `type` accepts more values than before. Default severity: error.
+    TypeWidened,
+    /// A JSON Schema `type` changed to an unrelated type. Default severity: error.
+    TypeChanged,
+    /// A value was added to a JSON Schema `enum`. Default severity: error.
+    EnumValueAdded,
+    /// A value was removed from a JSON Schema `enum`. Default severity: error.
+    EnumValueRemoved,
+    /// A JSON Schema `const` value changed. Default severity: error.
+    ConstChanged,
+    /// An unreferenced JSON Schema definition was added. Default severity: info.
+    DefinitionAdded,
+    /// An unreferenced JSON Schema definition was removed. Default severity: warning.
+    DefinitionRemoved,
+    /// A JSON Schema validation keyword accepts fewer values than before, e.g.
+    /// a lower `maxLength`. Default severity: error.
+    ConstraintTightened,
+    /// A JSON Schema validation keyword accepts more values than before, e.g.
+    /// a higher `maxLength`. Default severity: error.
+    ConstraintLoosened,
+    /// A JSON Schema validation keyword accepts different values, e.g. a new
+    /// `pattern`. Default severity: error.
+    ConstraintChanged,
+
+    /// An OpenAPI path was added. Default severity: info.
+    PathAdded,
+    /// An OpenAPI path was removed. Default severity: error.
+    PathRemoved,
+    /// An OpenAPI path was renamed. Default severity: error.
+    PathRenamed,
+    /// An HTTP method was added to a path. Default severity: info.
+    OperationAdded,
+    /// An HTTP method was removed from a path. Default severity: error.
+    OperationRemoved,
+    /// An optional operation parameter became required. Default severity: error.
+    ParameterBecameRequired,
+    /// A request body was added to an operation. Default severity: warning.
+    RequestBodyAdded,
+    /// A request body was removed from an operation. Default severity: error.
+    RequestBodyRemoved,
+    /// An operation's request body changed. Default severity: warning.
+    RequestBodyModified,
+    /// A response status was added to an operation. Default severity: error.
+    ResponseAdded,
+    /// A response status was removed from an operation. Default severity: error.
+    ResponseRemoved,
+    /// An operation's response changed. Default severity: warning.
+    ResponseModified,
+    /// A component schema was added. Default severity: info.
+    ComponentSchemaAdded,
+    /// A component schema was removed. Default severity: error.
+    ComponentSchemaRemoved,
+    /// A component schema was renamed. Default severity: warning.
+    ComponentSchemaRenamed,
+    /// A component schema changed. Default severity: warning.
+    ComponentSchemaModified,
+    /// A security scheme was removed. Default severity: error.
+    SecuritySchemeRemoved,
+    /// A security scheme changed. Default severity: warning.
+    SecuritySchemeModified,
+
+    /// A Protobuf message was added. Default severity: info.
+    MessageAdded,
+    /// A Protobuf message was removed. Default severity: error.
+    MessageRemoved,
+    /// A Protobuf message was renamed. Default severity: warning.
+    MessageRenamed,
+    /// A Protobuf field was added. Default severity: info.
+    FieldAdded,
+    /// A Protobuf field was removed. Default severity: error.
+    FieldRemoved,
+    /// A Protobuf field was renamed, keeping its number. Default severity: warning.
+    FieldRenamed,
+    /// A Protobuf field changed to a wire-incompatible type. Default severity: error.
+    FieldTypeChanged,
+    /// A Protobuf field changed to a wire-compatible type, e.g. `int32` to
+    /// `int64`. Default severity: warning.
+    FieldTypeWireCompatible,
+    /// A Protobuf field kept its name but changed its number. Default severity: error.
+    FieldNumberChanged,
+    /// A new Protobuf field reuses the number of a removed field with a
+    /// different type. Default severity: error.
+    FieldNumberReused,
+
+    /// A SQL table was added. Default severity: info.
+    TableAdded,
+    /// A SQL table was removed. Default severity: error.
+    TableRemoved,
+    /// A SQL table was renamed. Default severity: error.
+    TableRenamed,
+    /// A nullable or defaulted SQL column was added. Default severity: info.
+    ColumnAdded,
+    /// A `NOT NULL` SQL column without a default was added. Default severity: error.
+    NotNullColumnAdded,
+    /// A SQL column was removed. Default severity: error.
+    ColumnRemoved,
+    /// A SQL column was renamed. Default severity: error.
+    ColumnRenamed,
+    /// A SQL column type can hold fewer values than before. Default severity: error.
+    ColumnTypeNarrowed,
+    /// A SQL column type can hold more values than before. Default severity: error.
+    ColumnTypeWidened,
+    /// A SQL column changed to an unrelated type. Default severity: warning.
+    ColumnTypeChanged,
+    /// A constraint was added to a SQL column. Default severity: warning.
+    ConstraintAdded,
+    /// A constraint was removed from a SQL column. Default severity: warning.
+    ConstraintRemoved,
+}
+
+impl ChangeKind {
+    /// All change kinds, in declaration order
+    pub const ALL: &'static [ChangeKind] = &[
+        ChangeKind::Added,
+        ChangeKind::Removed,
+        ChangeKind::Modified,
+        ChangeKind::Renamed,
+        ChangeKind::PropertyAdded,
+        ChangeKind::PropertyRemoved,
+        ChangeKind::PropertyRenamed,
+        ChangeKind::KeywordAdded,
+        ChangeKind::KeywordRemoved,
+        ChangeKind::ValueChanged,
+        ChangeKind::ArrayLengthChanged,
+        ChangeKind::RequiredPropertyAdded,
+        ChangeKind::PropertyBecameRequired,
+        ChangeKind::PropertyBecameOptional,
+        ChangeKind::TypeNarrowed,
+        ChangeKind::TypeWidened,
+        ChangeKind::TypeChanged,
+        ChangeKind::EnumValueAdded,
+        ChangeKind::EnumValueRemoved,
+        ChangeKind::ConstChanged,
+        ChangeKind::DefinitionAdded,
+        ChangeKind::DefinitionRemoved,
+        ChangeKind::ConstraintTightened,
+        ChangeKind::ConstraintLoosened,
+        ChangeKind::ConstraintChanged,
+        ChangeKind::PathAdded,
+        ChangeKind::PathRemoved,
+        ChangeKind::PathRenamed,
+        ChangeKind::OperationAdded,
+        ChangeKind::OperationRemoved,
+        ChangeKind::ParameterBecameRequired,
+        ChangeKind::RequestBodyAdded,
+        ChangeKind::RequestBodyRemoved,
+        ChangeKind::RequestBodyModified,
+        ChangeKind::ResponseAdded,
+        ChangeKind::ResponseRemoved,
+        ChangeKind::ResponseModified,
+        ChangeKind::ComponentSchemaAdded,
+        ChangeKind::ComponentSchemaRemoved,
+        ChangeKind::ComponentSchemaRenamed,
+        ChangeKind::ComponentSchemaModified,
+        ChangeKind::SecuritySchemeRemoved,
+        ChangeKind::SecuritySchemeModified,
+        ChangeKind::MessageAdded,
+        ChangeKind::MessageRemoved,
+        ChangeKind::MessageRenamed,
+        ChangeKind::FieldAdded,
+        ChangeKind::FieldRemoved,
+        ChangeKind::FieldRenamed,
+        ChangeKind::FieldTypeChanged,
+        ChangeKind::FieldTypeWireCompatible,
+        ChangeKind::FieldNumberChanged,
+        ChangeKind::FieldNumberReused,
+        ChangeKind::TableAdded,
+        ChangeKind::TableRemoved,
+        ChangeKind::TableRenamed,
+        ChangeKind::ColumnAdded,
+        ChangeKind::NotNullColumnAdded,
+        ChangeKind::ColumnRemoved,
+        ChangeKind::ColumnRenamed,
+        ChangeKind::ColumnTypeNarrowed,
+        ChangeKind::ColumnTypeWidened,
+        ChangeKind::ColumnTypeChanged,
+        ChangeKind::ConstraintAdded,
+        ChangeKind::ConstraintRemoved,
+    ];
+
+    /// Returns the generic kind for a change type
+    ///
+    /// Used for changes reported by analyzers that do not classify them further.
+    pub fn generic(change_type: &ChangeType) -> ChangeKind {
+        match change_type {
+            ChangeType::Addition => ChangeKind::Added,
+            ChangeType::Removal => ChangeKind::Removed,
+            ChangeType::Modification => ChangeKind::Modified,
+            ChangeType::Rename => ChangeKind::Renamed,
+        }
+    }
+
+    /// Looks up a kind by its rule ID
+    pub fn from_rule_id(rule_id: &str) -> Option<ChangeKind> {
+        Self::ALL.iter().copied().find(|kind| kind.rule_id() == rule_id)
+    }
+
+    /// Returns the stable rule ID of this kind
+    pub fn rule_id(&self) -> &'static str {
+        match self {
+            ChangeKind::Added => "added",
+            ChangeKind::Removed => "removed",
+            ChangeKind::Modified => "modified",
+            ChangeKind::Renamed => "renamed",
+            ChangeKind::PropertyAdded => "property-added",
+            ChangeKind::PropertyRemoved => "property-removed",
+            ChangeKind::PropertyRenamed => "property-renamed",
+            ChangeKind::KeywordAdded => "keyword-added",
+            ChangeKind::KeywordRemoved => "keyword-removed",
+            ChangeKind::ValueChanged => "value-changed",
+            ChangeKind::ArrayLengthChanged => "array-length-changed",
+            ChangeKind::RequiredPropertyAdded => "required-property-added",
+            ChangeKind::PropertyBecameRequired => "property-became-required",
+            ChangeKind::PropertyBecameOptional => "property-became-optional",
+            ChangeKind::TypeNarrowed => "type-narrowed",
+            ChangeKind::TypeWidened => "type-widened",
+            ChangeKind::TypeChanged => "type-changed",
+            ChangeKind::EnumValueAdded => "enum-value-added",
+            ChangeKind::EnumValueRemoved => "enum-value-removed",
+            ChangeKind::ConstChanged => "const-changed",
+            ChangeKind::DefinitionAdded => "definition-added",
+            ChangeKind::DefinitionRemoved => "definition-removed",
+            ChangeKind::ConstraintTightened => "constraint-tightened",
+            ChangeKind::ConstraintLoosened => "constraint-loosened",
+            ChangeKind::ConstraintChanged => "constraint-changed",
+            ChangeKind::PathAdded => "path-added",
+            ChangeKind::PathRemoved => "path-removed",
+            ChangeKind::PathRenamed => "path-renamed",
+            ChangeKind::OperationAdded => "operation-added",
+            ChangeKind::OperationRemoved => "operation-removed",
+            ChangeKind::ParameterBecameRequired => "parameter-became-required",
+            ChangeKind::RequestBodyAdded => "request-body-added",
+            ChangeKind::RequestBodyRemoved => "request-body-removed",
+            ChangeKind::RequestBodyModified => "request-body-modified",
+            ChangeKind::ResponseAdded => "response-added",
+            ChangeKind::ResponseRemoved => "response-removed",
+            ChangeKind::ResponseModified => "response-modified",
+            ChangeKind::ComponentSchemaAdded => "component-schema-added",
+            ChangeKind::ComponentSchemaRemoved => "component-schema-removed",
+            ChangeKind::ComponentSchemaRenamed => "component-schema-renamed",
+            ChangeKind::ComponentSchemaModified => "component-schema-modified",
+            ChangeKind::SecuritySchemeRemoved => "security-scheme-removed",
+            ChangeKind::SecuritySchemeModified => "security-scheme-modified",
+            ChangeKind::MessageAdded => "message-added",
+            ChangeKind::MessageRemoved => "message-removed",
+            ChangeKind::MessageRenamed => "message-renamed",
+            ChangeKind::FieldAdded => "field-added",
+            ChangeKind::FieldRemoved => "field-removed",
+            ChangeKind::FieldRenamed => "field-renamed",
+            ChangeKind::FieldTypeChanged => "field-type-changed",
+            ChangeKind::FieldTypeWireCompatible => "field-type-wire-compatible",
+            ChangeKind::FieldNumberChanged => "field-number-changed",
+            ChangeKind::FieldNumberReused => "field-number-reused",
+            ChangeKind::TableAdded => "table-added",
+            ChangeKind::TableRemoved => "table-removed",
+            ChangeKind::TableRenamed => "table-renamed",
+            ChangeKind::ColumnAdded => "column-added",
+            ChangeKind::NotNullColumnAdded => "not-null-column-added",
+            ChangeKind::ColumnRemoved => "column-removed",
+            ChangeKind::ColumnRenamed => "column-renamed",
+            ChangeKind::ColumnTypeNarrowed => "column-type-narrowed",
+            ChangeKind::ColumnTypeWidened => "column-type-widened",
+            ChangeKind::ColumnTypeChanged => "column-type-changed",
+            ChangeKind::ConstraintAdded => "constraint-added",
+            ChangeKind::ConstraintRemoved => "constraint-removed",
+        }
+    }
+
+    /// Returns the change type this kind belongs to
+    pub fn change_type(&self) -> ChangeType {
+        match self {
+            ChangeKind::Added
+            | ChangeKind::PropertyAdded
+            | ChangeKind::KeywordAdded
+            | ChangeKind::RequiredPropertyAdded
+            | ChangeKind::EnumValueAdded
+            | ChangeKind::DefinitionAdded
+            | ChangeKind::PathAdded
+            | ChangeKind::OperationAdded
+            | ChangeKind::RequestBodyAdded
+            | ChangeKind::ResponseAdded
+            | ChangeKind::ComponentSchemaAdded
+            | ChangeKind::MessageAdded
+            | ChangeKind::FieldAdded
+            | ChangeKind::FieldNumberReused
+            | ChangeKind::TableAdded
+            | ChangeKind::ColumnAdded
+            | ChangeKind::NotNullColumnAdded
+            | ChangeKind::ConstraintAdded => ChangeType::Addition,
+            ChangeKind::Removed
+            | ChangeKind::PropertyRemoved
+            | ChangeKind::KeywordRemoved
+            | ChangeKind::EnumValueRemoved
+            | ChangeKind::DefinitionRemoved
+            | ChangeKind::PathRemoved
+            | ChangeKind::OperationRemoved
+            | ChangeKind::RequestBodyRemoved
+            | ChangeKind::ResponseRemoved
+            | ChangeKind::ComponentSchemaRemoved
+            | ChangeKind::SecuritySchemeRemoved
+            | ChangeKind::MessageRemoved
+            | ChangeKind::FieldRemoved
+            | ChangeKind::TableRemoved
+            | ChangeKind::ColumnRemoved
+            | ChangeKind::ConstraintRemoved => ChangeType::Removal,
+            ChangeKind::Renamed
+            | ChangeKind::PropertyRenamed
+            | ChangeKind::PathRenamed
+            | ChangeKind::ComponentSchemaRenamed
+            | ChangeKind::MessageRenamed
+            | ChangeKind::FieldRenamed
+            | ChangeKind::TableRenamed
+            | ChangeKind::ColumnRenamed => ChangeType::Rename,
+            ChangeKind::Modified
+            | ChangeKind::ValueChanged
+            | ChangeKind::ArrayLengthChanged
+            | ChangeKind::PropertyBecameRequired
+            | ChangeKind::PropertyBecameOptional
+            | ChangeKind::TypeNarrowed
+            | ChangeKind::TypeWidened
+            | ChangeKind::TypeChanged
+            | ChangeKind::ConstChanged
+            | ChangeKind::ConstraintTightened
+            | ChangeKind::ConstraintLoosened
+            | ChangeKind::ConstraintChanged
+            | ChangeKind::ParameterBecameRequired
+            | ChangeKind::RequestBodyModified
+            | ChangeKind::ResponseModified
+            | ChangeKind::ComponentSchemaModified
+            | ChangeKind::SecuritySchemeModified
+            | ChangeKind::FieldTypeChanged
+            | ChangeKind::FieldTypeWireCompatible
+            | ChangeKind::FieldNumberChanged
+            | ChangeKind::ColumnTypeNarrowed
+            | ChangeKind::ColumnTypeWidened
+            | ChangeKind::ColumnTypeChanged => ChangeType::Modification,
+        }
+    }
+
+    /// Returns the documented default severity of this kind
+    pub fn default_severity(&self) -> IssueSeverity {
+        match self {
+            ChangeKind::Removed
+            | ChangeKind::PropertyRemoved
+            | ChangeKind::PropertyRenamed
+            | ChangeKind::RequiredPropertyAdded
+            | ChangeKind::PropertyBecameRequired
+            | ChangeKind::TypeNarrowed
+            | ChangeKind::TypeChanged
+            | ChangeKind::EnumValueRemoved
+            | ChangeKind::ConstChanged
+            | ChangeKind::ConstraintTightened
+            | ChangeKind::ConstraintLoosened
+            | ChangeKind::ConstraintChanged
+            | ChangeKind::PathRemoved
+            | ChangeKind::PathRenamed
+            | ChangeKind::OperationRemoved
+            | ChangeKind::ParameterBecameRequired
+            | ChangeKind::RequestBodyRemoved
+            | ChangeKind::ResponseRemoved
+            | ChangeKind::ComponentSchemaRemoved
+            | ChangeKind::SecuritySchemeRemoved
+            | ChangeKind::MessageRemoved
+            | ChangeKind::FieldRemoved
+            | ChangeKind::FieldTypeChanged
+            | ChangeKind::FieldNumberChanged
+            | ChangeKind::FieldNumberReused
+            | ChangeKind::TableRemoved
+            | ChangeKind::TableRenamed
+            | ChangeKind::NotNullColumnAdded
+            | ChangeKind::ColumnRemoved
+            | ChangeKind::ColumnRenamed
+            | ChangeKind::ColumnTypeNarrowed
+            | ChangeKind::PropertyBecameOptional
+            | ChangeKind::TypeWidened
+            | ChangeKind::EnumValueAdded
+            | ChangeKind::ResponseAdded
+            | ChangeKind::ColumnTypeWidened => IssueSeverity::Error,
+            ChangeKind::Modified
+            | ChangeKind::Renamed
+            | ChangeKind::KeywordAdded
+            | ChangeKind::KeywordRemoved
+            | ChangeKind::ValueChanged
+            | ChangeKind::ArrayLengthChanged
+            | ChangeKind::DefinitionRemoved
+            | ChangeKind::RequestBodyAdded
+            | ChangeKind::RequestBodyModified
+            | ChangeKind::ResponseModified
+            | ChangeKind::ComponentSchemaRenamed
+            | ChangeKind::ComponentSchemaModified
+            | ChangeKind::SecuritySchemeModified
+            | ChangeKind::MessageRenamed
+            | ChangeKind::FieldRenamed
+            | ChangeKind::FieldTypeWireCompatible
+            | ChangeKind::ColumnTypeChanged
+            | ChangeKind::ConstraintAdded
+            | ChangeKind::ConstraintRemoved => IssueSeverity::Warning,
+            ChangeKind::Added
+            | ChangeKind::PropertyAdded
+            | ChangeKind::DefinitionAdded
+            | ChangeKind::PathAdded
+            | ChangeKind::OperationAdded
+            | ChangeKind::ComponentSchemaAdded
+            | ChangeKind::MessageAdded
+            | ChangeKind::FieldAdded
+            | ChangeKind::TableAdded
+            | ChangeKind::ColumnAdded => IssueSeverity::Info,
+        }
+    }
+
+    /// Returns the severity of this kind under a compatibility mode
+    ///
+    /// Kinds keep their default severity in the modes that include their
+    /// [direction](ChangeKind::direction) and are `Info` otherwise. `Full`
+    /// includes both directions, so it is the stricter of `Backward` and
+    /// `Forward` and always the default severity.
+    pub fn severity(&self, mode: CompatibilityMode) -> IssueSeverity {
+        if mode.includes(self.direction()) {
+            self.default_severity()
+        } else {
+            IssueSeverity::Info
+        }
+    }
+
+    /// Returns the compatibility direction a change of this kind can break
+    ///
+    /// Constraints added to what readers accept break backward compatibility,
+    /// since old data may not satisfy them. Elements that readers may rely on
+    /// being removed break forward compatibility. Kinds whose effect depends
+    /// on the details of the change yield `Full`.
+    pub fn direction(&self) -> CompatibilityMode {
+        match self {
+            ChangeKind::KeywordAdded
+            | ChangeKind::RequiredPropertyAdded
+            | ChangeKind::PropertyBecameRequired
+            | ChangeKind::TypeNarrowed
+            | ChangeKind::EnumValueRemoved
+            | ChangeKind::ConstraintTightened
+            | ChangeKind::PathRemoved
+            | ChangeKind::OperationRemoved
+            | ChangeKind::ParameterBecameRequired
+            | ChangeKind::RequestBodyAdded
+            | ChangeKind::RequestBodyRemoved
+            | ChangeKind::SecuritySchemeRemoved
+            | ChangeKind::PropertyAdded
+            | ChangeKind::FieldAdded
+            | ChangeKind::ColumnAdded
+            | ChangeKind::NotNullColumnAdded
+            | ChangeKind::ColumnTypeNarrowed
+            | ChangeKind::ConstraintAdded => CompatibilityMode::Backward,
+            ChangeKind::Removed
+            | ChangeKind::PropertyRemoved
+            | ChangeKind::KeywordRemoved
+            | ChangeKind::PropertyBecameOptional
+            | ChangeKind::TypeWidened
+            | ChangeKind::EnumValueAdded
+            | ChangeKind::DefinitionRemoved
+            | ChangeKind::ConstraintLoosened
+            | ChangeKind::ResponseAdded
+            | ChangeKind::ResponseRemoved
+            | ChangeKind::ComponentSchemaRemoved
+            | ChangeKind::MessageRemoved
+            | ChangeKind::FieldRemoved
+            | ChangeKind::FieldTypeWireCompatible
+            | ChangeKind::TableRemoved
+            | ChangeKind::ColumnRemoved
+            | ChangeKind::ColumnTypeWidened
+            | ChangeKind::ConstraintRemoved => CompatibilityMode::Forward,
+            ChangeKind::Added
+            | ChangeKind::Modified
+            | ChangeKind::Renamed
+            | ChangeKind::PropertyRenamed
+            | ChangeKind::ValueChanged
+            | ChangeKind::ArrayLengthChanged
+            | ChangeKind::TypeChanged
+            | ChangeKind::ConstChanged
+            | ChangeKind::DefinitionAdded
+            | ChangeKind::ConstraintChanged
+            | ChangeKind::PathAdded
+            | ChangeKind::PathRenamed
+            | ChangeKind::OperationAdded
+            | ChangeKind::RequestBodyModified
+            | ChangeKind::ResponseModified
+            | ChangeKind::ComponentSchemaAdded
+            | ChangeKind::ComponentSchemaRenamed
+            | ChangeKind::ComponentSchemaModified
+            | ChangeKind::SecuritySchemeModified
+            | ChangeKind::MessageAdded
+            | ChangeKind::MessageRenamed
+            | ChangeKind::FieldRenamed
+            | ChangeKind::FieldTypeChanged
+            | ChangeKind::FieldNumberChanged
+            | ChangeKind::FieldNumberReused
+            | ChangeKind::TableAdded
+            | ChangeKind::TableRenamed
+            | ChangeKind::ColumnRenamed
+            | ChangeKind::ColumnTypeChanged => CompatibilityMode::Full,
+        }
+    }
+}
+
+impl fmt::Display for ChangeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.rule_id())
+    }
+}
+
+#[cfg(test)]
+mod tests;
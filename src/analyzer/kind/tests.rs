@@ -0,0 +1,42 @@
+use super::*;
+
+#[test]
+fn test_rule_ids_are_unique_and_round_trip() {
+    for kind in ChangeKind::ALL {
+        assert_eq!(ChangeKind::from_rule_id(kind.rule_id()), Some(*kind));
+        assert_eq!(serde_json::to_value(kind).unwrap(), kind.rule_id());
+    }
+    assert_eq!(ChangeKind::from_rule_id("no-such-rule"), None);
+}
+
+#[test]
+fn test_kind_determines_change_type() {
+    assert_eq!(ChangeKind::ParameterBecameRequired.change_type(), ChangeType::Modification);
+    assert_eq!(ChangeKind::FieldNumberReused.change_type(), ChangeType::Addition);
+    assert_eq!(ChangeKind::ColumnTypeNarrowed.default_severity(), IssueSeverity::Error);
+    assert_eq!(ChangeKind::ColumnTypeWidened.default_severity(), IssueSeverity::Error);
+}
+
+#[test]
+fn test_severity_depends_on_mode() {
+    use crate::compatibility::CompatibilityMode;
+
+    let kind = ChangeKind::NotNullColumnAdded;
+    assert_eq!(kind.severity(CompatibilityMode::Backward), IssueSeverity::Error);
+    assert_eq!(kind.severity(CompatibilityMode::Forward), IssueSeverity::Info);
+    assert_eq!(kind.severity(CompatibilityMode::Full), IssueSeverity::Error);
+    assert_eq!(ChangeKind::ColumnRemoved.severity(CompatibilityMode::Backward), IssueSeverity::Info);
+    assert_eq!(ChangeKind::FieldNumberReused.severity(CompatibilityMode::Forward), IssueSeverity::Error);
+
+    let widened = ChangeKind::TypeWidened;
+    assert_eq!(widened.severity(CompatibilityMode::Forward), IssueSeverity::Error);
+    assert_eq!(widened.severity(CompatibilityMode::Backward), IssueSeverity::Info);
+    assert_eq!(widened.severity(CompatibilityMode::Full), IssueSeverity::Error);
+    assert_eq!(ChangeKind::KeywordAdded.severity(CompatibilityMode::Backward), IssueSeverity::Warning);
+
+    for kind in ChangeKind::ALL {
+        let (backward, forward) = (kind.severity(CompatibilityMode::Backward), kind.severity(CompatibilityMode::Forward));
+        let stricter = if backward.is_at_least(forward) { backward } else { forward };
+        assert_eq!(kind.severity(CompatibilityMode::Full), stricter, "{}", kind.rule_id());
+    }
+}
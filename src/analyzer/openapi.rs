@@ -4,9 +4,10 @@
 //! generating compatibility reports and migration paths.
 
 use openapiv3::{OpenAPI, ReferenceOr, Parameter, RequestBody, Responses};
-use crate::analyzer::{SchemaAnalyzer, SchemaChange, ChangeType};
-use crate::report::{CompatibilityIssue, IssueSeverity, ValidationError};
-use crate::{Schema, CompatibilityReport, MigrationPlan, ValidationResult};
+use crate::analyzer::{SchemaAnalyzer, AnalysisOptions, SchemaChange, ChangeType, ChangeKind, build_report, rename_members, resolve_positions, validation_errors};
+use crate::location::Location;
+use crate::{Schema, CompatibilityReport, MigrationPlan, RenameHints, ValidationResult};
+use serde_json::Value;
 use crate::error::Result;
 use std::collections::HashMap;
 use crate::error::SchemaDiffError;
@@ -26,13 +27,60 @@ impl SchemaAnalyzer for OpenApiAnalyzer {
     ///
     /// A `CompatibilityReport` detailing the differences and compatibility status.
     fn analyze_compatibility(&self, old: &Schema, new: &Schema) -> Result<CompatibilityReport> {
-        let mut changes = Vec::new();
+        self.analyze_renamed(old, new, &RenameHints::default())
+    }
+
+    /// Analyzes compatibility between two OpenAPI versions, applying the options' rename hints.
+    fn analyze_with_options(&self, old: &Schema, new: &Schema, options: &AnalysisOptions) -> Result<CompatibilityReport> {
+        let report = self.analyze_renamed(old, new, &options.renames)?;
+        options.apply(report)
+    }
+
+    /// Generates a migration path between OpenAPI versions.
+    ///
+    /// # Arguments
+    ///
+    /// * `old` - The source OpenAPI version.
+    /// * `new` - The target OpenAPI version.
+    ///
+    /// # Returns
+    ///
+    /// A `MigrationPlan` detailing the required changes.
+    fn generate_migration_path(&self, old: &Schema, new: &Schema) -> Result<MigrationPlan> {
+        let mut old_document = self.parse_openapi(&old.content)?;
+        let mut new_document = self.parse_openapi(&new.content)?;
+
+        let mut changes = rename_members(&mut old_document, &mut new_document, &RenameHints::default());
+        self.compare_apis(&Self::to_spec(old_document)?, &Self::to_spec(new_document)?, &mut changes)?;
+
+        Ok(MigrationPlan::new(
+            old.version.to_string(),
+            new.version.to_string(),
+            changes,
+        ))
+    }
+
+    fn validate_changes(&self, changes: &[SchemaChange]) -> Result<ValidationResult> {
+        let errors = validation_errors(changes);
+
+        Ok(ValidationResult {
+            is_valid: errors.is_empty(),
+            errors,
+            context: self.build_validation_context(changes),
+        })
+    }
+}
+
+impl OpenApiAnalyzer {
+    /// Analyzes compatibility with declared renames applied
+    fn analyze_renamed(&self, old: &Schema, new: &Schema, hints: &RenameHints) -> Result<CompatibilityReport> {
         let mut metadata = HashMap::new();
 
-        let old_spec: OpenAPI = serde_yaml::from_str(&old.content)
-            .map_err(|e| SchemaDiffError::ParseError(format!("Failed to parse OpenAPI: {}", e)))?;
-        let new_spec: OpenAPI = serde_yaml::from_str(&new.content)
-            .map_err(|e| SchemaDiffError::ParseError(format!("Failed to parse OpenAPI: {}", e)))?;
+        let mut old_document = Self::parse_yaml(&old.content)?;
+        let mut new_document = Self::parse_yaml(&new.content)?;
+        let mut changes = rename_members(&mut old_document, &mut new_document, hints);
+        let old_spec = Self::to_spec(old_document)?;
+        let new_spec = Self::to_spec(new_document)?;
 
         // Compare paths
         for (path, old_path_item) in old_spec.paths.paths.iter() {
@@ -44,10 +92,11 @@ impl SchemaAnalyzer for OpenApiAnalyzer {
                 } else {
                     let mut metadata = HashMap::new();
                     metadata.insert("path".to_string(), path.to_string());
+                    metadata.insert("definition".to_string(), Self::definition(old_item));
                     
-                    changes.push(SchemaChange::new(
-                        ChangeType::Removal,
-                        format!("paths/{}", path),
+                    changes.push(SchemaChange::from_kind(
+                        ChangeKind::PathRemoved,
+                        Location::pointer(["paths", path.as_str()]),
                         format!("Path '{}' was removed", path),
                         metadata,
                     ));
@@ -57,14 +106,15 @@ impl SchemaAnalyzer for OpenApiAnalyzer {
 
         // Check for new paths
         for (path, new_path_item) in new_spec.paths.paths.iter() {
-            if let ReferenceOr::Item(_) = new_path_item {
+            if let ReferenceOr::Item(new_item) = new_path_item {
                 if !old_spec.paths.paths.contains_key(path) {
                     let mut metadata = HashMap::new();
                     metadata.insert("path".to_string(), path.to_string());
+                    metadata.insert("definition".to_string(), Self::definition(new_item));
                     
-                    changes.push(SchemaChange::new(
-                        ChangeType::Addition,
-                        format!("paths/{}", path),
+                    changes.push(SchemaChange::from_kind(
+                        ChangeKind::PathAdded,
+                        Location::pointer(["paths", path.as_str()]),
                         format!("New path '{}' was added", path),
                         metadata,
                     ));
@@ -72,71 +122,30 @@ impl SchemaAnalyzer for OpenApiAnalyzer {
             }
         }
 
+        resolve_positions(&mut changes, &old.content, &new.content);
+
         // Compare versions
         metadata.insert("new_version".to_string(), new_spec.info.version.to_string());
         metadata.insert("old_version".to_string(), old_spec.info.version.to_string());
 
-        let compatibility_score = self.calculate_compatibility_score(&changes);
-        let validation_result = self.validate_changes(&changes)?;
-
-        Ok(CompatibilityReport {
-            changes,
-            compatibility_score: compatibility_score as u8,
-            is_compatible: compatibility_score >= 80,
-            metadata,
-            issues: validation_result.errors.into_iter().map(|err| CompatibilityIssue {
-                severity: match err.code.as_str() {
-                    "API001" => IssueSeverity::Error,
-                    "API002" => IssueSeverity::Warning,
-                    _ => IssueSeverity::Info,
-                },
-                description: err.message,
-                location: err.path.clone(),
-            }).collect(),
-        })
+        Ok(build_report(changes, metadata))
     }
 
-    /// Generates a migration path between OpenAPI versions.
-    ///
-    /// # Arguments
-    ///
-    /// * `old` - The source OpenAPI version.
-    /// * `new` - The target OpenAPI version.
-    ///
-    /// # Returns
-    ///
-    /// A `MigrationPlan` detailing the required changes.
-    fn generate_migration_path(&self, old: &Schema, new: &Schema) -> Result<MigrationPlan> {
-        let old_api = self.parse_openapi(&old.content)?;
-        let new_api = self.parse_openapi(&new.content)?;
-
-        let mut changes = Vec::new();
-        self.compare_apis(&old_api, &new_api, &mut changes)?;
-
-        Ok(MigrationPlan::new(
-            old.version.to_string(),
-            new.version.to_string(),
-            changes,
-        ))
+    /// Parses an OpenAPI document from YAML or JSON
+    fn parse_yaml(content: &str) -> Result<Value> {
+        let document: serde_yaml::Value = serde_yaml::from_str(content)
+            .map_err(|e| SchemaDiffError::ParseError(format!("Failed to parse OpenAPI: {}", e)))?;
+        Ok(serde_json::to_value(document)?)
     }
 
-    fn validate_changes(&self, changes: &[SchemaChange]) -> Result<ValidationResult> {
-        let errors = changes
-            .iter()
-            .filter_map::<ValidationError, _>(|change| self.validate_change(change))
-            .collect::<Vec<ValidationError>>();
-
-        Ok(ValidationResult {
-            is_valid: errors.is_empty(),
-            errors,
-            context: self.build_validation_context(changes),
-        })
+    /// Converts a parsed document into an OpenAPI specification
+    fn to_spec(document: Value) -> Result<OpenAPI> {
+        serde_json::from_value(document)
+            .map_err(|e| SchemaDiffError::ParseError(format!("Failed to parse OpenAPI: {}", e)))
     }
-}
 
-impl OpenApiAnalyzer {
     /// Parses OpenAPI content
-    fn parse_openapi(&self, content: &str) -> Result<OpenAPI> {
+    fn parse_openapi(&self, content: &str) -> Result<Value> {
         serde_json::from_str(content)
             .map_err(|e| SchemaDiffError::ParseError(format!("Failed to parse OpenAPI: {}", e)))
     }
@@ -161,23 +170,29 @@ impl OpenApiAnalyzer {
                     self.compare_path_items(path, old_item, new_item, changes);
                 }
                 None => {
-                    changes.push(SchemaChange::new(
-                        ChangeType::Removal,
-                        format!("/paths/{}", path),
+                    let mut metadata = HashMap::new();
+                    metadata.insert("definition".to_string(), Self::definition(old_item));
+
+                    changes.push(SchemaChange::from_kind(
+                        ChangeKind::PathRemoved,
+                        Location::pointer(["paths", path.as_str()]),
                         format!("Removed path: {}", path),
-                        HashMap::new(),
+                        metadata,
                     ));
                 }
             }
         }
 
-        for path in new.paths.paths.keys() {
+        for (path, new_item) in new.paths.paths.iter() {
             if !old.paths.paths.contains_key(path) {
-                changes.push(SchemaChange::new(
-                    ChangeType::Addition,
-                    format!("/paths/{}", path),
+                let mut metadata = HashMap::new();
+                metadata.insert("definition".to_string(), Self::definition(new_item));
+
+                changes.push(SchemaChange::from_kind(
+                    ChangeKind::PathAdded,
+                    Location::pointer(["paths", path.as_str()]),
                     format!("Added path: {}", path),
-                    HashMap::new(),
+                    metadata,
                 ));
             }
         }
@@ -200,21 +215,20 @@ impl OpenApiAnalyzer {
 
             match (old_op, new_op) {
                 (Some(old_op), Some(new_op)) => {
-                    self.compare_parameters(path, method, &old_op.parameters, &new_op.parameters, changes);
                     self.compare_operation_details(path, method, old_op, new_op, changes);
                 }
                 (Some(_), None) => {
-                    changes.push(SchemaChange::new(
-                        ChangeType::Removal,
-                        format!("/paths{}/{}", path, method),
+                    changes.push(SchemaChange::from_kind(
+                        ChangeKind::OperationRemoved,
+                        Self::operation_location(path, method),
                         format!("HTTP method '{}' was removed from '{}'", method, path),
                         HashMap::new()
                     ));
                 }
                 (None, Some(_)) => {
-                    changes.push(SchemaChange::new(
-                        ChangeType::Addition,
-                        format!("/paths{}/{}", path, method),
+                    changes.push(SchemaChange::from_kind(
+                        ChangeKind::OperationAdded,
+                        Self::operation_location(path, method),
                         format!("HTTP method '{}' was added to '{}'", method, path),
                         HashMap::new(),
                     ));
@@ -224,6 +238,16 @@ impl OpenApiAnalyzer {
         }
     }
 
+    /// Renders an element as canonical JSON, so renamed elements can be recognized
+    fn definition<T: serde::Serialize>(element: &T) -> String {
+        serde_json::to_value(element).map(|value| value.to_string()).unwrap_or_default()
+    }
+
+    /// Builds the JSON Pointer of an operation
+    fn operation_location(path: &str, method: &str) -> Location {
+        Location::pointer(["paths", path, method])
+    }
+
     /// Gets operation for a specific HTTP method
     fn get_operation<'a>(
         &self,
@@ -252,49 +276,6 @@ impl OpenApiAnalyzer {
         metadata
     }
 
-    /// Calculates compatibility score
-    fn calculate_compatibility_score(&self, changes: &[SchemaChange]) -> i32 {
-        let base_score: i32 = 100;
-        let mut deductions: i32 = 0;
-        
-        for change in changes {
-            match change.change_type {
-                ChangeType::Addition => deductions += 5,
-                ChangeType::Removal => deductions += 20,
-                ChangeType::Modification => {
-                    if change.description.contains("optional to required") {
-                        deductions += 25;
-                    } else {
-                        deductions += 10;
-                    }
-                }
-                ChangeType::Rename => deductions += 8,
-            }
-        }
-        
-        base_score.saturating_sub(deductions)
-    }
-
-    #[allow(dead_code)]
-    fn detect_issues(&self, changes: &[SchemaChange]) -> Vec<CompatibilityIssue> {
-        changes.iter()
-            .filter_map(|change| {
-                let severity = match change.change_type {
-                    ChangeType::Removal => IssueSeverity::Error,
-                    ChangeType::Modification => IssueSeverity::Warning,
-                    ChangeType::Rename => IssueSeverity::Info,
-                    ChangeType::Addition => IssueSeverity::Info,
-                };
-
-                Some(CompatibilityIssue {
-                    severity,
-                    description: change.description.clone(),
-                    location: change.location.clone(),
-                })
-            })
-            .collect()
-    }
-
     /// Builds validation context for changes
     fn build_validation_context(&self, changes: &[SchemaChange]) -> HashMap<String, String> {
         let mut context = HashMap::new();
@@ -336,33 +317,39 @@ impl OpenApiAnalyzer {
                 match new_components.schemas.get(name) {
                     Some(new_schema) => {
                         if old_schema != new_schema {
-                            changes.push(SchemaChange::new(
-                                ChangeType::Modification,
-                                format!("/components/schemas/{}", name),
+                            changes.push(SchemaChange::from_kind(
+                                ChangeKind::ComponentSchemaModified,
+                                Location::pointer(["components", "schemas", name.as_str()]),
                                 format!("Schema '{}' was modified", name),
                                 HashMap::new(),
                             ));
                         }
                     }
                     None => {
-                        changes.push(SchemaChange::new(
-                            ChangeType::Removal,
-                            format!("/components/schemas/{}", name),
+                        let mut metadata = HashMap::new();
+                        metadata.insert("definition".to_string(), Self::definition(old_schema));
+
+                        changes.push(SchemaChange::from_kind(
+                            ChangeKind::ComponentSchemaRemoved,
+                            Location::pointer(["components", "schemas", name.as_str()]),
                             format!("Schema '{}' was removed", name),
-                            HashMap::new(),
+                            metadata,
                         ));
                     }
                 }
             }
 
             // Check for new schemas
-            for name in new_components.schemas.keys() {
+            for (name, new_schema) in &new_components.schemas {
                 if !old_components.schemas.contains_key(name) {
-                    changes.push(SchemaChange::new(
-                        ChangeType::Addition,
-                        format!("/components/schemas/{}", name),
+                    let mut metadata = HashMap::new();
+                    metadata.insert("definition".to_string(), Self::definition(new_schema));
+
+                    changes.push(SchemaChange::from_kind(
+                        ChangeKind::ComponentSchemaAdded,
+                        Location::pointer(["components", "schemas", name.as_str()]),
                         format!("Schema '{}' was added", name),
-                        HashMap::new(),
+                        metadata,
                     ));
                 }
             }
@@ -382,18 +369,18 @@ impl OpenApiAnalyzer {
                 match new_components.security_schemes.get(name) {
                     Some(new_scheme) => {
                         if old_scheme != new_scheme {
-                            changes.push(SchemaChange::new(
-                                ChangeType::Modification,
-                                format!("/components/securitySchemes/{}", name),
+                            changes.push(SchemaChange::from_kind(
+                                ChangeKind::SecuritySchemeModified,
+                                Location::pointer(["components", "securitySchemes", name.as_str()]),
                                 format!("Security scheme '{}' was modified", name),
                                 HashMap::new(),
                             ));
                         }
                     }
                     None => {
-                        changes.push(SchemaChange::new(
-                            ChangeType::Removal,
-                            format!("/components/securitySchemes/{}", name),
+                        changes.push(SchemaChange::from_kind(
+                            ChangeKind::SecuritySchemeRemoved,
+                            Location::pointer(["components", "securitySchemes", name.as_str()]),
                             format!("Security scheme '{}' was removed", name),
                             HashMap::new(),
                         ));
@@ -440,7 +427,7 @@ impl OpenApiAnalyzer {
                     Parameter::Cookie { parameter_data, .. } => &parameter_data.name,
                 };
 
-                if let Some(new_param) = new_params.iter().find(|p| {
+                if let Some((index, ReferenceOr::Item(new_param))) = new_params.iter().enumerate().find(|(_, p)| {
                     if let ReferenceOr::Item(p) = p {
                         match p {
                             Parameter::Path { parameter_data, .. } |
@@ -452,34 +439,32 @@ impl OpenApiAnalyzer {
                         false
                     }
                 }) {
-                    if let ReferenceOr::Item(new_param) = new_param {
-                        let old_required = match old_param {
-                            Parameter::Path { parameter_data, .. } |
-                            Parameter::Query { parameter_data, .. } |
-                            Parameter::Header { parameter_data, .. } |
-                            Parameter::Cookie { parameter_data, .. } => parameter_data.required,
-                        };
-
-                        let new_required = match new_param {
-                            Parameter::Path { parameter_data, .. } |
-                            Parameter::Query { parameter_data, .. } |
-                            Parameter::Header { parameter_data, .. } |
-                            Parameter::Cookie { parameter_data, .. } => parameter_data.required,
-                        };
-
-                        if !old_required && new_required {
-                            let mut metadata = HashMap::new();
-                            metadata.insert("path".to_string(), path.to_string());
-                            metadata.insert("method".to_string(), method.to_string());
-                            metadata.insert("parameter".to_string(), param_name.to_string());
-                            
-                            changes.push(SchemaChange::new(
-                                ChangeType::Modification,
-                                format!("paths/{}/{}/parameters/{}", path, method, param_name),
-                                format!("Parameter '{}' changed from optional to required", param_name),
-                                metadata,
-                            ));
-                        }
+                    let old_required = match old_param {
+                        Parameter::Path { parameter_data, .. } |
+                        Parameter::Query { parameter_data, .. } |
+                        Parameter::Header { parameter_data, .. } |
+                        Parameter::Cookie { parameter_data, .. } => parameter_data.required,
+                    };
+
+                    let new_required = match new_param {
+                        Parameter::Path { parameter_data, .. } |
+                        Parameter::Query { parameter_data, .. } |
+                        Parameter::Header { parameter_data, .. } |
+                        Parameter::Cookie { parameter_data, .. } => parameter_data.required,
+                    };
+
+                    if !old_required && new_required {
+                        let mut metadata = HashMap::new();
+                        metadata.insert("path".to_string(), path.to_string());
+                        metadata.insert("method".to_string(), method.to_string());
+                        metadata.insert("parameter".to_string(), param_name.to_string());
+                        
+                        changes.push(SchemaChange::from_kind(
+                            ChangeKind::ParameterBecameRequired,
+                            Self::operation_location(path, method).child("parameters").child(index.to_string()),
+                            format!("Parameter '{}' changed from optional to required", param_name),
+                            metadata,
+                        ));
                     }
                 }
             }
@@ -497,26 +482,26 @@ impl OpenApiAnalyzer {
     ) {
         match (old_body, new_body) {
             (Some(_), None) => {
-                changes.push(SchemaChange::new(
-                    ChangeType::Removal,
-                    format!("/paths{}/{}/requestBody", path, method),
+                changes.push(SchemaChange::from_kind(
+                    ChangeKind::RequestBodyRemoved,
+                    Self::operation_location(path, method).child("requestBody"),
                     "Request body was removed".to_string(),
                     HashMap::new(),
                 ));
             }
             (None, Some(_)) => {
-                changes.push(SchemaChange::new(
-                    ChangeType::Addition,
-                    format!("/paths{}/{}/requestBody", path, method),
+                changes.push(SchemaChange::from_kind(
+                    ChangeKind::RequestBodyAdded,
+                    Self::operation_location(path, method).child("requestBody"),
                     "Request body was added".to_string(),
                     HashMap::new(),
                 ));
             }
             (Some(old_body), Some(new_body)) => {
                 if old_body != new_body {
-                    changes.push(SchemaChange::new(
-                        ChangeType::Modification,
-                        format!("/paths{}/{}/requestBody", path, method),
+                    changes.push(SchemaChange::from_kind(
+                        ChangeKind::RequestBodyModified,
+                        Self::operation_location(path, method).child("requestBody"),
                         "Request body was modified".to_string(),
                         HashMap::new(),
                     ));
@@ -540,18 +525,18 @@ impl OpenApiAnalyzer {
             match new_responses.responses.get(status) {
                 Some(new_response) => {
                     if old_response != new_response {
-                        changes.push(SchemaChange::new(
-                            ChangeType::Modification,
-                            format!("/paths{}/{}/responses/{}", path, method, status),
+                        changes.push(SchemaChange::from_kind(
+                            ChangeKind::ResponseModified,
+                            Self::operation_location(path, method).child("responses").child(status.to_string()),
                             format!("Response '{}' was modified", status),
                             HashMap::new(),
                         ));
                     }
                 }
                 None => {
-                    changes.push(SchemaChange::new(
-                        ChangeType::Removal,
-                        format!("/paths{}/{}/responses/{}", path, method, status),
+                    changes.push(SchemaChange::from_kind(
+                        ChangeKind::ResponseRemoved,
+                        Self::operation_location(path, method).child("responses").child(status.to_string()),
                         format!("Response '{}' was removed", status),
                         HashMap::new(),
                     ));
@@ -562,9 +547,9 @@ impl OpenApiAnalyzer {
         // Check for new responses
         for status in new_responses.responses.keys() {
             if !old_responses.responses.contains_key(status) {
-                changes.push(SchemaChange::new(
-                    ChangeType::Addition,
-                    format!("/paths{}/{}/responses/{}", path, method, status),
+                changes.push(SchemaChange::from_kind(
+                    ChangeKind::ResponseAdded,
+                    Self::operation_location(path, method).child("responses").child(status.to_string()),
                     format!("Response '{}' was added", status),
                     HashMap::new(),
                 ));
@@ -588,30 +573,6 @@ impl OpenApiAnalyzer {
             }
         }
     }
-
-    fn validate_change(&self, change: &SchemaChange) -> Option<ValidationError> {
-        match change.change_type {
-            ChangeType::Removal => Some(ValidationError {
-                message: format!("Breaking change: {}", change.description),
-                path: change.location.clone(),
-                code: "API001".to_string(),
-            }),
-            ChangeType::Modification => {
-                // Check if this is a parameter becoming required or type change
-                if (change.location.contains("parameters") && change.description.contains("required")) ||
-                   (change.location.contains("schema") && change.description.contains("type")) {
-                    Some(ValidationError {
-                        message: format!("Breaking change: {}", change.description),
-                        path: change.location.clone(),
-                        code: "API002".to_string(),
-                    })
-                } else {
-                    None
-                }
-            },
-            _ => None
-        }
-    }
 }
 
 #[cfg(test)]
@@ -72,7 +72,8 @@ fn test_basic_path_changes() {
     
     let change = &result.changes[0];
     assert_eq!(change.change_type, ChangeType::Addition);
-    assert_eq!(change.location, "paths//users/{id}");
+    assert_eq!(change.location, "/paths/~1users~1{id}");
+    assert_eq!(change.location.line, Some(17));
     assert!(change.description.contains("added"));
 }
 
@@ -3,11 +3,12 @@
 //! This module provides functionality for analyzing Protobuf changes and
 //! generating compatibility reports and migration paths.
 
-use protobuf::descriptor::{FileDescriptorProto, DescriptorProto};
-use crate::analyzer::{SchemaAnalyzer, SchemaChange, ChangeType};
-use crate::{Schema, CompatibilityReport, MigrationPlan, ValidationResult, SchemaDiffError};
+use protobuf::descriptor::{FileDescriptorProto, DescriptorProto, FieldDescriptorProto};
+use protobuf::descriptor::field_descriptor_proto::Type;
+use crate::analyzer::{SchemaAnalyzer, AnalysisOptions, SchemaChange, ChangeKind, build_report, declared_rename, resolve_positions, validation_errors};
+use crate::location::Location;
+use crate::{Schema, CompatibilityReport, MigrationPlan, RenameHints, ValidationResult, SchemaDiffError};
 use crate::error::Result;
-use crate::report::{CompatibilityIssue, IssueSeverity, ValidationError};
 use std::collections::HashMap;
 
 /// Analyzes Protobuf changes and generates compatibility reports.
@@ -25,22 +26,13 @@ impl SchemaAnalyzer for ProtobufAnalyzer {
     ///
     /// A `CompatibilityReport` detailing the differences and compatibility status.
     fn analyze_compatibility(&self, old: &Schema, new: &Schema) -> Result<CompatibilityReport> {
-        let old_desc = self.parse_proto(&old.content)?;
-        let new_desc = self.parse_proto(&new.content)?;
-
-        let mut changes = Vec::new();
-        self.compare_descriptors(&old_desc, &new_desc, "", &mut changes)?;
-
-        let compatibility_score = self.calculate_compatibility_score(&changes);
-        let is_compatible = compatibility_score >= 80;
+        self.analyze_renamed(old, new, &RenameHints::default())
+    }
 
-        Ok(CompatibilityReport {
-            compatibility_score: compatibility_score.try_into().unwrap(),
-            is_compatible,
-            changes: changes,
-            issues: vec![],
-            metadata: Default::default(),
-        })
+    /// Analyzes compatibility between two Protobuf versions, applying the options' rename hints.
+    fn analyze_with_options(&self, old: &Schema, new: &Schema, options: &AnalysisOptions) -> Result<CompatibilityReport> {
+        let report = self.analyze_renamed(old, new, &options.renames)?;
+        options.apply(report)
     }
 
     /// Generates a migration path between Protobuf versions.
@@ -58,7 +50,7 @@ impl SchemaAnalyzer for ProtobufAnalyzer {
         let new_desc = self.parse_proto(&new.content)?;
 
         let mut changes = Vec::new();
-        self.compare_descriptors(&old_desc, &new_desc, "", &mut changes)?;
+        self.compare_descriptors(&old_desc, &new_desc, &mut changes)?;
 
         Ok(MigrationPlan::new(
             old.version.to_string(),
@@ -68,20 +60,7 @@ impl SchemaAnalyzer for ProtobufAnalyzer {
     }
 
     fn validate_changes(&self, changes: &[SchemaChange]) -> Result<ValidationResult> {
-        let errors: Vec<ValidationError> = changes
-            .iter()
-            .filter_map(|change| {
-                self.validate_change(change).map(|issue| ValidationError {
-                    message: issue.description,
-                    path: issue.location,
-                    code: format!("PROTO{}", match issue.severity {
-                        IssueSeverity::Error => "001",
-                        IssueSeverity::Warning => "002",
-                        IssueSeverity::Info => "003",
-                    }),
-                })
-            })
-            .collect();
+        let errors = validation_errors(changes);
 
         Ok(ValidationResult {
             is_valid: errors.is_empty(),
@@ -92,6 +71,90 @@ impl SchemaAnalyzer for ProtobufAnalyzer {
 }
 
 impl ProtobufAnalyzer {
+    /// Analyzes compatibility with hinted renames applied
+    fn analyze_renamed(&self, old: &Schema, new: &Schema, hints: &RenameHints) -> Result<CompatibilityReport> {
+        let mut old_desc = self.parse_proto(&old.content)?;
+        let new_desc = self.parse_proto(&new.content)?;
+
+        let mut changes = Self::apply_renames(&mut old_desc, &new_desc, hints);
+        self.compare_descriptors(&old_desc, &new_desc, &mut changes)?;
+        resolve_positions(&mut changes, &old.content, &new.content);
+
+        Ok(build_report(changes, Default::default()))
+    }
+
+    /// Renames messages and fields of the old descriptor as hinted
+    ///
+    /// Message renames are applied first. Fields may be hinted under either
+    /// the old or the new name of their message, but stay in their message.
+    /// A rename is skipped unless the old element only exists in the old
+    /// descriptor and the new element only exists in the new one.
+    ///
+    /// # Returns
+    /// The changes reporting the applied renames
+    fn apply_renames(old: &mut FileDescriptorProto, new: &FileDescriptorProto, hints: &RenameHints) -> Vec<SchemaChange> {
+        let (old_package, new_package) = (Self::package_location(old), Self::package_location(new));
+        let message_name = |location: &Location, package: &Location| match location.parent() {
+            Some(parent) if parent == *package => location.last_segment().map(str::to_string),
+            _ => None,
+        };
+        let has_message = |desc: &FileDescriptorProto, name: &str| desc.message_type.iter().any(|m| m.name() == name);
+        let has_field = |desc: &FileDescriptorProto, message: &str, field: &str| {
+            desc.message_type.iter().any(|m| m.name() == message && m.field.iter().any(|f| f.name() == field))
+        };
+
+        let mut changes = Vec::new();
+        let mut renamed_messages = Vec::new();
+        for (from, to) in hints.iter() {
+            let (Some(old_name), Some(new_name)) = (message_name(from, &old_package), message_name(to, &new_package)) else {
+                continue;
+            };
+            if !has_message(old, &old_name) || has_message(old, &new_name) || has_message(new, &old_name) || !has_message(new, &new_name) {
+                continue;
+            }
+
+            if let Some(message) = old.message_type.iter_mut().find(|m| m.name() == old_name) {
+                message.set_name(new_name.clone());
+                changes.push(declared_rename(ChangeKind::MessageRenamed, from, to, "hint"));
+                renamed_messages.push((old_name, new_name));
+            }
+        }
+
+        for (from, to) in hints.iter() {
+            let old_message = from.parent().and_then(|parent| message_name(&parent, &old_package));
+            let new_message = to.parent().and_then(|parent| message_name(&parent, &new_package));
+            let (Some(message), Some(new_message)) = (old_message, new_message) else {
+                continue;
+            };
+            let (Some(old_field), Some(new_field)) = (from.last_segment(), to.last_segment()) else {
+                continue;
+            };
+            let message = renamed_messages
+                .iter()
+                .find(|(old_name, _)| *old_name == message)
+                .map_or(message, |(_, new_name)| new_name.clone());
+            if message != new_message
+                || !has_field(old, &message, old_field)
+                || has_field(old, &message, new_field)
+                || has_field(new, &message, old_field)
+                || !has_field(new, &message, new_field)
+            {
+                continue;
+            }
+
+            let field = old
+                .message_type
+                .iter_mut()
+                .find(|m| m.name() == message)
+                .and_then(|m| m.field.iter_mut().find(|f| f.name() == old_field));
+            if let Some(field) = field {
+                field.set_name(new_field.to_string());
+                changes.push(declared_rename(ChangeKind::FieldRenamed, from, to, "hint"));
+            }
+        }
+        changes
+    }
+
     /// Parses protobuf content into a FileDescriptorProto
     fn parse_proto(&self, content: &str) -> Result<FileDescriptorProto> {
         // Basic implementation using protobuf parser
@@ -101,37 +164,48 @@ impl ProtobufAnalyzer {
         }
     }
 
+    /// Builds the qualified name of a file's package
+    fn package_location(desc: &FileDescriptorProto) -> Location {
+        Location::qualified(desc.package().split('.').filter(|part| !part.is_empty()))
+    }
+
     /// Compares two protobuf descriptors
+    ///
+    /// Removed elements are located in the old descriptor's package, and all
+    /// other changes in the new descriptor's package.
     fn compare_descriptors(
         &self,
         old: &FileDescriptorProto,
         new: &FileDescriptorProto,
-        path: &str,
         changes: &mut Vec<SchemaChange>,
     ) -> Result<()> {
+        let (old_path, new_path) = (Self::package_location(old), Self::package_location(new));
+        let old_index = Self::index_by_name(&old.message_type, DescriptorProto::name);
+        let new_index = Self::index_by_name(&new.message_type, DescriptorProto::name);
+
         // Compare messages
         for old_msg in &old.message_type {
-            if let Some(new_msg) = new.message_type.iter().find(|m| m.name() == old_msg.name()) {
-                self.compare_messages(old_msg, new_msg, path, changes)?;
+            if let Some(new_msg) = new_index.get(old_msg.name()) {
+                self.compare_messages(old_msg, new_msg, &old_path, &new_path, changes)?;
             } else {
-                changes.push(SchemaChange {
-                    change_type: ChangeType::Removal,
-                    location: format!("{}/{}", path, old_msg.name()),
-                    description: format!("Message '{}' was removed", old_msg.name()),
-                    metadata: Default::default(),
-                });
+                changes.push(SchemaChange::from_kind(
+                    ChangeKind::MessageRemoved,
+                    old_path.child(old_msg.name()),
+                    format!("Message '{}' was removed", old_msg.name()),
+                    Self::message_metadata(old_msg),
+                ));
             }
         }
 
         // Check for new messages
         for new_msg in &new.message_type {
-            if !old.message_type.iter().any(|m| m.name() == new_msg.name()) {
-                changes.push(SchemaChange {
-                    change_type: ChangeType::Addition,
-                    location: format!("{}/{}", path, new_msg.name()),
-                    description: format!("Message '{}' was added", new_msg.name()),
-                    metadata: Default::default(),
-                });
+            if !old_index.contains_key(new_msg.name()) {
+                changes.push(SchemaChange::from_kind(
+                    ChangeKind::MessageAdded,
+                    new_path.child(new_msg.name()),
+                    format!("Message '{}' was added", new_msg.name()),
+                    Self::message_metadata(new_msg),
+                ));
             }
         }
 
@@ -143,32 +217,47 @@ impl ProtobufAnalyzer {
         &self,
         old_msg: &DescriptorProto,
         new_msg: &DescriptorProto,
-        path: &str,
+        old_path: &Location,
+        new_path: &Location,
         changes: &mut Vec<SchemaChange>,
     ) -> Result<()> {
-        self.compare_fields(path, old_msg, new_msg, changes);
+        self.compare_fields(old_path, new_path, old_msg, new_msg, changes);
         Ok(())
     }
 
     fn compare_fields(
         &self,
-        path: &str,
+        old_path: &Location,
+        new_path: &Location,
         old_msg: &DescriptorProto,
         new_msg: &DescriptorProto,
         changes: &mut Vec<SchemaChange>,
     ) {
+        let old_index = Self::index_by_name(&old_msg.field, FieldDescriptorProto::name);
+        let new_index = Self::index_by_name(&new_msg.field, FieldDescriptorProto::name);
+        let mut old_numbers: HashMap<i32, Vec<&FieldDescriptorProto>> = HashMap::new();
+        for old_field in &old_msg.field {
+            old_numbers.entry(old_field.number()).or_default().push(old_field);
+        }
+
         for old_field in old_msg.field.iter() {
-            if let Some(new_field) = new_msg.field.iter().find(|f| f.name() == old_field.name()) {
+            if let Some(new_field) = new_index.get(old_field.name()) {
                 if old_field.type_() != new_field.type_() {
                     let mut metadata = HashMap::new();
                     metadata.insert("message".to_string(), old_msg.name().to_string());
                     metadata.insert("field".to_string(), old_field.name().to_string());
                     metadata.insert("old_type".to_string(), format!("{:?}", old_field.type_()));
                     metadata.insert("new_type".to_string(), format!("{:?}", new_field.type_()));
-                    
-                    changes.push(SchemaChange::new(
-                        ChangeType::Modification,
-                        format!("{}/{}/{}", path, old_msg.name(), old_field.name()),
+
+                    let kind = if Self::wire_compatible(old_field.type_(), new_field.type_()) {
+                        ChangeKind::FieldTypeWireCompatible
+                    } else {
+                        ChangeKind::FieldTypeChanged
+                    };
+
+                    changes.push(SchemaChange::from_kind(
+                        kind,
+                        new_path.child(new_msg.name()).child(new_field.name()),
                         format!(
                             "Field '{}' type changed from {:?} to {:?}",
                             old_field.name(),
@@ -178,73 +267,124 @@ impl ProtobufAnalyzer {
                         metadata,
                     ));
                 }
+
+                if old_field.number() != new_field.number() {
+                    let mut metadata = HashMap::new();
+                    metadata.insert("message".to_string(), old_msg.name().to_string());
+                    metadata.insert("field".to_string(), old_field.name().to_string());
+                    metadata.insert("old_number".to_string(), old_field.number().to_string());
+                    metadata.insert("new_number".to_string(), new_field.number().to_string());
+
+                    changes.push(SchemaChange::from_kind(
+                        ChangeKind::FieldNumberChanged,
+                        new_path.child(new_msg.name()).child(new_field.name()),
+                        format!(
+                            "Field '{}' number changed from {} to {}",
+                            old_field.name(),
+                            old_field.number(),
+                            new_field.number()
+                        ),
+                        metadata,
+                    ));
+                }
             } else {
-                let mut metadata = HashMap::new();
-                metadata.insert("message".to_string(), old_msg.name().to_string());
-                metadata.insert("field".to_string(), old_field.name().to_string());
-                
-                changes.push(SchemaChange::new(
-                    ChangeType::Removal,
-                    format!("{}/{}/{}", path, old_msg.name(), old_field.name()),
+                changes.push(SchemaChange::from_kind(
+                    ChangeKind::FieldRemoved,
+                    old_path.child(old_msg.name()).child(old_field.name()),
                     format!("Field '{}' was removed", old_field.name()),
-                    metadata,
+                    Self::field_metadata(old_msg, old_field),
                 ));
             }
         }
 
         // Check for new fields
         for new_field in new_msg.field.iter() {
-            if !old_msg.field.iter().any(|f| f.name() == new_field.name()) {
-                let mut metadata = HashMap::new();
-                metadata.insert("message".to_string(), new_msg.name().to_string());
-                metadata.insert("field".to_string(), new_field.name().to_string());
-                
-                changes.push(SchemaChange::new(
-                    ChangeType::Addition,
-                    format!("{}/{}/{}", path, new_msg.name(), new_field.name()),
-                    format!("New field '{}' was added", new_field.name()),
-                    metadata,
-                ));
+            if old_index.contains_key(new_field.name()) {
+                continue;
             }
+
+            let mut metadata = Self::field_metadata(new_msg, new_field);
+            let reused = old_numbers.get(&new_field.number()).into_iter().flatten().find(|f| {
+                f.type_() != new_field.type_() && !new_index.contains_key(f.name())
+            });
+
+            let (kind, description) = match reused {
+                Some(old_field) => {
+                    metadata.insert("reused_from".to_string(), old_field.name().to_string());
+                    (
+                        ChangeKind::FieldNumberReused,
+                        format!(
+                            "New field '{}' reuses number {} of removed field '{}'",
+                            new_field.name(),
+                            new_field.number(),
+                            old_field.name()
+                        ),
+                    )
+                }
+                None => (ChangeKind::FieldAdded, format!("New field '{}' was added", new_field.name())),
+            };
+
+            changes.push(SchemaChange::from_kind(
+                kind,
+                new_path.child(new_msg.name()).child(new_field.name()),
+                description,
+                metadata,
+            ));
         }
     }
 
-    /// Validates a single schema change
-    fn validate_change(&self, change: &SchemaChange) -> Option<CompatibilityIssue> {
-        match change.change_type {
-            ChangeType::Removal => Some(CompatibilityIssue {
-                severity: IssueSeverity::Error,
-                description: format!("Breaking change: {}", change.description),
-                location: change.location.clone(),
-            }),
-            ChangeType::Modification => Some(CompatibilityIssue {
-                severity: IssueSeverity::Warning,
-                description: format!("Potential compatibility issue: {}", change.description),
-                location: change.location.clone(),
-            }),
-            ChangeType::Rename => {
-                todo!("Implement handling for Rename change type");
-            },
-            _ => None,
+    /// Indexes messages or fields by name, keeping the first of duplicates
+    ///
+    /// Elements are matched through these indexes rather than by scanning
+    /// the other descriptor, so comparing large proto sets stays linear.
+    fn index_by_name<T>(elements: &[T], name: fn(&T) -> &str) -> HashMap<&str, &T> {
+        let mut index = HashMap::new();
+        for element in elements {
+            index.entry(name(element)).or_insert(element);
         }
+        index
     }
 
-    /// Calculates compatibility score for protobuf changes
-    fn calculate_compatibility_score(&self, changes: &[SchemaChange]) -> i32 {
-        let base_score: i32 = 100;
-        let mut deductions: i32 = 0;
-        
-        for change in changes {
-            match change.change_type {
-                ChangeType::Addition => (),
-                ChangeType::Removal => deductions += 20,
-                ChangeType::Modification => deductions += 10,
-                ChangeType::Rename => {
-                    todo!("Implement handling for Rename change type");
-                },
-            }
-        }
-        
-        base_score.saturating_sub(deductions)
+    /// Builds the metadata describing a message
+    ///
+    /// Records the message's fields as its `definition`, so renamed messages
+    /// can be recognized.
+    fn message_metadata(msg: &DescriptorProto) -> HashMap<String, String> {
+        let fields: Vec<String> = msg
+            .field
+            .iter()
+            .map(|field| format!("{:?} {} = {}", field.type_(), field.name(), field.number()))
+            .collect();
+
+        let mut metadata = HashMap::new();
+        metadata.insert("message".to_string(), msg.name().to_string());
+        metadata.insert("definition".to_string(), fields.join("; "));
+        metadata
+    }
+
+    /// Builds the metadata describing a field
+    fn field_metadata(msg: &DescriptorProto, field: &FieldDescriptorProto) -> HashMap<String, String> {
+        let mut metadata = HashMap::new();
+        metadata.insert("message".to_string(), msg.name().to_string());
+        metadata.insert("field".to_string(), field.name().to_string());
+        metadata.insert("number".to_string(), field.number().to_string());
+        metadata.insert("type".to_string(), format!("{:?}", field.type_()));
+        metadata
     }
-} 
\ No newline at end of file
+
+    /// Checks whether values of one field type can be decoded as another
+    ///
+    /// Follows the Protobuf language guide's list of compatible type changes.
+    fn wire_compatible(old: Type, new: Type) -> bool {
+        let group = |t: Type| match t {
+            Type::TYPE_INT32 | Type::TYPE_UINT32 | Type::TYPE_INT64 | Type::TYPE_UINT64
+            | Type::TYPE_BOOL | Type::TYPE_ENUM => 1,
+            Type::TYPE_SINT32 | Type::TYPE_SINT64 => 2,
+            Type::TYPE_FIXED32 | Type::TYPE_SFIXED32 => 3,
+            Type::TYPE_FIXED64 | Type::TYPE_SFIXED64 => 4,
+            Type::TYPE_STRING | Type::TYPE_BYTES => 5,
+            _ => 0,
+        };
+        group(old) != 0 && group(old) == group(new)
+    }
+}
@@ -0,0 +1,327 @@
+//! Rename detection across analyzers
+//!
+//! Analyzers report a renamed element as a removal plus an addition. This
+//! module pairs such changes by the structure analyzers record in their
+//! metadata and by the similarity of the element names, and merges each pair
+//! into a single rename. It also applies renames declared by
+//! [`RenameHints`] or `x-renamed-from` annotations to JSON documents.
+
+use crate::analyzer::{ChangeKind, ChangeType, SchemaChange};
+use crate::location::{Location, LocationStyle};
+use crate::renames::RenameHints;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+
+/// Metadata keys describing the structure of an added or removed element
+///
+/// Analyzers record whichever of these apply, e.g. a column's `type` and
+/// `constraints`, a Protobuf field's `number` and `type`, or the canonical
+/// JSON `definition` of a property.
+pub(crate) const STRUCTURE_KEYS: [&str; 4] = ["type", "number", "constraints", "definition"];
+
+/// Minimum confidence for a removal and an addition to be reported as a rename
+///
+/// Structure alone reaches 0.75, so siblings of the same type are only
+/// paired if their names are alike too.
+pub(crate) const RENAME_CONFIDENCE: f64 = 0.8;
+
+/// JSON Schema and OpenAPI keyword holding the old name of a renamed member
+pub(crate) const RENAMED_FROM: &str = "x-renamed-from";
+
+/// Merges removals and additions that look like renames into single changes
+///
+/// A removal and an addition are candidates if they are siblings of kinds
+/// that can be renamed into each other. Their confidence weighs the share of
+/// matching [`STRUCTURE_KEYS`] at three quarters and the similarity of their
+/// names at one quarter; candidates at or above [`RENAME_CONFIDENCE`] are
+/// paired greedily from the most confident down. Additions are grouped by
+/// parent first, so only siblings are ever scored against each other.
+///
+/// Each rename takes the place of its removal, is located at the new element
+/// and records `old_location` and `confidence` in its metadata.
+pub(crate) fn detect_renames(changes: Vec<SchemaChange>) -> Vec<SchemaChange> {
+    let mut siblings: HashMap<Option<Location>, Vec<usize>> = HashMap::new();
+    for (new_index, added) in changes.iter().enumerate() {
+        if added.change_type == ChangeType::Addition {
+            siblings.entry(added.location.parent()).or_default().push(new_index);
+        }
+    }
+
+    let mut candidates = Vec::new();
+    for (old_index, removed) in changes.iter().enumerate() {
+        if removed.change_type != ChangeType::Removal {
+            continue;
+        }
+        for &new_index in siblings.get(&removed.location.parent()).into_iter().flatten() {
+            let added = &changes[new_index];
+            if renamed_kind(removed.kind, added.kind).is_none() || removed.location.style() != added.location.style() {
+                continue;
+            }
+            if let Some(confidence) = confidence(removed, added).filter(|confidence| *confidence >= RENAME_CONFIDENCE) {
+                candidates.push((old_index, new_index, confidence));
+            }
+        }
+    }
+    candidates.sort_by(|a, b| b.2.total_cmp(&a.2).then(a.0.cmp(&b.0)).then(a.1.cmp(&b.1)));
+
+    let mut renames: HashMap<usize, (usize, f64)> = HashMap::new();
+    let mut renamed_to = HashSet::new();
+    for (old_index, new_index, confidence) in candidates {
+        if !renames.contains_key(&old_index) && !renamed_to.contains(&new_index) {
+            renames.insert(old_index, (new_index, confidence));
+            renamed_to.insert(new_index);
+        }
+    }
+    if renames.is_empty() {
+        return changes;
+    }
+
+    let mut slots: Vec<Option<SchemaChange>> = changes.into_iter().map(Some).collect();
+    let mut merged = Vec::with_capacity(slots.len() - renames.len());
+    for index in 0..slots.len() {
+        if renamed_to.contains(&index) {
+            continue;
+        }
+        match renames.get(&index) {
+            Some(&(new_index, confidence)) => {
+                let removed = slots[index].take().expect("each removal is renamed once");
+                let added = slots[new_index].take().expect("each addition is renamed once");
+                merged.push(rename(removed, added, confidence));
+            }
+            None => merged.extend(slots[index].take()),
+        }
+    }
+    merged
+}
+
+/// Returns the rename kind for a removal and an addition, if they can be one
+///
+/// Only kinds with a dedicated rename kind are paired; a generic `Removed`
+/// is never merged into the milder `Renamed`.
+fn renamed_kind(removed: ChangeKind, added: ChangeKind) -> Option<ChangeKind> {
+    let renamed = match (removed, added) {
+        (ChangeKind::PropertyRemoved, ChangeKind::PropertyAdded | ChangeKind::RequiredPropertyAdded) => ChangeKind::PropertyRenamed,
+        (ChangeKind::PathRemoved, ChangeKind::PathAdded) => ChangeKind::PathRenamed,
+        (ChangeKind::ComponentSchemaRemoved, ChangeKind::ComponentSchemaAdded) => ChangeKind::ComponentSchemaRenamed,
+        (ChangeKind::MessageRemoved, ChangeKind::MessageAdded) => ChangeKind::MessageRenamed,
+        (ChangeKind::FieldRemoved, ChangeKind::FieldAdded) => ChangeKind::FieldRenamed,
+        (ChangeKind::TableRemoved, ChangeKind::TableAdded) => ChangeKind::TableRenamed,
+        (ChangeKind::ColumnRemoved, ChangeKind::ColumnAdded | ChangeKind::NotNullColumnAdded) => ChangeKind::ColumnRenamed,
+        _ => return None,
+    };
+    Some(renamed)
+}
+
+/// Returns the word describing the element of a rename kind
+fn noun(kind: ChangeKind) -> &'static str {
+    match kind {
+        ChangeKind::PropertyRenamed => "Property",
+        ChangeKind::PathRenamed => "Path",
+        ChangeKind::ComponentSchemaRenamed => "Schema",
+        ChangeKind::MessageRenamed => "Message",
+        ChangeKind::FieldRenamed => "Field",
+        ChangeKind::TableRenamed => "Table",
+        ChangeKind::ColumnRenamed => "Column",
+        _ => "Element",
+    }
+}
+
+/// Rates how likely an addition is a renamed removal
+///
+/// # Returns
+/// The confidence between 0 and 1, or `None` if neither change records its structure
+fn confidence(removed: &SchemaChange, added: &SchemaChange) -> Option<f64> {
+    let (mut compared, mut matching) = (0, 0);
+    for key in STRUCTURE_KEYS {
+        match (removed.metadata.get(key), added.metadata.get(key)) {
+            (None, None) => {}
+            (old, new) => {
+                compared += 1;
+                if old == new {
+                    matching += 1;
+                }
+            }
+        }
+    }
+    if compared == 0 {
+        return None;
+    }
+
+    let structure = matching as f64 / compared as f64;
+    let name = name_similarity(
+        removed.location.last_segment().unwrap_or_default(),
+        added.location.last_segment().unwrap_or_default(),
+    );
+    Some(0.75 * structure + 0.25 * name)
+}
+
+/// Measures how similar two element names are
+///
+/// Computes the Dice coefficient of the names' character bigrams, ignoring
+/// case and any characters other than letters and digits, so `user_id` and
+/// `userId` are identical.
+pub(crate) fn name_similarity(old: &str, new: &str) -> f64 {
+    let normalize = |name: &str| -> Vec<char> {
+        name.chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(char::to_lowercase)
+            .collect()
+    };
+    let (old, new) = (normalize(old), normalize(new));
+    if old == new {
+        return 1.0;
+    }
+
+    let mut old_bigrams: Vec<_> = old.windows(2).collect();
+    let new_bigrams: Vec<_> = new.windows(2).collect();
+    let total = old_bigrams.len() + new_bigrams.len();
+    let mut common = 0;
+    for bigram in new_bigrams {
+        if let Some(position) = old_bigrams.iter().position(|old| *old == bigram) {
+            old_bigrams.swap_remove(position);
+            common += 1;
+        }
+    }
+
+    if total == 0 {
+        0.0
+    } else {
+        (2 * common) as f64 / total as f64
+    }
+}
+
+/// Merges a removal and an addition into a rename
+fn rename(removed: SchemaChange, added: SchemaChange, confidence: f64) -> SchemaChange {
+    let kind = renamed_kind(removed.kind, added.kind).expect("only renamable changes are paired");
+    let mut metadata = added.metadata;
+    metadata.insert("confidence".to_string(), format!("{:.2}", confidence));
+
+    renamed(kind, &removed.location, added.location, metadata)
+}
+
+/// Builds the change for a rename declared by a hint or an annotation
+///
+/// Declared renames have a confidence of 1 and record whether a `hint` or an
+/// `annotation` declared them as `declared_by` metadata.
+pub(crate) fn declared_rename(kind: ChangeKind, old: &Location, new: &Location, declared_by: &str) -> SchemaChange {
+    let mut metadata = HashMap::new();
+    metadata.insert("confidence".to_string(), "1.00".to_string());
+    metadata.insert("declared_by".to_string(), declared_by.to_string());
+
+    renamed(kind, old, new.clone(), metadata)
+}
+
+/// Builds a rename change located at the new element
+fn renamed(kind: ChangeKind, old: &Location, new: Location, mut metadata: HashMap<String, String>) -> SchemaChange {
+    let description = format!(
+        "{} '{}' was renamed to '{}'",
+        noun(kind),
+        old.last_segment().unwrap_or_default(),
+        new.last_segment().unwrap_or_default()
+    );
+    metadata.insert("old_location".to_string(), old.to_string());
+
+    SchemaChange::from_kind(kind, new, description, metadata)
+}
+
+/// Applies declared renames to the members of two JSON documents
+///
+/// Removes `x-renamed-from` annotations from both documents, then moves each
+/// member of `old` that a hint or an annotation in `new` declares renamed to
+/// its new location, so the analyzer compares it with its new version. A
+/// rename is skipped unless the old member only exists in `old` and the new
+/// member only exists in `new`.
+///
+/// # Returns
+/// The changes reporting the applied renames
+pub(crate) fn rename_members(old: &mut Value, new: &mut Value, hints: &RenameHints) -> Vec<SchemaChange> {
+    let mut declared: Vec<(Location, Location, &str)> = hints
+        .iter()
+        .filter(|(from, to)| from.style() == LocationStyle::Pointer && to.style() == LocationStyle::Pointer)
+        .map(|(from, to)| (from.clone(), to.clone(), "hint"))
+        .collect();
+
+    take_annotations(old, &Location::root(), &mut Vec::new());
+    let mut annotated = Vec::new();
+    take_annotations(new, &Location::root(), &mut annotated);
+    declared.extend(annotated.into_iter().map(|(from, to)| (from, to, "annotation")));
+
+    let mut changes = Vec::new();
+    for (from, to, declared_by) in declared {
+        let (Some(from_parent), Some(to_parent)) = (from.parent(), to.parent()) else {
+            continue;
+        };
+        let (from_pointer, to_pointer) = (from.to_string(), to.to_string());
+        if old.pointer(&from_pointer).is_none()
+            || old.pointer(&to_pointer).is_some()
+            || new.pointer(&from_pointer).is_some()
+            || new.pointer(&to_pointer).is_none()
+            || !old.pointer(&to_parent.to_string()).is_some_and(Value::is_object)
+        {
+            continue;
+        }
+
+        let member = old
+            .pointer_mut(&from_parent.to_string())
+            .and_then(Value::as_object_mut)
+            .and_then(|object| object.remove(from.last_segment().unwrap_or_default()));
+        let target = old.pointer_mut(&to_parent.to_string()).and_then(Value::as_object_mut);
+        if let (Some(member), Some(target)) = (member, target) {
+            target.insert(to.last_segment().unwrap_or_default().to_string(), member);
+            let kind = member_rename_kind(&to);
+            if kind == ChangeKind::PropertyRenamed && from_parent == to_parent {
+                rename_required(old, &from_parent, from.last_segment().unwrap_or_default(), to.last_segment().unwrap_or_default());
+            }
+            changes.push(declared_rename(kind, &from, &to, declared_by));
+        }
+    }
+    changes
+}
+
+/// Renames a property in the `required` keyword of the schema owning a `properties` map
+fn rename_required(document: &mut Value, properties: &Location, old_name: &str, new_name: &str) {
+    let required = properties
+        .parent()
+        .and_then(|schema| document.pointer_mut(&schema.to_string()))
+        .and_then(|schema| schema.get_mut("required"))
+        .and_then(Value::as_array_mut);
+    for name in required.into_iter().flatten() {
+        if name == old_name {
+            *name = Value::String(new_name.to_string());
+        }
+    }
+}
+
+/// Removes `x-renamed-from` annotations from a document, collecting the renames they declare
+fn take_annotations(value: &mut Value, location: &Location, renames: &mut Vec<(Location, Location)>) {
+    match value {
+        Value::Object(object) => {
+            if let (Some(Value::String(old_name)), Some(parent)) = (object.remove(RENAMED_FROM), location.parent()) {
+                renames.push((parent.child(old_name), location.clone()));
+            }
+            for (key, child) in object.iter_mut() {
+                take_annotations(child, &location.child(key.as_str()), renames);
+            }
+        }
+        Value::Array(items) => {
+            for (index, item) in items.iter_mut().enumerate() {
+                take_annotations(item, &location.child(index.to_string()), renames);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Returns the rename kind of a JSON document member by where it lives
+fn member_rename_kind(location: &Location) -> ChangeKind {
+    let parent = location.parent().unwrap_or_else(Location::root);
+    match parent.segments() {
+        [paths] if paths == "paths" => ChangeKind::PathRenamed,
+        [components, schemas] if components == "components" && schemas == "schemas" => ChangeKind::ComponentSchemaRenamed,
+        [.., properties] if properties == "properties" => ChangeKind::PropertyRenamed,
+        _ => ChangeKind::Renamed,
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,240 @@
+use super::*;
+use crate::{AnalysisOptions, JsonSchemaAnalyzer, Location, ProtobufAnalyzer, Schema, SchemaAnalyzer, SchemaFormat, SqlAnalyzer};
+use semver::Version;
+
+fn analyze(analyzer: &dyn SchemaAnalyzer, format: SchemaFormat, old: &str, new: &str) -> Vec<SchemaChange> {
+    let schema = |content: &str| Schema::new(format, content.to_string(), Version::new(1, 0, 0));
+    analyzer.analyze_compatibility(&schema(old), &schema(new)).unwrap().changes
+}
+
+#[test]
+fn test_sql_column_rename() {
+    let changes = analyze(
+        &SqlAnalyzer,
+        SchemaFormat::SqlDDL,
+        "CREATE TABLE users (id INT, email_address TEXT NOT NULL, age INT);",
+        "CREATE TABLE users (id INT, email TEXT NOT NULL, years TEXT);",
+    );
+
+    let rename = changes.iter().find(|c| c.change_type == ChangeType::Rename).unwrap();
+    assert_eq!(rename.kind, ChangeKind::ColumnRenamed);
+    assert_eq!(rename.location, "users.email");
+    assert_eq!(rename.metadata["old_location"], "users.email_address");
+    assert!(rename.metadata["confidence"].parse::<f64>().unwrap() >= RENAME_CONFIDENCE);
+    assert_eq!(rename.description, "Column 'email_address' was renamed to 'email'");
+
+    let kinds: Vec<_> = changes.iter().map(|c| c.kind).collect();
+    assert!(kinds.contains(&ChangeKind::ColumnRemoved) && kinds.contains(&ChangeKind::ColumnAdded));
+    assert_eq!(changes.len(), 3);
+}
+
+#[test]
+fn test_protobuf_field_rename_keeps_number() {
+    let message = |field: &str| {
+        format!(
+            "message_type {{\n  name: \"User\"\n  field {{ name: \"id\" number: 1 type: TYPE_INT64 }}\n  field {{ name: \"{}\" number: 2 type: TYPE_STRING }}\n}}\n",
+            field
+        )
+    };
+    let changes = analyze(&ProtobufAnalyzer, SchemaFormat::Protobuf, &message("display_name"), &message("full_name"));
+
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].kind, ChangeKind::FieldRenamed);
+    assert_eq!(changes[0].metadata["old_location"], "User.display_name");
+    assert_eq!(changes[0].location, "User.full_name");
+}
+
+#[test]
+fn test_json_schema_property_rename() {
+    let changes = analyze(
+        &JsonSchemaAnalyzer,
+        SchemaFormat::JsonSchema,
+        r#"{"properties": {"userName": {"type": "string", "maxLength": 64}, "age": {"type": "integer"}}}"#,
+        r#"{"properties": {"user_name": {"type": "string", "maxLength": 64}, "tags": {"type": "array"}}}"#,
+    );
+
+    let kinds: Vec<_> = changes.iter().map(|c| c.kind).collect();
+    assert_eq!(kinds.iter().filter(|kind| **kind == ChangeKind::PropertyRenamed).count(), 1);
+    assert!(kinds.contains(&ChangeKind::PropertyRemoved) && kinds.contains(&ChangeKind::PropertyAdded));
+    let rename = changes.iter().find(|c| c.kind == ChangeKind::PropertyRenamed).unwrap();
+    assert_eq!(rename.metadata["confidence"], "1.00");
+}
+
+#[test]
+fn test_changes_without_structure_are_not_paired() {
+    let removed = SchemaChange::new(ChangeType::Removal, Location::parse("users.a"), "removed", Default::default());
+    let added = SchemaChange::new(ChangeType::Addition, Location::parse("users.a_b"), "added", Default::default());
+
+    let changes = detect_renames(vec![removed, added]);
+    assert_eq!(changes.len(), 2);
+}
+
+#[test]
+fn test_generic_removals_are_not_softened_into_renames() {
+    let structure = |name: &str| HashMap::from([("name".to_string(), name.to_string()), ("type".to_string(), "string".to_string())]);
+    let removed = SchemaChange::from_kind(ChangeKind::Removed, Location::parse("users.email"), "removed", structure("email"));
+    let added = SchemaChange::from_kind(ChangeKind::Added, Location::parse("users.email_address"), "added", structure("email_address"));
+
+    let kinds: Vec<_> = detect_renames(vec![removed, added]).iter().map(|change| change.kind).collect();
+    assert_eq!(kinds, [ChangeKind::Removed, ChangeKind::Added]);
+}
+
+#[test]
+fn test_name_similarity() {
+    assert_eq!(name_similarity("user_id", "userId"), 1.0);
+    assert_eq!(name_similarity("abc", "xyz"), 0.0);
+    assert!(name_similarity("email", "email_address") > 0.5);
+}
+
+#[test]
+fn test_unrelated_names_of_same_type_are_not_paired() {
+    let changes = analyze(
+        &SqlAnalyzer,
+        SchemaFormat::SqlDDL,
+        "CREATE TABLE users (id INT, name TEXT);",
+        "CREATE TABLE users (id INT, email TEXT);",
+    );
+
+    assert!(changes.iter().all(|c| c.change_type != ChangeType::Rename));
+}
+
+fn analyze_hinted(analyzer: &dyn SchemaAnalyzer, format: SchemaFormat, old: &str, new: &str, hints: RenameHints) -> Vec<SchemaChange> {
+    let schema = |content: &str| Schema::new(format, content.to_string(), Version::new(1, 0, 0));
+    let options = AnalysisOptions::default().with_renames(hints);
+    analyzer.analyze_with_options(&schema(old), &schema(new), &options).unwrap().changes
+}
+
+#[test]
+fn test_json_schema_annotation_declares_rename() {
+    let changes = analyze(
+        &JsonSchemaAnalyzer,
+        SchemaFormat::JsonSchema,
+        r#"{"properties": {"login": {"type": "string"}}}"#,
+        r#"{"properties": {"username": {"type": "string", "maxLength": 32, "x-renamed-from": "login"}}}"#,
+    );
+
+    let rename = changes.iter().find(|c| c.kind == ChangeKind::PropertyRenamed).unwrap();
+    assert_eq!(rename.location, "/properties/username");
+    assert_eq!(rename.metadata["old_location"], "/properties/login");
+    assert_eq!(rename.metadata["declared_by"], "annotation");
+    assert!(changes.iter().all(|c| c.kind != ChangeKind::PropertyRemoved && c.kind != ChangeKind::PropertyAdded));
+    assert!(changes.iter().all(|c| !c.location.to_string().contains(RENAMED_FROM)));
+    assert!(changes.iter().any(|c| c.location.to_string().starts_with("/properties/username/") && c.kind != ChangeKind::PropertyRenamed));
+}
+
+#[test]
+fn test_sql_comment_declares_rename() {
+    let changes = analyze(
+        &SqlAnalyzer,
+        SchemaFormat::SqlDDL,
+        "CREATE TABLE users (id INT, mail VARCHAR(100));",
+        "CREATE TABLE users (\n  id INT,\n  contact TEXT -- renamed from mail\n);",
+    );
+
+    let rename = changes.iter().find(|c| c.change_type == ChangeType::Rename).unwrap();
+    assert_eq!(rename.kind, ChangeKind::ColumnRenamed);
+    assert_eq!(rename.metadata["old_location"], "users.mail");
+    assert_eq!(rename.metadata["declared_by"], "annotation");
+    assert!(changes.iter().any(|c| c.kind == ChangeKind::ColumnTypeWidened && c.location == "users.contact"));
+    assert!(changes.iter().all(|c| c.kind != ChangeKind::ColumnRemoved));
+}
+
+#[test]
+fn test_sql_hint_renames_table_and_column() {
+    let changes = analyze_hinted(
+        &SqlAnalyzer,
+        SchemaFormat::SqlDDL,
+        "CREATE TABLE people (id INT, surname TEXT);",
+        "CREATE TABLE users (id INT, last_name TEXT);",
+        RenameHints::new()
+            .with_rename("people", "users")
+            .with_rename("people.surname", "users.last_name"),
+    );
+
+    let kinds: Vec<_> = changes.iter().map(|c| c.kind).collect();
+    assert_eq!(kinds, [ChangeKind::TableRenamed, ChangeKind::ColumnRenamed]);
+    assert!(changes.iter().all(|c| c.metadata["declared_by"] == "hint"));
+}
+
+#[test]
+fn test_protobuf_hint_renames_message() {
+    let message = |name: &str| format!("message_type {{\n  name: \"{}\"\n  field {{ name: \"id\" number: 1 type: TYPE_INT64 }}\n}}\n", name);
+    let changes = analyze_hinted(
+        &ProtobufAnalyzer,
+        SchemaFormat::Protobuf,
+        &message("Person"),
+        &message("Account"),
+        RenameHints::new().with_rename("Person", "Account"),
+    );
+
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].kind, ChangeKind::MessageRenamed);
+    assert_eq!(changes[0].location, "Account");
+    assert_eq!(changes[0].metadata["declared_by"], "hint");
+}
+
+#[test]
+fn test_protobuf_locations_follow_the_containing_package() {
+    let file = |package: &str, messages: &[&str]| {
+        let messages: String = messages
+            .iter()
+            .map(|name| format!("message_type {{\n  name: \"{}\"\n  field {{ name: \"id\" number: 1 type: TYPE_INT64 }}\n}}\n", name))
+            .collect();
+        format!("package: \"{}\"\n{}", package, messages)
+    };
+    let changes = analyze_hinted(
+        &ProtobufAnalyzer,
+        SchemaFormat::Protobuf,
+        &file("shop.v1", &["Person", "Cart"]),
+        &file("shop.v2", &["Account", "Refund"]),
+        RenameHints::new().with_rename("shop.v1.Person", "shop.v2.Account"),
+    );
+
+    let locations: Vec<_> = changes.iter().map(|c| (c.kind, c.location.to_string())).collect();
+    assert_eq!(
+        locations,
+        [
+            (ChangeKind::MessageRenamed, "shop.v2.Account".to_string()),
+            (ChangeKind::MessageRemoved, "shop.v1.Cart".to_string()),
+            (ChangeKind::MessageAdded, "shop.v2.Refund".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_hint_is_ignored_when_old_element_remains() {
+    let changes = analyze_hinted(
+        &JsonSchemaAnalyzer,
+        SchemaFormat::JsonSchema,
+        r#"{"properties": {"a": {"type": "string"}}}"#,
+        r#"{"properties": {"a": {"type": "string"}, "b": {"type": "integer"}}}"#,
+        RenameHints::new().with_rename("/properties/a", "/properties/b"),
+    );
+
+    assert!(changes.iter().all(|c| c.change_type != ChangeType::Rename));
+}
+
+#[test]
+fn test_renamed_required_property_stays_required() {
+    let old = r#"{"properties": {"login": {"type": "string"}}, "required": ["login"]}"#;
+    let new = r#"{"properties": {"username": {"type": "string"}}, "required": ["username"]}"#;
+
+    let hinted = analyze_hinted(
+        &JsonSchemaAnalyzer,
+        SchemaFormat::JsonSchema,
+        old,
+        new,
+        RenameHints::new().with_rename("/properties/login", "/properties/username"),
+    );
+    let detected = analyze(
+        &JsonSchemaAnalyzer,
+        SchemaFormat::JsonSchema,
+        old,
+        &new.replace("username", "login_name"),
+    );
+
+    for changes in [hinted, detected] {
+        let kinds: Vec<_> = changes.iter().map(|c| c.kind).collect();
+        assert_eq!(kinds, [ChangeKind::PropertyRenamed]);
+    }
+}
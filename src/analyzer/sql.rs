@@ -3,11 +3,12 @@
 //! This module provides functionality for analyzing SQL DDL changes and
 //! generating compatibility reports and migration paths.
 
-use sqlparser::ast::{Statement, ColumnDef, ColumnOption};
-use crate::analyzer::{SchemaAnalyzer, SchemaChange, ChangeType};
-use crate::{Schema, CompatibilityReport, MigrationPlan, ValidationResult, SchemaDiffError};
+use sqlparser::ast::{Statement, ColumnDef, ColumnOption, CreateTable, Ident, ObjectName, DataType, CharacterLength, ExactNumberInfo};
+use crate::analyzer::{SchemaAnalyzer, AnalysisOptions, SchemaChange, ChangeType, ChangeKind, build_report, declared_rename, resolve_positions, validation_errors};
+use crate::location::{Location, LocationStyle};
+use crate::{Schema, CompatibilityReport, MigrationPlan, RenameHints, ValidationResult, SchemaDiffError};
 use crate::error::Result;
-use crate::report::{CompatibilityIssue, IssueSeverity, ValidationError};
+use std::cmp::Ordering;
 use std::collections::HashMap;
 
 /// Analyzes SQL DDL changes and generates compatibility reports.
@@ -25,29 +26,13 @@ impl SchemaAnalyzer for SqlAnalyzer {
     ///
     /// A `CompatibilityReport` detailing the differences and compatibility status.
     fn analyze_compatibility(&self, old: &Schema, new: &Schema) -> Result<CompatibilityReport> {
-        let metadata = HashMap::new();
-
-        let mut changes = Vec::new();
-        self.compare_schemas(old, new, &mut changes);
-
-        let compatibility_score = self.calculate_compatibility_score(&changes);
-        let validation_result = self.validate_changes(&changes)?;
+        self.analyze_renamed(old, new, &RenameHints::default())
+    }
 
-        Ok(CompatibilityReport {
-            changes,
-            compatibility_score,
-            is_compatible: compatibility_score >= 80,
-            issues: validation_result.errors.into_iter().map(|err| CompatibilityIssue {
-                severity: match err.code.as_str() {
-                    "SQL001" => IssueSeverity::Error,
-                    "SQL002" => IssueSeverity::Warning,
-                    _ => IssueSeverity::Info,
-                },
-                description: err.message,
-                location: err.path,
-            }).collect(),
-            metadata,
-        })
+    /// Analyzes compatibility between two SQL DDL versions, applying the options' rename hints.
+    fn analyze_with_options(&self, old: &Schema, new: &Schema, options: &AnalysisOptions) -> Result<CompatibilityReport> {
+        let report = self.analyze_renamed(old, new, &options.renames)?;
+        options.apply(report)
     }
 
     /// Generates a migration path between SQL DDL versions.
@@ -62,7 +47,7 @@ impl SchemaAnalyzer for SqlAnalyzer {
     /// A `MigrationPlan` detailing the required changes.
     fn generate_migration_path(&self, old: &Schema, new: &Schema) -> Result<MigrationPlan> {
         let mut changes = Vec::new();
-        self.compare_schemas(old, new, &mut changes);
+        self.compare_schemas(old, new, &RenameHints::default(), &mut changes);
         
         Ok(MigrationPlan::new(
             old.version.to_string(),
@@ -72,22 +57,8 @@ impl SchemaAnalyzer for SqlAnalyzer {
     }
 
     fn validate_changes(&self, changes: &[SchemaChange]) -> Result<ValidationResult> {
-        let mut errors = Vec::new();
-        
-        for change in changes {
-            if let Some(issue) = self.validate_change(change) {
-                errors.push(ValidationError {
-                    message: issue.description,
-                    path: issue.location,
-                    code: match issue.severity {
-                        IssueSeverity::Error => "SQL001",
-                        IssueSeverity::Warning => "SQL002",
-                        IssueSeverity::Info => "SQL003",
-                    }.to_string(),
-                });
-            }
-        }
-        
+        let errors = validation_errors(changes);
+
         Ok(ValidationResult {
             errors: errors.clone(),
             is_valid: errors.is_empty(),
@@ -97,70 +68,237 @@ impl SchemaAnalyzer for SqlAnalyzer {
 }
 
 impl SqlAnalyzer {
-    fn compare_schemas(&self, old: &Schema, new: &Schema, changes: &mut Vec<SchemaChange>) {
-        if let (Ok(old_tables), Ok(new_tables)) = (
+    /// Builds the qualified name of a table
+    fn table_location(name: &ObjectName) -> Location {
+        Location::qualified(name.0.iter().map(|part| part.value.as_str()))
+    }
+
+    /// Analyzes compatibility with declared renames applied
+    fn analyze_renamed(&self, old: &Schema, new: &Schema, hints: &RenameHints) -> Result<CompatibilityReport> {
+        let metadata = HashMap::new();
+
+        let mut changes = Vec::new();
+        self.compare_schemas(old, new, hints, &mut changes);
+        resolve_positions(&mut changes, &old.content, &new.content);
+
+        Ok(build_report(changes, metadata))
+    }
+
+    fn compare_schemas(&self, old: &Schema, new: &Schema, hints: &RenameHints, changes: &mut Vec<SchemaChange>) {
+        if let (Ok(mut old_tables), Ok(new_tables)) = (
             self.parse_tables(&old.content),
             self.parse_tables(&new.content)
         ) {
+            let mut declared: Vec<(Location, Location, &str)> = hints
+                .iter()
+                .filter(|(from, to)| from.style() == LocationStyle::QualifiedName && to.style() == LocationStyle::QualifiedName)
+                .map(|(from, to)| (from.clone(), to.clone(), "hint"))
+                .collect();
+            declared.extend(Self::annotated_renames(&new.content).into_iter().map(|(from, to)| (from, to, "annotation")));
+            Self::apply_renames(&mut old_tables, &new_tables, &declared, changes);
+
+            let old_index = Self::table_index(&old_tables);
+            let new_index = Self::table_index(&new_tables);
+
             // Compare existing tables
-            for old_table in old_tables.iter() {
-                if let Statement::CreateTable(ref old_table_data) = old_table {
-                    let name = &old_table_data.name;
-                    let old_columns = &old_table_data.columns;
-                    if let Some(new_table) = new_tables.iter().find(|t| {
-                        if let Statement::CreateTable(ref new_table_data) = t {
-                            &new_table_data.name == name
-                        } else {
-                            false
-                        }
-                    }) {
-                        if let Statement::CreateTable(ref new_table_data) = new_table {
-                            let new_columns = &new_table_data.columns;
-                            self.compare_columns(name.to_string(), old_columns, new_columns, changes);
-                        }
-                    } else {
-                        let mut metadata = HashMap::new();
-                        metadata.insert("table".to_string(), name.to_string());
-                        
-                        changes.push(SchemaChange::new(
-                            ChangeType::Removal,
-                            format!("table/{}", name),
-                            format!("Table '{}' was removed", name),
-                            metadata,
-                        ));
-                    }
+            for (name, old_table_data) in Self::tables(&old_tables) {
+                if let Some(new_table_data) = new_index.get(name) {
+                    self.compare_columns(name, &old_table_data.columns, &new_table_data.columns, changes);
+                } else {
+                    let mut metadata = HashMap::new();
+                    metadata.insert("table".to_string(), name.to_string());
+                    metadata.insert("definition".to_string(), Self::table_definition(&old_table_data.columns));
+                    
+                    changes.push(SchemaChange::from_kind(
+                        ChangeKind::TableRemoved,
+                        Self::table_location(name),
+                        format!("Table '{}' was removed", name),
+                        metadata,
+                    ));
                 }
             }
 
             // Check for new tables
-            for new_table in new_tables.iter() {
-                if let Statement::CreateTable(ref new_table_data) = new_table {
-                    let table_name = &new_table_data.name;
-                    if !old_tables.iter().any(|t| {
-                        if let Statement::CreateTable(ref old_table_data) = t {
-                            &old_table_data.name == table_name
-                        } else {
-                            false
-                        }
-                    }) {
-                        let mut metadata = HashMap::new();
-                        metadata.insert("table".to_string(), table_name.to_string());
-                        
-                        changes.push(SchemaChange::new(
-                            ChangeType::Addition,
-                            format!("table/{}", table_name),
-                            format!("New table '{}' was added", table_name),
-                            metadata,
-                        ));
-                    }
+            for (table_name, new_table_data) in Self::tables(&new_tables) {
+                if !old_index.contains_key(table_name) {
+                    let mut metadata = HashMap::new();
+                    metadata.insert("table".to_string(), table_name.to_string());
+                    metadata.insert("definition".to_string(), Self::table_definition(&new_table_data.columns));
+                    
+                    changes.push(SchemaChange::from_kind(
+                        ChangeKind::TableAdded,
+                        Self::table_location(table_name),
+                        format!("New table '{}' was added", table_name),
+                        metadata,
+                    ));
                 }
             }
         }
     }
 
-    fn compare_columns(&self, table_name: String, old_columns: &[ColumnDef], new_columns: &[ColumnDef], changes: &mut Vec<SchemaChange>) {
+    /// Returns the created tables of a schema with their names, in order
+    fn tables(statements: &[Statement]) -> impl Iterator<Item = (&ObjectName, &CreateTable)> {
+        statements.iter().filter_map(|statement| match statement {
+            Statement::CreateTable(table) => Some((&table.name, table)),
+            _ => None,
+        })
+    }
+
+    /// Indexes the created tables of a schema by name
+    ///
+    /// Tables are matched through this index rather than by scanning the
+    /// other schema, so comparing large schemas stays linear. The first of
+    /// several tables with the same name is kept.
+    fn table_index(statements: &[Statement]) -> HashMap<&ObjectName, &CreateTable> {
+        let mut index = HashMap::new();
+        for (name, table) in Self::tables(statements) {
+            index.entry(name).or_insert(table);
+        }
+        index
+    }
+
+    /// Collects the renames declared by `-- renamed from <old name>` comments
+    ///
+    /// A comment on a `CREATE TABLE` line renames the table, a comment on a
+    /// column definition renames the column within the enclosing table.
+    fn annotated_renames(sql: &str) -> Vec<(Location, Location)> {
+        let unquote = |name: &str| {
+            name.trim_matches(|c: char| matches!(c, '"' | '`' | '[' | ']' | '(' | ')' | ',' | ';'))
+                .to_string()
+        };
+
+        let mut renames = Vec::new();
+        let mut table: Option<String> = None;
+        for line in sql.lines() {
+            let (code, comment) = match line.split_once("--") {
+                Some((code, comment)) => (code, Some(comment)),
+                None => (line, None),
+            };
+
+            let words: Vec<&str> = code.split(|c: char| c.is_whitespace() || c == '(').filter(|word| !word.is_empty()).collect();
+            let upper: Vec<String> = words.iter().map(|word| word.to_ascii_uppercase()).collect();
+            let created = match upper.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+                ["CREATE", "TABLE", "IF", "NOT", "EXISTS", ..] => words.get(5).map(|name| unquote(name)),
+                ["CREATE", "TABLE", ..] => words.get(2).map(|name| unquote(name)),
+                _ => None,
+            };
+            if created.is_some() {
+                table.clone_from(&created);
+            }
+
+            let Some(old_name) = comment.and_then(Self::renamed_from) else {
+                continue;
+            };
+            match (created, &table, words.first()) {
+                (Some(name), _, _) => renames.push((Location::parse(&unquote(old_name)), Location::parse(&name))),
+                (None, Some(table), Some(column)) => {
+                    let table = Location::parse(table);
+                    renames.push((table.child(unquote(old_name)), table.child(unquote(column))));
+                }
+                _ => {}
+            }
+        }
+        renames
+    }
+
+    /// Extracts the old name from a `renamed from <old name>` comment
+    fn renamed_from(comment: &str) -> Option<&str> {
+        let comment = comment.trim();
+        let prefix = comment.get(..12)?;
+        if !prefix.eq_ignore_ascii_case("renamed from") {
+            return None;
+        }
+        comment[12..].split_whitespace().next()
+    }
+
+    /// Renames tables and columns of the old schema as declared
+    ///
+    /// Table renames are applied first. Columns may be declared under either
+    /// the old or the new name of their table. A rename is skipped unless the
+    /// old element only exists in the old schema and the new element only
+    /// exists in the new one.
+    fn apply_renames(
+        old_tables: &mut [Statement],
+        new_tables: &[Statement],
+        declared: &[(Location, Location, &str)],
+        changes: &mut Vec<SchemaChange>,
+    ) {
+        let has_table = |tables: &[Statement], location: &Location| Self::find_table(tables, location).is_some();
+        let has_column = |tables: &[Statement], location: &Location| {
+            let (Some(table), Some(column)) = (location.parent(), location.last_segment()) else {
+                return false;
+            };
+            Self::find_table(tables, &table).is_some_and(|table| table.columns.iter().any(|c| c.name.value == column))
+        };
+
+        let mut renamed_tables = Vec::new();
+        for (from, to, declared_by) in declared {
+            if !has_table(old_tables, from) || has_table(old_tables, to) || has_table(new_tables, from) || !has_table(new_tables, to) {
+                continue;
+            }
+
+            if let Some(table) = Self::find_table_mut(old_tables, from) {
+                table.name = ObjectName(to.segments().iter().map(|part| Ident::new(part.as_str())).collect());
+                changes.push(declared_rename(ChangeKind::TableRenamed, from, to, declared_by));
+                renamed_tables.push((from.clone(), to.clone()));
+            }
+        }
+
+        for (from, to, declared_by) in declared {
+            let (Some(table), Some(column), Some(new_name)) = (from.parent(), from.last_segment(), to.last_segment()) else {
+                continue;
+            };
+            let table = renamed_tables
+                .iter()
+                .find(|(old_table, _)| *old_table == table)
+                .map_or(table, |(_, new_table)| new_table.clone());
+            let current = table.child(column);
+            if to.parent().as_ref() != Some(&table)
+                || !has_column(old_tables, &current) || has_column(old_tables, to) || has_column(new_tables, &current) || !has_column(new_tables, to) {
+                continue;
+            }
+
+            if let Some(column) = Self::find_table_mut(old_tables, &table)
+                .and_then(|table| table.columns.iter_mut().find(|c| c.name.value == column))
+            {
+                column.name.value = new_name.to_string();
+                changes.push(declared_rename(ChangeKind::ColumnRenamed, from, to, declared_by));
+            }
+        }
+    }
+
+    /// Finds the table created at a location
+    fn find_table<'a>(tables: &'a [Statement], location: &Location) -> Option<&'a CreateTable> {
+        tables.iter().find_map(|statement| match statement {
+            Statement::CreateTable(table) if Self::table_location(&table.name) == *location => Some(table),
+            _ => None,
+        })
+    }
+
+    /// Finds the table created at a location for renaming
+    fn find_table_mut<'a>(tables: &'a mut [Statement], location: &Location) -> Option<&'a mut CreateTable> {
+        tables.iter_mut().find_map(|statement| match statement {
+            Statement::CreateTable(table) if Self::table_location(&table.name) == *location => Some(table),
+            _ => None,
+        })
+    }
+
+    /// Indexes the columns of a table by name, keeping the first of duplicates
+    fn column_index(columns: &[ColumnDef]) -> HashMap<&Ident, &ColumnDef> {
+        let mut index = HashMap::new();
+        for column in columns {
+            index.entry(&column.name).or_insert(column);
+        }
+        index
+    }
+
+    fn compare_columns(&self, table: &ObjectName, old_columns: &[ColumnDef], new_columns: &[ColumnDef], changes: &mut Vec<SchemaChange>) {
+        let (old_index, new_index) = (Self::column_index(old_columns), Self::column_index(new_columns));
+        let table_name = table.to_string();
+        let table_location = Self::table_location(table);
+
         for old_col in old_columns {
-            if let Some(new_col) = new_columns.iter().find(|c| c.name == old_col.name) {
+            if let Some(new_col) = new_index.get(&old_col.name) {
                 // Compare data types
                 if old_col.data_type != new_col.data_type {
                     let mut metadata = HashMap::new();
@@ -169,9 +307,9 @@ impl SqlAnalyzer {
                     metadata.insert("old_type".to_string(), format!("{:?}", old_col.data_type));
                     metadata.insert("new_type".to_string(), format!("{:?}", new_col.data_type));
                     
-                    changes.push(SchemaChange::new(
-                        ChangeType::Modification,
-                        format!("{}/{}", table_name, old_col.name),
+                    changes.push(SchemaChange::from_kind(
+                        Self::type_change_kind(&old_col.data_type, &new_col.data_type),
+                        table_location.child(old_col.name.value.as_str()),
                         format!("Column '{}' type changed from {:?} to {:?}", 
                             old_col.name, old_col.data_type, new_col.data_type),
                         metadata,
@@ -189,37 +327,38 @@ impl SqlAnalyzer {
                 // Now pass the converted options
                 self.compare_column_constraints(
                     &table_name,
+                    &table_location.child(old_col.name.value.as_str()),
                     &old_col.name.to_string(),
                     &old_opts,
                     &new_opts,
                     changes,
                 );
             } else {
-                let mut metadata = HashMap::new();
-                metadata.insert("table".to_string(), table_name.clone());
-                metadata.insert("column".to_string(), old_col.name.to_string());
-                
-                changes.push(SchemaChange::new(
-                    ChangeType::Removal,
-                    format!("{}/{}", table_name, old_col.name),
+                changes.push(SchemaChange::from_kind(
+                    ChangeKind::ColumnRemoved,
+                    table_location.child(old_col.name.value.as_str()),
                     format!("Column '{}' was removed", old_col.name),
-                    metadata,
+                    Self::column_metadata(&table_name, old_col),
                 ));
             }
         }
 
         // Check for new columns
         for new_col in new_columns {
-            if !old_columns.iter().any(|c| c.name == new_col.name) {
-                let mut metadata = HashMap::new();
-                metadata.insert("table".to_string(), table_name.clone());
-                metadata.insert("column".to_string(), new_col.name.to_string());
-                
-                changes.push(SchemaChange::new(
-                    ChangeType::Addition,
-                    format!("{}/{}", table_name, new_col.name),
+            if !old_index.contains_key(&new_col.name) {
+                let is_required = new_col.options.iter().any(|opt| matches!(opt.option, ColumnOption::NotNull))
+                    && !new_col.options.iter().any(|opt| matches!(opt.option, ColumnOption::Default(_)));
+                let kind = if is_required {
+                    ChangeKind::NotNullColumnAdded
+                } else {
+                    ChangeKind::ColumnAdded
+                };
+
+                changes.push(SchemaChange::from_kind(
+                    kind,
+                    table_location.child(new_col.name.value.as_str()),
                     format!("New column '{}' was added", new_col.name),
-                    metadata,
+                    Self::column_metadata(&table_name, new_col),
                 ));
             }
         }
@@ -228,6 +367,7 @@ impl SqlAnalyzer {
     fn compare_column_constraints(
         &self,
         table_name: &str,
+        location: &Location,
         column_name: &str,
         old_options: &[ColumnOption],
         new_options: &[ColumnOption],
@@ -253,9 +393,9 @@ impl SqlAnalyzer {
                 metadata.insert("column".to_string(), column_name.to_string());
                 metadata.insert("constraint".to_string(), format!("{:?}", old_opt));
                 
-                changes.push(SchemaChange::new(
-                    ChangeType::Removal,
-                    format!("{}/{}/constraints", table_name, column_name),
+                changes.push(SchemaChange::from_kind(
+                    ChangeKind::ConstraintRemoved,
+                    location.clone(),
                     format!("Constraint removed from column '{}': {:?}", column_name, old_opt),
                     metadata,
                 ));
@@ -282,9 +422,9 @@ impl SqlAnalyzer {
                 metadata.insert("column".to_string(), column_name.to_string());
                 metadata.insert("constraint".to_string(), format!("{:?}", new_opt));
                 
-                changes.push(SchemaChange::new(
-                    ChangeType::Addition,
-                    format!("{}/{}/constraints", table_name, column_name),
+                changes.push(SchemaChange::from_kind(
+                    ChangeKind::ConstraintAdded,
+                    location.clone(),
                     format!("New constraint added to column '{}': {:?}", column_name, new_opt),
                     metadata,
                 ));
@@ -292,44 +432,80 @@ impl SqlAnalyzer {
         }
     }
 
-    fn calculate_compatibility_score(&self, changes: &[SchemaChange]) -> u8 {
-        let base_score: u8 = 100;
-        let mut deductions: u8 = 0;
+    /// Builds the metadata describing an added or removed column
+    ///
+    /// Records the column's `type` and its sorted `constraints` so renamed
+    /// columns can be recognized.
+    fn column_metadata(table_name: &str, column: &ColumnDef) -> HashMap<String, String> {
+        let mut constraints: Vec<String> = column.options.iter().map(|opt| opt.option.to_string()).collect();
+        constraints.sort();
 
-        for change in changes {
-            match change.change_type {
-                ChangeType::Addition => deductions = deductions.saturating_add(5),
-                ChangeType::Removal => deductions = deductions.saturating_add(15),
-                ChangeType::Modification => deductions = deductions.saturating_add(10),
-                ChangeType::Rename => deductions = deductions.saturating_add(8),
-            }
-        }
+        let mut metadata = HashMap::new();
+        metadata.insert("table".to_string(), table_name.to_string());
+        metadata.insert("column".to_string(), column.name.to_string());
+        metadata.insert("type".to_string(), column.data_type.to_string());
+        metadata.insert("constraints".to_string(), constraints.join(" "));
+        metadata
+    }
 
-        base_score.saturating_sub(deductions)
+    /// Renders the columns of a table, so renamed tables can be recognized
+    fn table_definition(columns: &[ColumnDef]) -> String {
+        columns.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ")
     }
 
-    fn validate_change(&self, change: &SchemaChange) -> Option<CompatibilityIssue> {
-        match change.change_type {
-            ChangeType::Removal => Some(CompatibilityIssue {
-                severity: IssueSeverity::Error,
-                description: format!("Breaking change: {}", change.description),
-                location: change.location.clone(),
-            }),
-            ChangeType::Modification => {
-                if change.location.contains("type") {
-                    Some(CompatibilityIssue {
-                        severity: IssueSeverity::Warning,
-                        description: format!("Potential data loss: {}", change.description),
-                        location: change.location.clone(),
-                    })
-                } else {
-                    None
+    /// Classifies a column type change as narrowing, widening or unrelated
+    fn type_change_kind(old: &DataType, new: &DataType) -> ChangeKind {
+        match (Self::type_capacity(old), Self::type_capacity(new)) {
+            (Some((old_family, old_size)), Some((new_family, new_size))) if old_family == new_family => {
+                match new_size.cmp(&old_size) {
+                    Ordering::Less => ChangeKind::ColumnTypeNarrowed,
+                    Ordering::Greater => ChangeKind::ColumnTypeWidened,
+                    Ordering::Equal => ChangeKind::ColumnTypeChanged,
                 }
             }
-            _ => None,
+            _ => ChangeKind::ColumnTypeChanged,
         }
     }
 
+    /// Returns the family of a column type and how much it can hold
+    ///
+    /// Sizes are only comparable within a family: bytes for integers and
+    /// floats, characters for strings and digits for decimals. Types without
+    /// a length limit have a size of `u64::MAX`.
+    fn type_capacity(data_type: &DataType) -> Option<(&'static str, u64)> {
+        let text_length = |length: &Option<CharacterLength>, default: u64| match length {
+            Some(CharacterLength::IntegerLength { length, .. }) => *length,
+            Some(CharacterLength::Max) => u64::MAX,
+            None => default,
+        };
+
+        let capacity = match data_type {
+            DataType::TinyInt(_) => ("integer", 1),
+            DataType::SmallInt(_) | DataType::Int2(_) => ("integer", 2),
+            DataType::MediumInt(_) => ("integer", 3),
+            DataType::Int(_) | DataType::Integer(_) | DataType::Int4(_) => ("integer", 4),
+            DataType::BigInt(_) | DataType::Int8(_) => ("integer", 8),
+            DataType::Real | DataType::Float4 => ("float", 4),
+            DataType::Double | DataType::DoublePrecision | DataType::Float8 => ("float", 8),
+            DataType::Char(length) | DataType::Character(length) => ("string", text_length(length, 1)),
+            DataType::Varchar(length)
+            | DataType::CharVarying(length)
+            | DataType::CharacterVarying(length)
+            | DataType::Nvarchar(length) => ("string", text_length(length, u64::MAX)),
+            DataType::Text => ("string", u64::MAX),
+            DataType::String(length) => ("string", length.unwrap_or(u64::MAX)),
+            DataType::Decimal(info) | DataType::Numeric(info) | DataType::Dec(info) => match info {
+                ExactNumberInfo::None => ("decimal", u64::MAX),
+                ExactNumberInfo::Precision(precision) | ExactNumberInfo::PrecisionAndScale(precision, _) => {
+                    ("decimal", *precision)
+                }
+            },
+            _ => return None,
+        };
+
+        Some(capacity)
+    }
+
     fn parse_tables(&self, sql: &str) -> Result<Vec<Statement>> {
         use sqlparser::dialect::GenericDialect;
         use sqlparser::parser::Parser;
@@ -343,17 +519,17 @@ impl SqlAnalyzer {
     fn generate_sql_for_change(&self, change: &SchemaChange) -> String {
         match change.change_type {
             ChangeType::Addition => {
-                if change.location.starts_with("table/") {
-                    format!("CREATE TABLE {} (...);", change.location.strip_prefix("table/").unwrap_or(""))
-                } else {
+                if change.metadata.contains_key("column") {
                     format!("ALTER TABLE {} ADD COLUMN ...;", change.location)
+                } else {
+                    format!("CREATE TABLE {} (...);", change.location)
                 }
             }
             ChangeType::Removal => {
-                if change.location.starts_with("table/") {
-                    format!("DROP TABLE {};", change.location.strip_prefix("table/").unwrap_or(""))
-                } else {
+                if change.metadata.contains_key("column") {
                     format!("ALTER TABLE {} DROP COLUMN ...;", change.location)
+                } else {
+                    format!("DROP TABLE {};", change.location)
                 }
             }
             ChangeType::Modification => {
@@ -364,4 +540,7 @@ impl SqlAnalyzer {
             }
         }
     }
-} 
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests;
@@ -1,41 +1,77 @@
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::Schema;
-    use semver::Version;
-
-    fn create_schema(content: &str, version: &str) -> Schema {
-        Schema::new(
-            crate::SchemaFormat::SqlDDL,
-            content.to_string(),
-            Version::parse(version).unwrap(),
-        )
-    }
-
-    #[test]
-    fn test_table_changes() {
-        let old_sql = r#"
-            CREATE TABLE users (
-                id INTEGER PRIMARY KEY,
-                name TEXT NOT NULL
-            );
-        "#;
-
-        let new_sql = r#"
-            CREATE TABLE users (
-                id INTEGER PRIMARY KEY,
-                name TEXT NOT NULL,
-                email TEXT
-            );
-        "#;
-
-        let analyzer = SqlAnalyzer;
-        let result = analyzer.analyze_compatibility(
-            &create_schema(old_sql, "1.0.0"),
-            &create_schema(new_sql, "1.1.0")
-        ).unwrap();
-
-        assert!(result.is_compatible);
-        assert!(result.changes.iter().any(|c| matches!(c.change_type, ChangeType::Addition)));
-    }
-} 
\ No newline at end of file
+use super::*;
+use crate::{IssueSeverity, Schema};
+use semver::Version;
+
+fn create_schema(content: &str, version: &str) -> Schema {
+    Schema::new(
+        crate::SchemaFormat::SqlDDL,
+        content.to_string(),
+        Version::parse(version).unwrap(),
+    )
+}
+
+#[test]
+fn test_table_changes() {
+    let old_sql = r#"
+        CREATE TABLE users (
+            id INTEGER PRIMARY KEY,
+            name TEXT NOT NULL
+        );
+    "#;
+
+    let new_sql = r#"
+        CREATE TABLE users (
+            id INTEGER PRIMARY KEY,
+            name TEXT NOT NULL,
+            email TEXT
+        );
+    "#;
+
+    let analyzer = SqlAnalyzer;
+    let result = analyzer.analyze_compatibility(
+        &create_schema(old_sql, "1.0.0"),
+        &create_schema(new_sql, "1.1.0")
+    ).unwrap();
+
+    assert!(result.is_compatible);
+    assert!(result.changes.iter().any(|c| matches!(c.change_type, ChangeType::Addition)));
+}
+
+#[test]
+fn test_column_change_kinds() {
+    let old_sql = "CREATE TABLE users (id INT, name VARCHAR(100), age BIGINT);";
+    let new_sql = "CREATE TABLE users (id BIGINT, name VARCHAR(50), age INT, email TEXT NOT NULL);";
+
+    let analyzer = SqlAnalyzer;
+    let result = analyzer.analyze_compatibility(
+        &create_schema(old_sql, "1.0.0"),
+        &create_schema(new_sql, "2.0.0")
+    ).unwrap();
+
+    let kind_at = |location: &str| {
+        result.changes.iter().find(|c| c.location == location).map(|c| c.kind)
+    };
+    assert_eq!(kind_at("users.id"), Some(ChangeKind::ColumnTypeWidened));
+    assert_eq!(kind_at("users.name"), Some(ChangeKind::ColumnTypeNarrowed));
+    assert_eq!(kind_at("users.age"), Some(ChangeKind::ColumnTypeNarrowed));
+    assert_eq!(kind_at("users.email"), Some(ChangeKind::NotNullColumnAdded));
+    assert!(!result.is_compatible);
+    assert!(result.issues.iter().any(|issue| issue.kind == ChangeKind::ColumnTypeWidened && issue.severity == IssueSeverity::Error));
+}
+
+#[test]
+fn test_schema_qualified_column_locations() {
+    let old_sql = r#"CREATE TABLE "public"."users" (id INT, email TEXT);"#;
+    let new_sql = r#"CREATE TABLE "public"."users" (id BIGINT);"#;
+
+    let analyzer = SqlAnalyzer;
+    let result = analyzer.analyze_compatibility(
+        &create_schema(old_sql, "1.0.0"),
+        &create_schema(new_sql, "2.0.0")
+    ).unwrap();
+
+    let removed = result.changes.iter().find(|c| c.kind == ChangeKind::ColumnRemoved).unwrap();
+    assert_eq!(removed.location.segments(), ["public", "users", "email"]);
+    assert_eq!(removed.location.parent(), Some(Location::qualified(["public", "users"])));
+    assert!(result.changes.iter().any(|c| c.kind == ChangeKind::ColumnTypeWidened && c.location == "public.users.id"));
+}
@@ -0,0 +1,155 @@
+//! Baselines of accepted compatibility issues
+//!
+//! This module provides [`Baseline`], a file listing reviewed issues by a
+//! stable fingerprint. Issues matching an unexpired baseline entry are marked
+//! as suppressed: they stay in the report but no longer count against its
+//! score or verdict.
+
+use crate::analyzer::ChangeKind;
+use crate::error::Result;
+use crate::location::Location;
+use crate::report::CompatibilityReport;
+use chrono::{NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Set of accepted compatibility issues
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Baseline {
+    /// Accepted issues
+    pub entries: Vec<BaselineEntry>,
+}
+
+/// Single accepted issue of a baseline
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    /// Fingerprint of the accepted issue, see [`CompatibilityIssue::fingerprint`](crate::CompatibilityIssue::fingerprint)
+    pub fingerprint: String,
+    /// Rule of the accepted issue
+    pub rule: ChangeKind,
+    /// Location of the accepted issue
+    pub location: Location,
+    /// Description of the issue when it was accepted
+    pub description: String,
+    /// Why the issue was accepted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub justification: Option<String>,
+    /// Last day on which the entry suppresses the issue
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires: Option<NaiveDate>,
+}
+
+/// Suppression of an issue by a baseline entry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Suppression {
+    /// Why the issue was accepted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub justification: Option<String>,
+    /// Last day on which the issue is suppressed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires: Option<NaiveDate>,
+}
+
+impl Baseline {
+    /// Creates a baseline accepting every issue of a report
+    pub fn from_report(report: &CompatibilityReport) -> Self {
+        let entries = report
+            .issues
+            .iter()
+            .map(|issue| BaselineEntry {
+                fingerprint: issue.fingerprint(),
+                rule: issue.kind,
+                location: issue.location.clone(),
+                description: issue.description.clone(),
+                justification: None,
+                expires: None,
+            })
+            .collect();
+
+        Self { entries }
+    }
+
+    /// Loads a baseline from a JSON file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let content = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Writes the baseline to a JSON file
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let mut content = serde_json::to_string_pretty(self)?;
+        content.push('\n');
+        fs::write(path, content)?;
+        Ok(())
+    }
+
+    /// Copies justifications and expiry dates from an earlier baseline
+    ///
+    /// Used when regenerating a baseline, so reviewed entries keep their
+    /// annotations.
+    pub fn keep_annotations(&mut self, previous: &Baseline) {
+        for entry in &mut self.entries {
+            if let Some(old) = previous.find(&entry.fingerprint) {
+                entry.justification = old.justification.clone();
+                entry.expires = old.expires;
+            }
+        }
+    }
+
+    /// Returns the entry with the given fingerprint
+    pub fn find(&self, fingerprint: &str) -> Option<&BaselineEntry> {
+        self.entries.iter().find(|entry| entry.fingerprint == fingerprint)
+    }
+
+    /// Suppresses the issues of a report accepted by this baseline as of today
+    ///
+    /// See [`Baseline::apply_on`].
+    pub fn apply(&self, report: CompatibilityReport, threshold: u8) -> CompatibilityReport {
+        self.apply_on(report, threshold, Utc::now().date_naive())
+    }
+
+    /// Suppresses the issues of a report accepted by this baseline on a given day
+    ///
+    /// The score is recomputed from the issues that remain unsuppressed, and
+    /// the verdict against `threshold`.
+    ///
+    /// # Arguments
+    /// * `report` - The report to apply the baseline to
+    /// * `threshold` - Minimum score for the report to count as compatible
+    /// * `today` - The day expiry dates are compared against
+    pub fn apply_on(&self, mut report: CompatibilityReport, threshold: u8, today: NaiveDate) -> CompatibilityReport {
+        let before = report.deductions();
+        for issue in report.issues.iter_mut().filter(|issue| issue.suppression.is_none()) {
+            let Some(entry) = self.find(&issue.fingerprint()) else {
+                continue;
+            };
+            if entry.expires.is_some_and(|expires| expires < today) {
+                continue;
+            }
+
+            issue.suppression = Some(Suppression {
+                justification: entry.justification.clone(),
+                expires: entry.expires,
+            });
+        }
+
+        report.rescore(threshold, before);
+        report
+    }
+}
+
+/// Computes the 64-bit FNV-1a hash of a rule ID and location as hex
+pub(crate) fn fingerprint(kind: ChangeKind, location: &Location) -> String {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let input = format!("{}\0{}", kind.rule_id(), location);
+    let hash = input
+        .bytes()
+        .fold(OFFSET_BASIS, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(PRIME));
+    format!("{:016x}", hash)
+}
+
+#[cfg(test)]
+mod tests;
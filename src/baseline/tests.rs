@@ -0,0 +1,91 @@
+use super::*;
+use crate::analyzer::build_report;
+use crate::{CompatibilityIssue, Policy, SchemaChange};
+use std::collections::HashMap;
+
+fn report() -> CompatibilityReport {
+    let changes = vec![
+        SchemaChange::from_kind(ChangeKind::ColumnRemoved, Location::qualified(["users", "name"]), "Column 'name' was removed", HashMap::new()),
+        SchemaChange::from_kind(ChangeKind::TableRemoved, Location::qualified(["orders"]), "Table 'orders' was removed", HashMap::new()),
+    ];
+    build_report(changes, HashMap::new())
+}
+
+fn day(text: &str) -> NaiveDate {
+    text.parse().unwrap()
+}
+
+#[test]
+fn test_fingerprint_ignores_description_and_position() {
+    let issue = &report().issues[0];
+    let mut moved = issue.clone();
+    moved.description = "Reworded".to_string();
+    moved.location.line = Some(42);
+
+    assert_eq!(issue.fingerprint(), moved.fingerprint());
+    assert_eq!(issue.fingerprint().len(), 16);
+    assert_ne!(issue.fingerprint(), report().issues[1].fingerprint());
+}
+
+#[test]
+fn test_baseline_suppresses_accepted_issues() {
+    let mut baseline = Baseline::from_report(&report());
+    baseline.entries.retain(|entry| entry.rule == ChangeKind::ColumnRemoved);
+    baseline.entries[0].justification = Some("Column moved to profiles".to_string());
+
+    let original = report();
+    let judged = baseline.apply_on(original.clone(), Policy::DEFAULT_THRESHOLD, day("2026-01-01"));
+
+    assert_eq!(judged.issues.len(), original.issues.len());
+    let suppression = judged.issues[0].suppression.as_ref().unwrap();
+    assert_eq!(suppression.justification.as_deref(), Some("Column moved to profiles"));
+    assert!(!judged.issues[1].is_suppressed());
+    assert_eq!(judged.compatibility_score, original.compatibility_score + 25);
+}
+
+#[test]
+fn test_expired_entries_do_not_suppress() {
+    let mut baseline = Baseline::from_report(&report());
+    for entry in &mut baseline.entries {
+        entry.expires = Some(day("2026-03-31"));
+    }
+
+    let on_expiry = baseline.apply_on(report(), Policy::DEFAULT_THRESHOLD, day("2026-03-31"));
+    assert!(on_expiry.issues.iter().all(CompatibilityIssue::is_suppressed));
+    assert_eq!(on_expiry.compatibility_score, 100);
+    assert!(on_expiry.is_compatible);
+
+    let expired = baseline.apply_on(report(), Policy::DEFAULT_THRESHOLD, day("2026-04-01"));
+    assert!(!expired.issues.iter().any(CompatibilityIssue::is_suppressed));
+    assert!(!expired.is_compatible);
+}
+
+#[test]
+fn test_baseline_round_trip_keeps_annotations() {
+    let path = std::env::temp_dir().join(format!("schema-diff-baseline-{}.json", std::process::id()));
+    let mut previous = Baseline::from_report(&report());
+    previous.entries[0].justification = Some("Accepted in review".to_string());
+    previous.entries[0].expires = Some(day("2027-01-01"));
+    previous.save(&path).unwrap();
+
+    let mut regenerated = Baseline::from_report(&report());
+    regenerated.keep_annotations(&Baseline::load(&path).unwrap());
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(regenerated, previous);
+}
+
+#[test]
+fn test_suppression_rescores_reports_below_zero() {
+    let changes = (0..5)
+        .map(|index| SchemaChange::from_kind(ChangeKind::TableRemoved, Location::qualified([format!("table_{}", index)]), "Table was removed", HashMap::new()))
+        .collect();
+    let report = build_report(changes, HashMap::new());
+    let mut baseline = Baseline::from_report(&report);
+    baseline.entries.truncate(1);
+
+    let judged = baseline.apply_on(report, Policy::DEFAULT_THRESHOLD, day("2026-01-01"));
+    assert_eq!(judged.issues.iter().filter(|issue| issue.is_suppressed()).count(), 1);
+    assert_eq!(judged.compatibility_score, 0);
+    assert!(!judged.is_compatible);
+}
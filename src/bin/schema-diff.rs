@@ -0,0 +1,474 @@
+//! `schema-diff` command-line interface
+//!
+//! Compares two schema files with the analyzer matching their format and
+//! prints the result. `diff` and `check` exit with status 1 when the schemas
+//! are incompatible, so the binary can gate CI pipelines directly.
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use rusty_schema_diff::{
+    AnalysisOptions, Baseline, CompatibilityMode, CompatibilityReport, DirectoryReport, HtmlRenderer, IssueSeverity, JunitRenderer, MarkdownRenderer,
+    MigrationPlan, Policy, RenameHints, ReportRenderer, SarifRenderer, Schema, SchemaDiff, SchemaDiffError, VersionCheck,
+};
+#[cfg(feature = "git")]
+use rusty_schema_diff::{GitRepository, Timeline};
+use std::io;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+/// Schema evolution analyzer for JSON Schema, OpenAPI, Protobuf and SQL DDL
+#[derive(Parser)]
+#[command(name = "schema-diff", version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print the full compatibility report between two schemas
+    Diff(DiffArgs),
+    /// Print only the compatibility verdict and issues between two schemas
+    Check(DiffArgs),
+    /// Print the compatibility of every schema file between two directory trees
+    DiffDir(DiffDirArgs),
+    /// Print the migration plan between two schemas
+    Migrate(MigrateArgs),
+    /// Print the evolution of a schema file across the git commits that changed it
+    #[cfg(feature = "git")]
+    Timeline(TimelineArgs),
+}
+
+#[derive(Args)]
+struct Inputs {
+    /// Original schema file, or `-` to read it from stdin
+    old: String,
+    /// New schema file, or `-` to read it from stdin
+    new: String,
+    /// Read `REV:PATH` inputs from git, e.g. `main:schemas/user.json`
+    ///
+    /// Paths are relative to the repository root. Inputs without a revision
+    /// are still read from disk.
+    #[cfg(feature = "git")]
+    #[arg(long)]
+    git: bool,
+    /// Repository to read `--git` inputs from
+    #[cfg(feature = "git")]
+    #[arg(long, value_name = "DIR", default_value = ".", requires = "git")]
+    repo: PathBuf,
+}
+
+#[derive(Args)]
+struct DiffArgs {
+    #[command(flatten)]
+    inputs: Inputs,
+    /// Output format of the report
+    #[arg(long, value_enum, default_value_t = ReportFormat::Text)]
+    format: ReportFormat,
+    /// Fail when an issue of this severity or higher is found
+    #[arg(long, default_value_t = IssueSeverity::Error)]
+    fail_on: IssueSeverity,
+    /// Direction in which the schemas must be compatible: backward, forward or full
+    #[arg(long, default_value_t = CompatibilityMode::Full)]
+    mode: CompatibilityMode,
+    /// Fail when the new schema's version is too small for the detected changes
+    #[arg(long)]
+    check_version: bool,
+    /// Policy file (TOML, YAML or JSON) overriding severities, ignored changes and the threshold
+    #[arg(long, value_name = "FILE")]
+    policy: Option<PathBuf>,
+    /// Rename hints file with one `old -> new` location per line
+    #[arg(long, value_name = "FILE")]
+    renames: Option<PathBuf>,
+    /// Baseline file of accepted issues, which are still reported but no longer fail the check
+    #[arg(long, value_name = "FILE")]
+    baseline: Option<PathBuf>,
+    /// Accept all current issues by writing them to a baseline file
+    ///
+    /// Justifications and expiry dates of entries already in the file, or in
+    /// the `--baseline` file, are kept.
+    #[arg(long, value_name = "FILE")]
+    write_baseline: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct DiffDirArgs {
+    /// Directory holding the original schemas
+    old: PathBuf,
+    /// Directory holding the new schemas
+    new: PathBuf,
+    /// Output format of the report
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+    /// Fail when an issue of this severity or higher is found
+    #[arg(long, default_value_t = IssueSeverity::Error)]
+    fail_on: IssueSeverity,
+    /// Direction in which the schemas must be compatible: backward, forward or full
+    #[arg(long, default_value_t = CompatibilityMode::Full)]
+    mode: CompatibilityMode,
+    /// Policy file (TOML, YAML or JSON) overriding severities, ignored changes and the threshold
+    #[arg(long, value_name = "FILE")]
+    policy: Option<PathBuf>,
+    /// Rename hints file with one `old -> new` location per line
+    #[arg(long, value_name = "FILE")]
+    renames: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct MigrateArgs {
+    #[command(flatten)]
+    inputs: Inputs,
+    /// Output format of the migration plan
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+}
+
+#[cfg(feature = "git")]
+#[derive(Args)]
+struct TimelineArgs {
+    /// Schema file, relative to the repository root
+    path: PathBuf,
+    /// Revision whose first-parent history is walked
+    #[arg(long, default_value = "HEAD")]
+    rev: String,
+    /// Repository containing the schema file
+    #[arg(long, value_name = "DIR", default_value = ".")]
+    repo: PathBuf,
+    /// Output format of the timeline
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ReportFormat {
+    /// Human-readable text
+    Text,
+    /// Pretty-printed JSON
+    Json,
+    /// Markdown, e.g. for pull request comments
+    Markdown,
+    /// Standalone HTML page
+    Html,
+    /// JUnit XML with one test case per issue
+    Junit,
+    /// SARIF 2.1.0 for code-scanning dashboards
+    Sarif,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum OutputFormat {
+    /// Human-readable text
+    Text,
+    /// Pretty-printed JSON
+    Json,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    match run(cli.command) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(1),
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Runs a subcommand, returning whether the schemas passed the check
+fn run(command: Command) -> Result<bool, SchemaDiffError> {
+    match command {
+        Command::Diff(args) => {
+            let (old, new, report) = analyze(&args)?;
+            let version = check_version(&args, &old, &new, &report);
+            match args.format {
+                ReportFormat::Text => {
+                    print_report(&report);
+                    print_version_check(&old, &new, version.as_ref());
+                }
+                format => print_rendered(&report, format)?,
+            }
+            Ok(passes(&report, args.fail_on) && version.as_ref().is_none_or(VersionCheck::is_sufficient))
+        }
+        Command::Check(args) => {
+            let (old, new, report) = analyze(&args)?;
+            let version = check_version(&args, &old, &new, &report);
+            let passed = passes(&report, args.fail_on) && version.as_ref().is_none_or(VersionCheck::is_sufficient);
+            match args.format {
+                ReportFormat::Text => {
+                    print_check(&report, passed);
+                    print_version_check(&old, &new, version.as_ref());
+                }
+                ReportFormat::Json => println!("{}", serde_json::to_string_pretty(&report.issues)?),
+                format => print_rendered(&report, format)?,
+            }
+            Ok(passed)
+        }
+        Command::DiffDir(args) => {
+            let mut options = AnalysisOptions::default().with_mode(args.mode);
+            if let Some(path) = &args.policy {
+                options = options.with_policy(Policy::from_path(path)?);
+            }
+            if let Some(path) = &args.renames {
+                options = options.with_renames(RenameHints::from_path(path)?);
+            }
+            let report = SchemaDiff::new().with_options(options).analyze_directories(&args.old, &args.new)?;
+            match args.format {
+                OutputFormat::Text => print_directory_report(&report),
+                OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+            }
+            Ok(report.removed.is_empty() && report.files.iter().all(|file| passes(&file.report, args.fail_on)))
+        }
+        Command::Migrate(args) => {
+            let (old, new) = load_inputs(&args.inputs)?;
+            let plan = SchemaDiff::new().generate_migration_path(&old, &new)?;
+            match args.format {
+                OutputFormat::Text => print_plan(&plan),
+                OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&plan)?),
+            }
+            Ok(true)
+        }
+        #[cfg(feature = "git")]
+        Command::Timeline(args) => {
+            let repo = GitRepository::discover(&args.repo)?;
+            let timeline = repo.timeline(&SchemaDiff::new(), &args.path, &args.rev)?;
+            match args.format {
+                OutputFormat::Text => print_timeline(&timeline),
+                OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&timeline)?),
+            }
+            Ok(true)
+        }
+    }
+}
+
+/// Loads and analyzes the inputs of `diff` and `check`, writing a baseline if requested
+fn analyze(args: &DiffArgs) -> Result<(Schema, Schema, CompatibilityReport), SchemaDiffError> {
+    let engine = diff_engine(args)?;
+    let (old, new) = load_inputs(&args.inputs)?;
+    let mut report = engine.analyze(&old, &new)?;
+
+    if let Some(path) = &args.write_baseline {
+        let mut baseline = Baseline::from_report(&report);
+        if let Some(previous) = engine.options().baseline.as_ref() {
+            baseline.keep_annotations(previous);
+        } else if path.exists() {
+            baseline.keep_annotations(&Baseline::load(path)?);
+        }
+        baseline.save(path)?;
+        report = baseline.apply(report, engine.options().policy.threshold);
+    }
+
+    Ok((old, new, report))
+}
+
+/// Creates the engine for `diff` and `check`, applying the policy, rename hints and baseline files if given
+fn diff_engine(args: &DiffArgs) -> Result<SchemaDiff, SchemaDiffError> {
+    let mut options = AnalysisOptions::default().with_mode(args.mode);
+    if let Some(path) = &args.policy {
+        options = options.with_policy(Policy::from_path(path)?);
+    }
+    if let Some(path) = &args.renames {
+        options = options.with_renames(RenameHints::from_path(path)?);
+    }
+    if let Some(path) = &args.baseline {
+        options = options.with_baseline(Baseline::load(path)?);
+    }
+    Ok(SchemaDiff::new().with_options(options))
+}
+
+/// Loads both input schemas, reading at most one of them from stdin
+fn load_inputs(inputs: &Inputs) -> Result<(Schema, Schema), SchemaDiffError> {
+    if inputs.old == "-" && inputs.new == "-" {
+        return Err(SchemaDiffError::InvalidFormat(
+            "only one schema can be read from stdin".to_string(),
+        ));
+    }
+
+    #[cfg(feature = "git")]
+    if inputs.git {
+        let repo = GitRepository::discover(&inputs.repo)?;
+        return Ok((load_revision(&repo, &inputs.old)?, load_revision(&repo, &inputs.new)?));
+    }
+
+    Ok((load_schema(&inputs.old)?, load_schema(&inputs.new)?))
+}
+
+/// Loads a `REV:PATH` input from git, or any other input like `load_schema`
+#[cfg(feature = "git")]
+fn load_revision(repo: &GitRepository, input: &str) -> Result<Schema, SchemaDiffError> {
+    match input.split_once(':') {
+        Some((revision, path)) => repo.load(revision, path),
+        None => load_schema(input),
+    }
+}
+
+fn load_schema(input: &str) -> Result<Schema, SchemaDiffError> {
+    if input == "-" {
+        Schema::from_reader(io::stdin().lock())
+    } else {
+        Schema::from_path(input)
+    }
+}
+
+/// Checks a report against the configured failure threshold, ignoring suppressed issues
+fn passes(report: &CompatibilityReport, fail_on: IssueSeverity) -> bool {
+    report.is_compatible
+        && !report
+            .issues
+            .iter()
+            .any(|issue| !issue.is_suppressed() && issue.severity.is_at_least(fail_on))
+}
+
+/// Checks the new schema's version when `--check-version` is given
+fn check_version(args: &DiffArgs, old: &Schema, new: &Schema, report: &CompatibilityReport) -> Option<VersionCheck> {
+    args.check_version.then(|| VersionCheck::new(&old.version, &new.version, report))
+}
+
+/// Prints a report with the renderer matching a non-text output format
+fn print_rendered(report: &CompatibilityReport, format: ReportFormat) -> Result<(), SchemaDiffError> {
+    let rendered = match format {
+        ReportFormat::Text => unreachable!("text reports are printed directly"),
+        ReportFormat::Json => serde_json::to_string_pretty(report)?,
+        ReportFormat::Markdown => MarkdownRenderer.render(report)?,
+        ReportFormat::Html => HtmlRenderer.render(report)?,
+        ReportFormat::Junit => JunitRenderer.render(report)?,
+        ReportFormat::Sarif => SarifRenderer.render(report)?,
+    };
+    print!("{}", rendered);
+    if !rendered.ends_with('\n') {
+        println!();
+    }
+    Ok(())
+}
+
+fn print_report(report: &CompatibilityReport) {
+    println!(
+        "Compatibility score: {}/100 ({})",
+        report.compatibility_score,
+        if report.is_compatible { "compatible" } else { "incompatible" }
+    );
+
+    println!("\nChanges ({}):", report.changes.len());
+    for change in &report.changes {
+        println!("  {:?} {}: {}", change.change_type, change.location, change.description);
+    }
+
+    print_issues(report);
+}
+
+fn print_directory_report(report: &DirectoryReport) {
+    println!(
+        "Compatibility score: {}/100 ({}), {} files compared, {} added, {} removed",
+        report.compatibility_score,
+        if report.is_compatible { "compatible" } else { "incompatible" },
+        report.files.len(),
+        report.added.len(),
+        report.removed.len()
+    );
+
+    for path in &report.added {
+        println!("  added {}", path);
+    }
+    for path in &report.removed {
+        println!("  removed {}", path);
+    }
+
+    for file in &report.files {
+        match file.similarity {
+            Some(similarity) => println!(
+                "\n== {} (renamed from {}, {:.0}% similar) ==",
+                file.new_path,
+                file.old_path,
+                similarity * 100.0
+            ),
+            None => println!("\n== {} ==", file.new_path),
+        }
+        print_report(&file.report);
+    }
+}
+
+fn print_check(report: &CompatibilityReport, passed: bool) {
+    println!(
+        "{}: {} changes, {} issues, score {}/100",
+        if passed { "PASS" } else { "FAIL" },
+        report.changes.len(),
+        report.issues.len(),
+        report.compatibility_score
+    );
+    print_issues(report);
+}
+
+fn print_issues(report: &CompatibilityReport) {
+    if report.issues.is_empty() {
+        return;
+    }
+
+    println!("\nIssues ({}):", report.issues.len());
+    for issue in &report.issues {
+        let suppressed = match &issue.suppression {
+            Some(suppression) => match &suppression.justification {
+                Some(justification) => format!(" (suppressed: {})", justification),
+                None => " (suppressed)".to_string(),
+            },
+            None => String::new(),
+        };
+        println!("  [{}] {}: {}{}", issue.severity, issue.location, issue.description, suppressed);
+    }
+}
+
+fn print_version_check(old: &Schema, new: &Schema, check: Option<&VersionCheck>) {
+    let Some(check) = check else {
+        return;
+    };
+
+    println!(
+        "\nVersion {} -> {}: {} bump, {} required{}",
+        old.version,
+        new.version,
+        check.actual,
+        check.required,
+        if check.is_sufficient() {
+            String::new()
+        } else {
+            format!(" (release as {} or later)", check.recommended)
+        }
+    );
+}
+
+fn print_plan(plan: &MigrationPlan) {
+    println!(
+        "Migration {} -> {} (impact {}/100, {})",
+        plan.source_version,
+        plan.target_version,
+        plan.impact_score,
+        if plan.is_breaking { "breaking" } else { "non-breaking" }
+    );
+
+    for (index, change) in plan.changes.iter().enumerate() {
+        println!("  {}. {:?} {}: {}", index + 1, change.change_type, change.location, change.description);
+    }
+}
+
+#[cfg(feature = "git")]
+fn print_timeline(timeline: &Timeline) {
+    println!("History of {} ({} versions)", timeline.path, timeline.entries.len());
+
+    for entry in &timeline.entries {
+        let bump = match &entry.version_check {
+            _ if entry.error.is_some() => "not analyzed".to_string(),
+            Some(check) if !check.is_sufficient() => format!("{} bump, {} required", check.actual, check.required),
+            Some(check) => format!("{} bump", check.required),
+            None => "added".to_string(),
+        };
+        println!(
+            "  {} {} {} <{}> {} ({}): {}",
+            &entry.commit[..7],
+            entry.date.format("%Y-%m-%d"),
+            entry.author,
+            entry.email,
+            entry.version,
+            bump,
+            entry.summary
+        );
+    }
+}
@@ -0,0 +1,44 @@
+//! `schema-registry` server
+//!
+//! Serves a local schema registry over the Confluent Schema Registry REST
+//! protocol, as a drop-in stand-in for tests and development environments.
+
+use clap::Parser;
+use rusty_schema_diff::{CompatibilityLevel, Registry, RegistryServer, SchemaDiffError};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+/// Confluent-compatible schema registry backed by a local directory
+#[derive(Parser)]
+#[command(name = "schema-registry", version, about)]
+struct Cli {
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1:8081")]
+    listen: String,
+    /// Directory the registry is stored in
+    #[arg(long, value_name = "DIR", default_value = "schema-registry")]
+    dir: PathBuf,
+    /// Default compatibility level of subjects without their own, e.g. BACKWARD or FULL_TRANSITIVE
+    #[arg(long)]
+    compatibility: Option<CompatibilityLevel>,
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<(), SchemaDiffError> {
+    let registry = Registry::open(&cli.dir)?;
+    if let Some(level) = cli.compatibility {
+        registry.set_default_compatibility(level)?;
+    }
+
+    eprintln!("Serving {} on http://{}", cli.dir.display(), cli.listen);
+    RegistryServer::new(registry).serve(&cli.listen)
+}
@@ -0,0 +1,89 @@
+//! Compatibility directions
+//!
+//! This module provides [`CompatibilityMode`], which selects the direction in
+//! which two schema versions must be compatible. The modes follow Confluent
+//! Schema Registry:
+//!
+//! - `BACKWARD`: readers using the new schema can read data written with the old one
+//! - `FORWARD`: readers using the old schema can read data written with the new one
+//! - `FULL`: both of the above
+//!
+//! For APIs the server reads requests and the client reads responses, so e.g.
+//! removing an endpoint breaks backward compatibility: the new server can no
+//! longer handle requests of old clients.
+
+use crate::report::CompatibilityReport;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Direction in which two schema versions must be compatible
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum CompatibilityMode {
+    /// New readers can read old data
+    #[serde(alias = "backward")]
+    Backward,
+    /// Old readers can read new data
+    #[serde(alias = "forward")]
+    Forward,
+    /// Both backward and forward compatible
+    #[default]
+    #[serde(alias = "full")]
+    Full,
+}
+
+impl CompatibilityMode {
+    /// Checks whether this mode requires compatibility in `direction`
+    ///
+    /// `Full` requires both directions, and every mode requires `Full`, which
+    /// stands for changes that may break either direction.
+    pub fn includes(&self, direction: CompatibilityMode) -> bool {
+        *self == CompatibilityMode::Full || direction == CompatibilityMode::Full || *self == direction
+    }
+
+    /// Judges a report produced in `Full` mode by this mode
+    ///
+    /// Issues whose kind does not break compatibility in this mode, i.e.
+    /// whose [severity](crate::ChangeKind::severity) under this mode is
+    /// `Info`, are dropped, and the score is recomputed from the retained
+    /// issues. The verdict is recomputed against `threshold`.
+    ///
+    /// # Arguments
+    /// * `report` - The report produced by an analyzer
+    /// * `threshold` - Minimum score for the report to count as compatible
+    pub fn apply(&self, mut report: CompatibilityReport, threshold: u8) -> CompatibilityReport {
+        let before = report.deductions();
+        report.issues.retain(|issue| self.includes(issue.kind.direction()));
+        report.rescore(threshold, before);
+        report.metadata.insert("compatibility_mode".to_string(), self.to_string());
+        report
+    }
+}
+
+impl fmt::Display for CompatibilityMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            CompatibilityMode::Backward => "backward",
+            CompatibilityMode::Forward => "forward",
+            CompatibilityMode::Full => "full",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for CompatibilityMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "backward" => Ok(CompatibilityMode::Backward),
+            "forward" => Ok(CompatibilityMode::Forward),
+            "full" => Ok(CompatibilityMode::Full),
+            other => Err(format!("unknown compatibility mode '{}'", other)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
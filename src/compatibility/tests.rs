@@ -0,0 +1,71 @@
+use super::*;
+use crate::analyzer::build_report;
+use crate::{ChangeKind, IssueSeverity, Location, Policy, SchemaChange};
+use std::collections::HashMap;
+
+fn report() -> CompatibilityReport {
+    let changes = vec![
+        SchemaChange::from_kind(ChangeKind::NotNullColumnAdded, Location::qualified(["users", "email"]), "Column 'email' was added", HashMap::new()),
+        SchemaChange::from_kind(ChangeKind::ColumnRemoved, Location::qualified(["users", "name"]), "Column 'name' was removed", HashMap::new()),
+    ];
+    build_report(changes, HashMap::new())
+}
+
+#[test]
+fn test_full_mode_keeps_report() {
+    let original = report();
+    let judged = CompatibilityMode::Full.apply(original.clone(), Policy::DEFAULT_THRESHOLD);
+
+    assert_eq!(judged.issues.len(), 2);
+    assert_eq!(judged.compatibility_score, original.compatibility_score);
+    assert_eq!(judged.metadata.get("compatibility_mode").map(String::as_str), Some("full"));
+}
+
+#[test]
+fn test_directional_modes_keep_only_their_issues() {
+    let backward = CompatibilityMode::Backward.apply(report(), Policy::DEFAULT_THRESHOLD);
+    assert_eq!(backward.issues.len(), 1);
+    assert_eq!(backward.issues[0].kind, ChangeKind::NotNullColumnAdded);
+    assert_eq!(backward.compatibility_score, 75);
+    assert_eq!(backward.changes.len(), 2);
+
+    let forward = CompatibilityMode::Forward.apply(report(), Policy::DEFAULT_THRESHOLD);
+    assert_eq!(forward.issues.len(), 1);
+    assert_eq!(forward.issues[0].kind, ChangeKind::ColumnRemoved);
+}
+
+#[test]
+fn test_full_mode_rejects_what_forward_mode_rejects() {
+    let changes = vec![SchemaChange::from_kind(ChangeKind::ColumnTypeWidened, Location::qualified(["users", "name"]), "Column 'name' was widened", HashMap::new())];
+    let report = build_report(changes, HashMap::new());
+
+    for mode in [CompatibilityMode::Forward, CompatibilityMode::Full] {
+        let judged = mode.apply(report.clone(), Policy::DEFAULT_THRESHOLD);
+        assert_eq!(judged.issues[0].severity, IssueSeverity::Error);
+        assert_eq!(judged.compatibility_score, 75);
+        assert!(!judged.is_compatible);
+    }
+    assert!(CompatibilityMode::Backward.apply(report, Policy::DEFAULT_THRESHOLD).issues.is_empty());
+}
+
+#[test]
+fn test_directional_modes_rescore_reports_below_zero() {
+    let mut changes: Vec<_> = (0..5)
+        .map(|index| SchemaChange::from_kind(ChangeKind::TableRemoved, Location::qualified([format!("table_{}", index)]), "Table was removed", HashMap::new()))
+        .collect();
+    changes.push(SchemaChange::from_kind(ChangeKind::NotNullColumnAdded, Location::qualified(["users", "email"]), "Column 'email' was added", HashMap::new()));
+    let report = build_report(changes, HashMap::new());
+    assert_eq!(report.compatibility_score, 0);
+
+    let backward = CompatibilityMode::Backward.apply(report.clone(), Policy::DEFAULT_THRESHOLD);
+    assert_eq!(backward.compatibility_score, 75);
+    assert_eq!(CompatibilityMode::Forward.apply(report, Policy::DEFAULT_THRESHOLD).compatibility_score, 0);
+}
+
+#[test]
+fn test_mode_names() {
+    assert_eq!("BACKWARD".parse::<CompatibilityMode>(), Ok(CompatibilityMode::Backward));
+    assert_eq!(serde_json::to_value(CompatibilityMode::Forward).unwrap(), "FORWARD");
+    assert_eq!(serde_json::from_str::<CompatibilityMode>("\"full\"").unwrap(), CompatibilityMode::Full);
+    assert!("sideways".parse::<CompatibilityMode>().is_err());
+}
@@ -0,0 +1,243 @@
+//! Comparison of schema directory trees
+//!
+//! This module provides [`DirectoryReport`], the result of comparing every
+//! schema file in two directory trees with
+//! [`SchemaDiff::analyze_directories`](crate::SchemaDiff::analyze_directories).
+//! Files are paired by their path relative to the tree's root, and files that
+//! only exist in one tree are paired by content similarity to detect renames.
+
+use crate::engine::SchemaDiff;
+use crate::error::{Result, SchemaDiffError};
+use crate::report::CompatibilityReport;
+use crate::Schema;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Minimum content similarity for a removed and an added file to count as a rename
+pub(crate) const RENAME_THRESHOLD: f64 = 0.5;
+
+/// Compatibility between every schema file of two directory trees
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DirectoryReport {
+    /// Reports for files present in both trees, sorted by new path
+    pub files: Vec<FileReport>,
+    /// Paths of files only present in the new tree
+    pub added: Vec<String>,
+    /// Paths of files only present in the old tree
+    pub removed: Vec<String>,
+    /// Lowest score of all files, `0` if a file was removed
+    pub compatibility_score: u8,
+    /// Whether every file is compatible and none was removed
+    pub is_compatible: bool,
+}
+
+/// Compatibility between the two versions of one schema file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileReport {
+    /// Path of the file in the old tree
+    pub old_path: String,
+    /// Path of the file in the new tree
+    pub new_path: String,
+    /// Content similarity between 0 and 1 of a renamed file, `None` for files paired by path
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub similarity: Option<f64>,
+    /// Report comparing the old file with the new one
+    pub report: CompatibilityReport,
+}
+
+impl FileReport {
+    /// Checks whether the file was paired by content rather than by path
+    pub fn is_renamed(&self) -> bool {
+        self.similarity.is_some()
+    }
+}
+
+impl DirectoryReport {
+    /// Aggregates the reports of the individual files
+    ///
+    /// Removing a schema file removes everything it defines, so any removed
+    /// file makes the trees incompatible.
+    ///
+    /// # Arguments
+    /// * `files` - The reports for files present in both trees
+    /// * `added` - The paths of files only present in the new tree
+    /// * `removed` - The paths of files only present in the old tree
+    pub fn new(mut files: Vec<FileReport>, mut added: Vec<String>, mut removed: Vec<String>) -> Self {
+        files.sort_by(|a, b| a.new_path.cmp(&b.new_path));
+        added.sort();
+        removed.sort();
+
+        let compatibility_score = if removed.is_empty() {
+            files.iter().map(|file| file.report.compatibility_score).min().unwrap_or(100)
+        } else {
+            0
+        };
+
+        Self {
+            is_compatible: removed.is_empty() && files.iter().all(|file| file.report.is_compatible),
+            compatibility_score,
+            files,
+            added,
+            removed,
+        }
+    }
+
+    /// Returns the files whose new version is incompatible with the old one
+    pub fn incompatible_files(&self) -> impl Iterator<Item = &FileReport> {
+        self.files.iter().filter(|file| !file.report.is_compatible)
+    }
+}
+
+/// Compares the schema files of two directory trees
+pub(crate) fn analyze_directories(engine: &SchemaDiff, old: &Path, new: &Path) -> Result<DirectoryReport> {
+    let old_files = load_tree(engine, old)?;
+    let mut new_files = load_tree(engine, new)?;
+
+    let mut files = Vec::new();
+    let mut removed = Vec::new();
+    for (path, old_schema) in old_files {
+        match new_files.remove(&path) {
+            Some(new_schema) if new_schema.format == old_schema.format => files.push(FileReport {
+                report: engine.analyze(&old_schema, &new_schema)?,
+                old_path: path.clone(),
+                new_path: path,
+                similarity: None,
+            }),
+            Some(new_schema) => {
+                removed.push((path.clone(), old_schema));
+                new_files.insert(path, new_schema);
+            }
+            None => removed.push((path, old_schema)),
+        }
+    }
+    let added: Vec<_> = new_files.into_iter().collect();
+
+    let renames = detect_renames(&removed, &added);
+    let mut removed: Vec<_> = removed.into_iter().map(Some).collect();
+    let mut added: Vec<_> = added.into_iter().map(Some).collect();
+    for (old_index, new_index, similarity) in renames {
+        let (old_path, old_schema) = removed[old_index].take().expect("rename pairs are unique");
+        let (new_path, new_schema) = added[new_index].take().expect("rename pairs are unique");
+        files.push(FileReport {
+            report: engine.analyze(&old_schema, &new_schema)?,
+            old_path,
+            new_path,
+            similarity: Some(similarity),
+        });
+    }
+
+    Ok(DirectoryReport::new(
+        files,
+        added.into_iter().flatten().map(|(path, _)| path).collect(),
+        removed.into_iter().flatten().map(|(path, _)| path).collect(),
+    ))
+}
+
+/// Loads every schema file below a directory the engine has an analyzer for
+///
+/// Hidden files and directories are skipped, as are files that are not
+/// UTF-8 or whose format cannot be detected.
+///
+/// # Returns
+/// The schemas keyed by their `/`-separated path relative to `root`
+fn load_tree(engine: &SchemaDiff, root: &Path) -> Result<BTreeMap<String, Schema>> {
+    if !root.is_dir() {
+        return Err(SchemaDiffError::InvalidFormat(format!("'{}' is not a directory", root.display())));
+    }
+
+    let mut schemas = BTreeMap::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.file_name().and_then(|name| name.to_str()).is_none_or(|name| name.starts_with('.')) {
+                continue;
+            }
+            if path.is_dir() {
+                pending.push(path);
+                continue;
+            }
+
+            let schema = match Schema::from_path(&path) {
+                Ok(schema) if engine.analyzer(schema.format).is_some() => schema,
+                Ok(_) | Err(SchemaDiffError::InvalidFormat(_)) => continue,
+                Err(SchemaDiffError::IoError(err)) if err.kind() == io::ErrorKind::InvalidData => continue,
+                Err(err) => return Err(err),
+            };
+            let relative = path.strip_prefix(root).unwrap_or(&path);
+            let key = relative.components().map(|part| part.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/");
+            schemas.insert(key, schema);
+        }
+    }
+
+    Ok(schemas)
+}
+
+/// Pairs removed and added files of the same format by content similarity
+///
+/// Candidates are matched greedily from the most similar pair down, so each
+/// file takes part in at most one rename.
+///
+/// # Returns
+/// The indices of each renamed file in `removed` and `added`, with their similarity
+pub(crate) fn detect_renames(removed: &[(String, Schema)], added: &[(String, Schema)]) -> Vec<(usize, usize, f64)> {
+    let mut candidates = Vec::new();
+    for (old_index, (_, old)) in removed.iter().enumerate() {
+        for (new_index, (_, new)) in added.iter().enumerate() {
+            if old.format != new.format {
+                continue;
+            }
+            let similarity = similarity(&old.content, &new.content);
+            if similarity >= RENAME_THRESHOLD {
+                candidates.push((old_index, new_index, similarity));
+            }
+        }
+    }
+    candidates.sort_by(|a, b| b.2.total_cmp(&a.2).then(a.0.cmp(&b.0)).then(a.1.cmp(&b.1)));
+
+    let mut renames: Vec<(usize, usize, f64)> = Vec::new();
+    for candidate in candidates {
+        if !renames.iter().any(|rename| rename.0 == candidate.0 || rename.1 == candidate.1) {
+            renames.push(candidate);
+        }
+    }
+    renames
+}
+
+/// Measures how similar two file contents are
+///
+/// Computes the Dice coefficient of the contents' non-blank lines, ignoring
+/// leading and trailing whitespace.
+///
+/// # Returns
+/// `1.0` for files with the same lines, `0.0` for files without any line in common
+pub(crate) fn similarity(old: &str, new: &str) -> f64 {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    let old_lines = old.lines().map(str::trim).filter(|line| !line.is_empty());
+    let mut total = 0;
+    for line in old_lines {
+        *counts.entry(line).or_default() += 1;
+        total += 1;
+    }
+
+    let mut common = 0;
+    for line in new.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        total += 1;
+        if let Some(count) = counts.get_mut(line).filter(|count| **count > 0) {
+            *count -= 1;
+            common += 1;
+        }
+    }
+
+    if total == 0 {
+        1.0
+    } else {
+        (2 * common) as f64 / total as f64
+    }
+}
+
+#[cfg(test)]
+mod tests;
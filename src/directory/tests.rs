@@ -0,0 +1,85 @@
+use super::*;
+use crate::SchemaFormat;
+use semver::Version;
+use std::path::PathBuf;
+
+fn tree(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("schema-diff-directory-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    for (path, content) in files {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+    root
+}
+
+const USERS: &str = "CREATE TABLE users (\n  id INT,\n  name TEXT,\n  email TEXT,\n  created_at TIMESTAMP\n);";
+const USERS_WITHOUT_EMAIL: &str = "CREATE TABLE users (\n  id INT,\n  name TEXT,\n  created_at TIMESTAMP\n);";
+const ORDER: &str = r#"{"$schema": "http://json-schema.org/draft-07/schema#", "type": "object"}"#;
+
+#[test]
+fn test_pairs_files_by_relative_path() {
+    let old = tree("paths-old", &[("sql/users.sql", USERS), ("json/order.json", ORDER), ("README.md", "# Schemas")]);
+    let new = tree("paths-new", &[("sql/users.sql", USERS_WITHOUT_EMAIL), ("json/order.json", ORDER), (".hidden/x.sql", USERS)]);
+
+    let report = SchemaDiff::new().analyze_directories(&old, &new).unwrap();
+
+    let paths: Vec<_> = report.files.iter().map(|file| file.new_path.as_str()).collect();
+    assert_eq!(paths, ["json/order.json", "sql/users.sql"]);
+    assert!(report.added.is_empty() && report.removed.is_empty());
+    assert!(report.files[0].report.is_compatible);
+    assert!(!report.files[1].report.is_compatible);
+    assert!(!report.is_compatible);
+    assert_eq!(report.compatibility_score, report.files[1].report.compatibility_score);
+    assert_eq!(report.incompatible_files().count(), 1);
+
+    fs::remove_dir_all(old).unwrap();
+    fs::remove_dir_all(new).unwrap();
+}
+
+#[test]
+fn test_detects_renamed_added_and_removed_files() {
+    let old = tree("renames-old", &[("users.sql", USERS), ("legacy.sql", "CREATE TABLE legacy (id INT);")]);
+    let new = tree("renames-new", &[("accounts/users.sql", USERS_WITHOUT_EMAIL), ("order.json", ORDER)]);
+
+    let report = SchemaDiff::new().analyze_directories(&old, &new).unwrap();
+
+    assert_eq!(report.files.len(), 1);
+    let renamed = &report.files[0];
+    assert!(renamed.is_renamed());
+    assert_eq!((renamed.old_path.as_str(), renamed.new_path.as_str()), ("users.sql", "accounts/users.sql"));
+    assert!(renamed.similarity.unwrap() >= RENAME_THRESHOLD);
+    assert_eq!(report.added, ["order.json"]);
+    assert_eq!(report.removed, ["legacy.sql"]);
+    assert_eq!(report.compatibility_score, 0);
+    assert!(!report.is_compatible);
+
+    fs::remove_dir_all(old).unwrap();
+    fs::remove_dir_all(new).unwrap();
+}
+
+#[test]
+fn test_rejects_files_as_roots() {
+    let old = tree("file-root", &[("users.sql", USERS)]);
+    let result = SchemaDiff::new().analyze_directories(old.join("users.sql"), &old);
+    assert!(matches!(result, Err(SchemaDiffError::InvalidFormat(_))));
+
+    fs::remove_dir_all(old).unwrap();
+}
+
+#[test]
+fn test_similarity_of_contents() {
+    assert_eq!(similarity("a\nb\n", "  a\n\nb"), 1.0);
+    assert_eq!(similarity("a\nb", "c\nd"), 0.0);
+    assert_eq!(similarity("a\nb\nc\nd", "a\nb\nc\ne"), 0.75);
+}
+
+#[test]
+fn test_renames_pair_the_most_similar_files() {
+    let sql = |content: &str| Schema::new(SchemaFormat::SqlDDL, content.to_string(), Version::new(1, 0, 0));
+    let removed = [("a.sql".to_string(), sql("x\ny\nz")), ("b.sql".to_string(), sql("x\ny\nw"))];
+    let added = [("c.sql".to_string(), sql("x\ny\nw"))];
+
+    assert_eq!(detect_renames(&removed, &added), [(1, 0, 1.0)]);
+}
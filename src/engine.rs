@@ -0,0 +1,224 @@
+//! Format-dispatching analysis engine
+//!
+//! This module provides [`SchemaDiff`], a registry of analyzers keyed by
+//! [`SchemaFormat`] that picks the right analyzer for a pair of schemas.
+
+use crate::analyzer::{AnalysisOptions, SchemaAnalyzer};
+use crate::error::{Result, SchemaDiffError};
+use crate::directory::{self, DirectoryReport};
+use crate::history::{HistoryReport, VersionReport};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use crate::{
+    CompatibilityReport, JsonSchemaAnalyzer, MigrationPlan, OpenApiAnalyzer, ProtobufAnalyzer,
+    Schema, SchemaFormat, SqlAnalyzer,
+};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Dispatches schema comparisons to the analyzer registered for their format
+///
+/// `SchemaDiff::new()` comes with the built-in analyzers for JSON Schema,
+/// OpenAPI, Protobuf and SQL DDL. Additional formats can be supported by
+/// registering a custom [`SchemaAnalyzer`]. Every analysis runs with the
+/// engine's [`AnalysisOptions`].
+pub struct SchemaDiff {
+    analyzers: HashMap<SchemaFormat, Box<dyn SchemaAnalyzer + Send + Sync>>,
+    options: AnalysisOptions,
+}
+
+impl SchemaDiff {
+    /// Creates an engine with all built-in analyzers registered
+    pub fn new() -> Self {
+        let mut engine = Self::empty();
+        engine.register(SchemaFormat::JsonSchema, JsonSchemaAnalyzer);
+        engine.register(SchemaFormat::OpenAPI, OpenApiAnalyzer);
+        engine.register(SchemaFormat::Protobuf, ProtobufAnalyzer);
+        engine.register(SchemaFormat::SqlDDL, SqlAnalyzer);
+        engine
+    }
+
+    /// Creates an engine without any registered analyzers
+    pub fn empty() -> Self {
+        Self {
+            analyzers: HashMap::new(),
+            options: AnalysisOptions::default(),
+        }
+    }
+
+    /// Sets the options used for every analysis
+    pub fn with_options(mut self, options: AnalysisOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Returns the options used for every analysis
+    pub fn options(&self) -> &AnalysisOptions {
+        &self.options
+    }
+
+    /// Registers an analyzer for a schema format
+    ///
+    /// # Arguments
+    /// * `format` - The schema format handled by the analyzer
+    /// * `analyzer` - The analyzer to use for that format
+    ///
+    /// # Returns
+    /// The analyzer previously registered for `format`, if any
+    pub fn register<A>(&mut self, format: SchemaFormat, analyzer: A) -> Option<Box<dyn SchemaAnalyzer + Send + Sync>>
+    where
+        A: SchemaAnalyzer + Send + Sync + 'static,
+    {
+        self.analyzers.insert(format, Box::new(analyzer))
+    }
+
+    /// Returns the analyzer registered for a schema format
+    pub fn analyzer(&self, format: SchemaFormat) -> Option<&(dyn SchemaAnalyzer + Send + Sync)> {
+        self.analyzers.get(&format).map(|analyzer| analyzer.as_ref())
+    }
+
+    /// Analyzes compatibility between two schema versions
+    ///
+    /// # Arguments
+    /// * `old` - The original schema version
+    /// * `new` - The new schema version to compare against
+    ///
+    /// # Returns
+    /// A compatibility report produced by the analyzer registered for the
+    /// schemas' format under the engine's options, with `old_source`/`new_source` metadata for schemas
+    /// loaded from files, or `SchemaDiffError::InvalidFormat` if the formats
+    /// differ or no analyzer is registered
+    pub fn analyze(&self, old: &Schema, new: &Schema) -> Result<CompatibilityReport> {
+        self.analyze_with(old, new, &self.options)
+    }
+
+    /// Analyzes compatibility between two schema versions under other options
+    ///
+    /// Like [`SchemaDiff::analyze`], but ignores the engine's options in
+    /// favour of `options`.
+    pub fn analyze_with(&self, old: &Schema, new: &Schema, options: &AnalysisOptions) -> Result<CompatibilityReport> {
+        let mut report = self.resolve(old, new)?.analyze_with_options(old, new, options)?;
+
+        if let Some(source) = &old.source {
+            report.metadata.insert("old_source".to_string(), source.display().to_string());
+        }
+        if let Some(source) = &new.source {
+            report.metadata.insert("new_source".to_string(), source.display().to_string());
+        }
+
+        Ok(report)
+    }
+
+    /// Analyzes many pairs of schema versions
+    ///
+    /// Pairs are analyzed like [`SchemaDiff::analyze`]. With the `parallel`
+    /// feature they are analyzed on rayon's global thread pool; run the call
+    /// inside `ThreadPool::install` to use another pool. Without it they are
+    /// analyzed one after another. A failing pair does not stop the others.
+    ///
+    /// # Arguments
+    /// * `pairs` - The original and new version of each schema
+    ///
+    /// # Returns
+    /// The report or error of each pair, in the order of `pairs`
+    pub fn analyze_many(&self, pairs: &[(Schema, Schema)]) -> Vec<Result<CompatibilityReport>> {
+        #[cfg(feature = "parallel")]
+        let pairs = pairs.par_iter();
+        #[cfg(not(feature = "parallel"))]
+        let pairs = pairs.iter();
+
+        pairs.map(|(old, new)| self.analyze(old, new)).collect()
+    }
+
+    /// Checks the newest schema of a history against every earlier version
+    ///
+    /// Each earlier version is compared with the newest one in the direction
+    /// of the engine's compatibility mode, so e.g. `Backward` checks that
+    /// readers using the newest schema can read data written with any
+    /// earlier version.
+    ///
+    /// # Arguments
+    /// * `history` - All versions of a schema, sorted by ascending version
+    ///
+    /// # Returns
+    /// The per-version reports and aggregated verdict, or
+    /// `SchemaDiffError::ComparisonError` if the history is empty or not
+    /// sorted by version
+    pub fn analyze_history(&self, history: &[Schema]) -> Result<HistoryReport> {
+        let (newest, earlier) = history
+            .split_last()
+            .ok_or_else(|| SchemaDiffError::ComparisonError("schema history is empty".to_string()))?;
+
+        if let Some(pair) = history.windows(2).find(|pair| pair[0].version >= pair[1].version) {
+            return Err(SchemaDiffError::ComparisonError(format!(
+                "schema history is not sorted by version: {} is followed by {}",
+                pair[0].version, pair[1].version
+            )));
+        }
+
+        let reports = earlier
+            .iter()
+            .map(|old| {
+                Ok(VersionReport {
+                    version: old.version.clone(),
+                    report: self.analyze(old, newest)?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(HistoryReport::new(newest.version.clone(), self.options.mode, reports))
+    }
+
+    /// Compares every schema file of two directory trees
+    ///
+    /// Files are paired by their path relative to each root. Files that only
+    /// exist in one tree are paired as renames if their contents are similar
+    /// enough, and reported as added or removed otherwise. Files the engine
+    /// has no analyzer for are skipped.
+    ///
+    /// # Arguments
+    /// * `old` - The directory holding the original schemas
+    /// * `new` - The directory holding the new schemas
+    ///
+    /// # Returns
+    /// The per-file reports and aggregated verdict, or
+    /// `SchemaDiffError::InvalidFormat` if either path is not a directory
+    pub fn analyze_directories(&self, old: impl AsRef<Path>, new: impl AsRef<Path>) -> Result<DirectoryReport> {
+        directory::analyze_directories(self, old.as_ref(), new.as_ref())
+    }
+
+    /// Generates a migration path between schema versions
+    ///
+    /// # Arguments
+    /// * `old` - The source schema version
+    /// * `new` - The target schema version
+    ///
+    /// # Returns
+    /// A migration plan produced by the analyzer registered for the schemas' format
+    pub fn generate_migration_path(&self, old: &Schema, new: &Schema) -> Result<MigrationPlan> {
+        self.resolve(old, new)?.generate_migration_path(old, new)
+    }
+
+    /// Finds the analyzer for a pair of schemas, checking that their formats match
+    fn resolve(&self, old: &Schema, new: &Schema) -> Result<&(dyn SchemaAnalyzer + Send + Sync)> {
+        if old.format != new.format {
+            return Err(SchemaDiffError::InvalidFormat(format!(
+                "cannot compare a {} schema with a {} schema",
+                old.format, new.format
+            )));
+        }
+
+        self.analyzer(old.format).ok_or_else(|| {
+            SchemaDiffError::InvalidFormat(format!("no analyzer registered for {}", old.format))
+        })
+    }
+}
+
+impl Default for SchemaDiff {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests;
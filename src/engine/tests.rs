@@ -0,0 +1,140 @@
+use super::*;
+use crate::analyzer::{ChangeType, SchemaChange};
+use crate::{CompatibilityMode, ValidationResult};
+use semver::Version;
+
+fn create_schema(format: SchemaFormat, content: &str, version: &str) -> Schema {
+    Schema::new(format, content.to_string(), Version::parse(version).unwrap())
+}
+
+struct StaticAnalyzer;
+
+impl SchemaAnalyzer for StaticAnalyzer {
+    fn analyze_compatibility(&self, _old: &Schema, _new: &Schema) -> Result<CompatibilityReport> {
+        Ok(CompatibilityReport {
+            changes: vec![SchemaChange::new(
+                ChangeType::Modification,
+                "/",
+                "Struct changed",
+                HashMap::new(),
+            )],
+            compatibility_score: 42,
+            is_compatible: false,
+            issues: vec![],
+            metadata: HashMap::new(),
+        })
+    }
+
+    fn generate_migration_path(&self, old: &Schema, new: &Schema) -> Result<MigrationPlan> {
+        Ok(MigrationPlan::new(old.version.to_string(), new.version.to_string(), vec![]))
+    }
+
+    fn validate_changes(&self, _changes: &[SchemaChange]) -> Result<ValidationResult> {
+        Ok(ValidationResult {
+            is_valid: true,
+            errors: vec![],
+            context: HashMap::new(),
+        })
+    }
+}
+
+#[test]
+fn test_dispatches_on_format() {
+    let old = create_schema(
+        SchemaFormat::JsonSchema,
+        r#"{"type": "object", "properties": {"name": {"type": "string"}}}"#,
+        "1.0.0",
+    );
+    let new = create_schema(
+        SchemaFormat::JsonSchema,
+        r#"{"type": "object", "properties": {"name": {"type": "string"}, "age": {"type": "integer"}}}"#,
+        "1.1.0",
+    );
+
+    let report = SchemaDiff::new().analyze(&old, &new).unwrap();
+
+    assert!(report.is_compatible);
+    assert!(report.changes.iter().any(|c| c.change_type == ChangeType::Addition));
+}
+
+#[test]
+fn test_rejects_mismatched_formats() {
+    let old = create_schema(SchemaFormat::JsonSchema, "{}", "1.0.0");
+    let new = create_schema(SchemaFormat::SqlDDL, "CREATE TABLE t (id INT);", "1.0.0");
+
+    let result = SchemaDiff::new().analyze(&old, &new);
+
+    assert!(matches!(result, Err(SchemaDiffError::InvalidFormat(_))));
+}
+
+#[test]
+fn test_custom_analyzer_registration() {
+    let old = create_schema(SchemaFormat::RustStruct, "struct A;", "1.0.0");
+    let new = create_schema(SchemaFormat::RustStruct, "struct B;", "1.0.0");
+
+    let mut engine = SchemaDiff::new();
+    assert!(matches!(engine.analyze(&old, &new), Err(SchemaDiffError::InvalidFormat(_))));
+
+    assert!(engine.register(SchemaFormat::RustStruct, StaticAnalyzer).is_none());
+    let report = engine.analyze(&old, &new).unwrap();
+
+    assert_eq!(report.compatibility_score, 42);
+    assert!(!report.is_compatible);
+}
+
+#[test]
+fn test_history_checks_every_earlier_version() {
+    let history = vec![
+        create_schema(SchemaFormat::SqlDDL, "CREATE TABLE users (id INT, name TEXT);", "1.0.0"),
+        create_schema(SchemaFormat::SqlDDL, "CREATE TABLE users (id INT);", "1.1.0"),
+        create_schema(SchemaFormat::SqlDDL, "CREATE TABLE users (id INT, email TEXT);", "1.2.0"),
+    ];
+
+    let forward = SchemaDiff::new().with_options(AnalysisOptions::default().with_mode(CompatibilityMode::Forward));
+    let report = forward.analyze_history(&history).unwrap();
+
+    assert_eq!(report.version, Version::new(1, 2, 0));
+    assert_eq!(report.reports.len(), 2);
+    assert!(!report.reports[0].report.is_compatible);
+    assert!(report.reports[1].report.is_compatible);
+    assert!(!report.is_compatible);
+    assert_eq!(report.oldest_incompatible, Some(Version::new(1, 0, 0)));
+
+    let backward = SchemaDiff::new().with_options(AnalysisOptions::default().with_mode(CompatibilityMode::Backward));
+    assert!(backward.analyze_history(&history).unwrap().is_compatible);
+}
+
+#[test]
+fn test_history_must_be_sorted() {
+    let history = vec![
+        create_schema(SchemaFormat::SqlDDL, "CREATE TABLE users (id INT);", "2.0.0"),
+        create_schema(SchemaFormat::SqlDDL, "CREATE TABLE users (id INT);", "1.0.0"),
+    ];
+
+    assert!(matches!(SchemaDiff::new().analyze_history(&history), Err(SchemaDiffError::ComparisonError(_))));
+    assert!(matches!(SchemaDiff::new().analyze_history(&[]), Err(SchemaDiffError::ComparisonError(_))));
+}
+
+#[test]
+fn test_analyze_many_keeps_order_and_collects_errors() {
+    let sql = |columns: &str| create_schema(SchemaFormat::SqlDDL, &format!("CREATE TABLE users ({});", columns), "1.0.0");
+    let json = create_schema(SchemaFormat::JsonSchema, r#"{"type": "object"}"#, "1.0.0");
+    let pairs: Vec<_> = (0..32)
+        .map(|index| match index % 3 {
+            0 => (sql("id INT, name TEXT"), sql("id INT")),
+            1 => (sql("id INT"), sql("id INT, name TEXT")),
+            _ => (sql("id INT"), json.clone()),
+        })
+        .collect();
+
+    let results = SchemaDiff::new().analyze_many(&pairs);
+
+    assert_eq!(results.len(), pairs.len());
+    for (index, result) in results.iter().enumerate() {
+        match index % 3 {
+            0 => assert!(!result.as_ref().unwrap().is_compatible),
+            1 => assert!(result.as_ref().unwrap().is_compatible),
+            _ => assert!(matches!(result, Err(SchemaDiffError::InvalidFormat(_)))),
+        }
+    }
+}
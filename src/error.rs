@@ -33,6 +33,26 @@ pub enum SchemaDiffError {
     /// Error that occurs during Protobuf operations
     #[error("Protobuf error: {0}")]
     ProtobufError(String),
+
+    /// Error that occurs when a policy cannot be loaded
+    #[error("Invalid policy: {0}")]
+    PolicyError(String),
+
+    /// Error that occurs when a registry subject, version or schema does not exist
+    #[error("Not found: {0}")]
+    NotFound(String),
+
+    /// Error that occurs when a schema fails a subject's compatibility check
+    #[error("Incompatible schema: {0}")]
+    IncompatibleSchema(String),
+
+    /// Error that occurs when the registry store is invalid or cannot be updated
+    #[error("Registry error: {0}")]
+    RegistryError(String),
+
+    /// Error that occurs when a git repository, revision or file cannot be read
+    #[error("Git error: {0}")]
+    GitError(String),
 }
 
 /// A specialized Result type for schema analysis operations
@@ -0,0 +1,225 @@
+//! Schemas from git revisions
+//!
+//! This module provides [`GitRepository`], which reads schema files as of a
+//! revision straight from a local repository's object database, so two
+//! revisions can be compared without checking them out, and builds a
+//! [`Timeline`] of every commit that changed a schema file. It is enabled by
+//! the `git` feature.
+
+use crate::engine::SchemaDiff;
+use crate::error::{Result, SchemaDiffError};
+use crate::report::CompatibilityReport;
+use crate::versioning::{VersionBump, VersionCheck};
+use crate::Schema;
+use chrono::{DateTime, FixedOffset, Offset, Utc};
+use git2::{Blob, Repository, Sort};
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// A local git repository to read schemas from
+pub struct GitRepository {
+    repo: Repository,
+}
+
+/// Evolution of a schema file across the commits that changed it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Timeline {
+    /// Path of the schema file relative to the repository root
+    pub path: String,
+    /// Commits that changed the file, from oldest to newest
+    pub entries: Vec<TimelineEntry>,
+}
+
+/// A commit that changed a schema file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelineEntry {
+    /// Full ID of the commit
+    pub commit: String,
+    /// First line of the commit message
+    pub summary: String,
+    /// Name of the commit's author
+    pub author: String,
+    /// Email address of the commit's author
+    pub email: String,
+    /// Time the commit was authored, in the author's time zone
+    pub date: DateTime<FixedOffset>,
+    /// Version declared by the schema at this commit, `0.0.0` if it has none
+    pub version: Version,
+    /// Report against the previous entry, `None` for the commit that added the file
+    pub report: Option<CompatibilityReport>,
+    /// Check of the declared version against the previous entry, `None` for the commit that added the file
+    pub version_check: Option<VersionCheck>,
+    /// Why the schema at this commit could not be analyzed, if it could not
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl TimelineEntry {
+    /// Returns the version bump the changes of this commit require
+    pub fn required_bump(&self) -> VersionBump {
+        self.version_check.as_ref().map_or(VersionBump::None, |check| check.required)
+    }
+}
+
+impl Timeline {
+    /// Returns the first commit whose changes require a major version bump
+    pub fn first_breaking(&self) -> Option<&TimelineEntry> {
+        self.entries.iter().find(|entry| entry.required_bump() == VersionBump::Major)
+    }
+}
+
+impl GitRepository {
+    /// Opens the repository containing a directory
+    ///
+    /// Like `git` itself, this searches the directory and its parents.
+    pub fn discover(path: impl AsRef<Path>) -> Result<Self> {
+        let repo = Repository::discover(path).map_err(git_error)?;
+        Ok(Self { repo })
+    }
+
+    /// Reads a schema file as of a revision
+    ///
+    /// The format and version are detected as in [`Schema::from_path`].
+    ///
+    /// # Arguments
+    /// * `revision` - Any revision git understands, e.g. `main`, `HEAD~2` or a commit ID
+    /// * `path` - Path of the file relative to the repository root
+    ///
+    /// # Returns
+    /// A schema whose `source` is `path`
+    pub fn load(&self, revision: &str, path: impl AsRef<Path>) -> Result<Schema> {
+        let path = path.as_ref();
+        let spec = format!("{}:{}", revision, tree_path(path));
+        let blob = self
+            .repo
+            .revparse_single(&spec)
+            .and_then(|object| object.peel_to_blob())
+            .map_err(git_error)?;
+
+        blob_schema(&blob, path, &spec)
+    }
+
+    /// Compares a schema file between two revisions
+    ///
+    /// # Arguments
+    /// * `engine` - The engine to analyze the schemas with
+    /// * `path` - Path of the file relative to the repository root
+    /// * `old` - The revision of the original schema
+    /// * `new` - The revision of the new schema
+    ///
+    /// # Returns
+    /// The engine's report, with the revisions recorded as `old_revision` and
+    /// `new_revision` metadata
+    pub fn analyze(&self, engine: &SchemaDiff, path: impl AsRef<Path>, old: &str, new: &str) -> Result<CompatibilityReport> {
+        let path = path.as_ref();
+        let mut report = engine.analyze(&self.load(old, path)?, &self.load(new, path)?)?;
+
+        report.metadata.insert("old_revision".to_string(), old.to_string());
+        report.metadata.insert("new_revision".to_string(), new.to_string());
+        Ok(report)
+    }
+
+    /// Builds the evolution timeline of a schema file
+    ///
+    /// Walks the first-parent history of `revision` from the oldest commit
+    /// and records every commit in which the file's content changed. Each
+    /// entry is compared with the previous one; commits that delete the file
+    /// are skipped, so a re-added file is compared with its last version.
+    /// A version that cannot be loaded or analyzed is recorded with its
+    /// `error` and without a report, and the next version is compared with
+    /// the last one that could.
+    ///
+    /// # Arguments
+    /// * `engine` - The engine to analyze the schemas with
+    /// * `path` - Path of the file relative to the repository root
+    /// * `revision` - The revision whose history is walked, e.g. `HEAD`
+    ///
+    /// # Returns
+    /// The timeline, or an error if the history cannot be read
+    pub fn timeline(&self, engine: &SchemaDiff, path: impl AsRef<Path>, revision: &str) -> Result<Timeline> {
+        let path = path.as_ref();
+        let tree_path = tree_path(path);
+        let head = self
+            .repo
+            .revparse_single(revision)
+            .and_then(|object| object.peel_to_commit())
+            .map_err(git_error)?;
+
+        let mut walk = self.repo.revwalk().map_err(git_error)?;
+        walk.push(head.id()).map_err(git_error)?;
+        walk.simplify_first_parent().map_err(git_error)?;
+        walk.set_sorting(Sort::TOPOLOGICAL | Sort::REVERSE).map_err(git_error)?;
+
+        let mut entries = Vec::new();
+        let mut previous: Option<Schema> = None;
+        let mut last_blob = None;
+        for oid in walk {
+            let commit = self.repo.find_commit(oid.map_err(git_error)?).map_err(git_error)?;
+            let Ok(tree_entry) = commit.tree().map_err(git_error)?.get_path(Path::new(&tree_path)) else {
+                continue;
+            };
+            if last_blob.replace(tree_entry.id()) == Some(tree_entry.id()) {
+                continue;
+            }
+
+            let blob = self.repo.find_blob(tree_entry.id()).map_err(git_error)?;
+            let analyzed = blob_schema(&blob, path, &format!("{}:{}", commit.id(), tree_path)).and_then(|schema| {
+                let report = previous.as_ref().map(|old| engine.analyze(old, &schema)).transpose()?;
+                Ok((schema, report))
+            });
+            let (version, report, version_check, error) = match analyzed {
+                Ok((schema, report)) => {
+                    let check = previous.as_ref().zip(report.as_ref()).map(|(old, report)| VersionCheck::new(&old.version, &schema.version, report));
+                    let version = schema.version.clone();
+                    previous = Some(schema);
+                    (version, report, check, None)
+                }
+                Err(err) => (Version::new(0, 0, 0), None, None, Some(err.to_string())),
+            };
+
+            let author = commit.author();
+            let time = author.when();
+            let offset = FixedOffset::east_opt(time.offset_minutes() * 60).unwrap_or_else(|| Utc.fix());
+            let date = DateTime::from_timestamp(time.seconds(), 0)
+                .ok_or_else(|| SchemaDiffError::GitError(format!("invalid commit time in {}", commit.id())))?
+                .with_timezone(&offset);
+
+            entries.push(TimelineEntry {
+                commit: commit.id().to_string(),
+                summary: String::from_utf8_lossy(commit.summary_bytes().unwrap_or_default()).into_owned(),
+                author: String::from_utf8_lossy(author.name_bytes()).into_owned(),
+                email: String::from_utf8_lossy(author.email_bytes()).into_owned(),
+                date,
+                version,
+                report,
+                version_check,
+                error,
+            });
+        }
+
+        Ok(Timeline { path: tree_path, entries })
+    }
+}
+
+/// Builds a schema from the content of a blob
+fn blob_schema(blob: &Blob<'_>, path: &Path, spec: &str) -> Result<Schema> {
+    let content = String::from_utf8(blob.content().to_vec())
+        .map_err(|_| SchemaDiffError::InvalidFormat(format!("'{}' is not valid UTF-8", spec)))?;
+
+    Schema::from_content(content, path)
+}
+
+/// Converts a path to the `/`-separated form git uses in trees
+fn tree_path(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    path.trim_start_matches("./").to_string()
+}
+
+/// Converts a libgit2 error
+fn git_error(err: git2::Error) -> SchemaDiffError {
+    SchemaDiffError::GitError(err.message().to_string())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,104 @@
+use super::*;
+use crate::{ChangeKind, SchemaFormat, VersionBump};
+use git2::{Signature, Time};
+use std::path::PathBuf;
+
+fn init(name: &str) -> (PathBuf, Repository) {
+    let root = std::env::temp_dir().join(format!("schema-diff-git-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&root);
+    let repo = Repository::init(&root).unwrap();
+    (root, repo)
+}
+
+fn commit(repo: &Repository, path: &str, content: &str, message: &str) -> git2::Oid {
+    let root = repo.workdir().unwrap();
+    std::fs::create_dir_all(root.join(path).parent().unwrap()).unwrap();
+    std::fs::write(root.join(path), content).unwrap();
+
+    let mut index = repo.index().unwrap();
+    index.add_path(Path::new(path)).unwrap();
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+
+    let signature = Signature::new("Test Author", "author@example.com", &Time::new(1_700_000_000, 0)).unwrap();
+    let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+    let parents: Vec<_> = parent.iter().collect();
+    repo.commit(Some("HEAD"), &signature, &signature, message, &tree, &parents).unwrap()
+}
+
+#[test]
+fn test_load_schema_at_revision() {
+    let (root, repo) = init("load");
+    commit(&repo, "schemas/users.sql", "CREATE TABLE users (id INT, name TEXT);", "Add users");
+    commit(&repo, "schemas/users.sql", "CREATE TABLE users (id INT);", "Drop name");
+
+    let git = GitRepository::discover(root.join("schemas")).unwrap();
+    let schema = git.load("HEAD~1", "./schemas/users.sql").unwrap();
+
+    assert_eq!(schema.format, SchemaFormat::SqlDDL);
+    assert!(schema.content.contains("name TEXT"));
+    assert_eq!(schema.source.as_deref(), Some(Path::new("./schemas/users.sql")));
+    assert!(matches!(git.load("HEAD", "schemas/missing.sql"), Err(SchemaDiffError::GitError(_))));
+
+    std::fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn test_analyze_between_revisions() {
+    let (root, repo) = init("analyze");
+    let first = commit(&repo, "users.sql", "CREATE TABLE users (id INT, name TEXT);", "Add users");
+    commit(&repo, "users.sql", "CREATE TABLE users (id INT);", "Drop name");
+
+    let git = GitRepository::discover(&root).unwrap();
+    let report = git.analyze(&SchemaDiff::new(), "users.sql", &first.to_string(), "HEAD").unwrap();
+
+    assert!(report.issues.iter().any(|issue| issue.kind == ChangeKind::ColumnRemoved));
+    assert_eq!(report.metadata.get("new_revision").map(String::as_str), Some("HEAD"));
+    assert_eq!(report.source_file(), Some("users.sql"));
+
+    std::fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn test_timeline_records_commits_that_changed_the_file() {
+    let (root, repo) = init("timeline");
+    commit(&repo, "user.json", r#"{"version": "1.0.0", "type": "object", "properties": {"id": {"type": "integer"}}}"#, "Add user schema");
+    commit(&repo, "README.md", "Schemas", "Add readme");
+    commit(&repo, "user.json", r#"{"version": "1.1.0", "type": "object", "properties": {"id": {"type": "integer"}, "name": {"type": "string"}}}"#, "Add name");
+    let breaking = commit(&repo, "user.json", r#"{"version": "1.2.0", "type": "object", "properties": {"name": {"type": "string"}}}"#, "Remove id");
+
+    let git = GitRepository::discover(&root).unwrap();
+    let timeline = git.timeline(&SchemaDiff::new(), "user.json", "HEAD").unwrap();
+
+    let summaries: Vec<_> = timeline.entries.iter().map(|entry| entry.summary.as_str()).collect();
+    assert_eq!(summaries, ["Add user schema", "Add name", "Remove id"]);
+    assert!(timeline.entries[0].report.is_none());
+    assert_eq!(timeline.entries[1].required_bump(), VersionBump::Minor);
+    assert_eq!(timeline.entries[2].author, "Test Author");
+    assert_eq!(timeline.entries[2].date.timestamp(), 1_700_000_000);
+
+    let first_breaking = timeline.first_breaking().unwrap();
+    assert_eq!(first_breaking.commit, breaking.to_string());
+    assert!(!first_breaking.version_check.as_ref().unwrap().is_sufficient());
+
+    std::fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn test_timeline_skips_versions_that_cannot_be_analyzed() {
+    let (root, repo) = init("malformed");
+    commit(&repo, "user.json", r#"{"version": "1.0.0", "type": "object", "properties": {"id": {"type": "integer"}}}"#, "Add user schema");
+    commit(&repo, "user.json", r#"{"version": "1.1.0", "type": "object", "properties": {"#, "Break user schema");
+    commit(&repo, "user.json", r#"{"version": "1.1.0", "type": "object", "properties": {"id": {"type": "integer"}, "name": {"type": "string"}}}"#, "Add name");
+
+    let git = GitRepository::discover(&root).unwrap();
+    let timeline = git.timeline(&SchemaDiff::new(), "user.json", "HEAD").unwrap();
+
+    assert_eq!(timeline.entries.len(), 3);
+    assert!(timeline.entries[1].error.is_some());
+    assert!(timeline.entries[1].report.is_none());
+    assert!(timeline.entries[2].error.is_none());
+    assert_eq!(timeline.entries[2].required_bump(), VersionBump::Minor);
+
+    std::fs::remove_dir_all(root).unwrap();
+}
@@ -0,0 +1,65 @@
+//! Transitive compatibility across a version history
+//!
+//! This module provides [`HistoryReport`], the result of checking the newest
+//! version of a schema against every earlier version with
+//! [`SchemaDiff::analyze_history`](crate::SchemaDiff::analyze_history).
+
+use crate::compatibility::CompatibilityMode;
+use crate::report::CompatibilityReport;
+use semver::Version;
+use serde::{Deserialize, Serialize};
+
+/// Compatibility of the newest schema version with each earlier version
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryReport {
+    /// Version of the newest schema, which was checked against the others
+    pub version: Version,
+    /// Direction in which compatibility was required
+    pub mode: CompatibilityMode,
+    /// Reports against each earlier version, from oldest to newest
+    pub reports: Vec<VersionReport>,
+    /// Whether the newest schema is compatible with every earlier version
+    pub is_compatible: bool,
+    /// Oldest version the newest schema is incompatible with, if any
+    pub oldest_incompatible: Option<Version>,
+}
+
+/// Compatibility of the newest schema version with one earlier version
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionReport {
+    /// Version of the earlier schema
+    pub version: Version,
+    /// Report comparing the earlier schema with the newest one
+    pub report: CompatibilityReport,
+}
+
+impl HistoryReport {
+    /// Aggregates the reports against each earlier version
+    ///
+    /// # Arguments
+    /// * `version` - The version of the newest schema
+    /// * `mode` - The direction in which compatibility was required
+    /// * `reports` - The reports against each earlier version, from oldest to newest
+    pub fn new(version: Version, mode: CompatibilityMode, reports: Vec<VersionReport>) -> Self {
+        let oldest_incompatible = reports
+            .iter()
+            .find(|entry| !entry.report.is_compatible)
+            .map(|entry| entry.version.clone());
+
+        Self {
+            version,
+            mode,
+            is_compatible: oldest_incompatible.is_none(),
+            oldest_incompatible,
+            reports,
+        }
+    }
+
+    /// Returns the earlier versions the newest schema is incompatible with
+    pub fn incompatible_versions(&self) -> impl Iterator<Item = &Version> {
+        self.reports
+            .iter()
+            .filter(|entry| !entry.report.is_compatible)
+            .map(|entry| &entry.version)
+    }
+}
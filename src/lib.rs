@@ -8,7 +8,20 @@
 //! - Schema compatibility analysis
 //! - Migration path generation
 //! - Breaking change detection
+//! - Rename detection across all formats, with rename hints and annotations
 //! - Multi-format support
+//! - Format-based analyzer dispatch via [`SchemaDiff`]
+//! - Markdown, HTML, JUnit XML and SARIF report rendering
+//! - Semantic version bump recommendation and verification
+//! - Configurable policies for severities, ignored changes and thresholds
+//! - Baselines suppressing reviewed breaking changes
+//! - Backward, forward and full compatibility modes
+//! - Transitive compatibility checks across a version history
+//! - Comparison of whole directory trees with rename detection
+//! - Parallel batch analysis of many schema pairs (`parallel` feature)
+//! - Local file-backed schema registry with per-subject compatibility levels
+//! - Confluent-compatible registry HTTP API (`server` feature)
+//! - Comparison of schema files between git revisions and evolution timelines (`git` feature)
 //!
 //! # Example
 //! ```rust
@@ -35,9 +48,27 @@ mod schema;
 mod migration;
 mod report;
 mod error;
+mod engine;
+mod location;
+mod versioning;
+mod policy;
+mod baseline;
+mod renames;
+mod compatibility;
+mod history;
+mod directory;
+mod registry;
+#[cfg(feature = "server")]
+mod server;
+#[cfg(feature = "git")]
+mod git;
 
 pub use analyzer::{
     SchemaAnalyzer,
+    AnalysisOptions,
+    SchemaChange,
+    ChangeType,
+    ChangeKind,
     json_schema::JsonSchemaAnalyzer,
     protobuf::ProtobufAnalyzer,
     openapi::OpenApiAnalyzer,
@@ -45,19 +76,59 @@ pub use analyzer::{
 };
 pub use schema::{Schema, SchemaFormat};
 pub use migration::MigrationPlan;
-pub use report::{CompatibilityReport, ValidationResult};
+pub use report::{CompatibilityReport, CompatibilityIssue, IssueSeverity, ValidationResult, ValidationError};
+pub use report::{
+    ReportRenderer,
+    markdown::MarkdownRenderer,
+    html::HtmlRenderer,
+    junit::JunitRenderer,
+    sarif::SarifRenderer,
+};
 pub use error::SchemaDiffError;
+pub use engine::SchemaDiff;
+pub use location::{Location, LocationStyle};
+pub use versioning::{VersionBump, VersionCheck};
+pub use policy::Policy;
+pub use baseline::{Baseline, BaselineEntry, Suppression};
+pub use renames::RenameHints;
+pub use compatibility::CompatibilityMode;
+pub use history::{HistoryReport, VersionReport};
+pub use directory::{DirectoryReport, FileReport};
+pub use registry::{CompatibilityLevel, RegisteredSchema, Registry};
+#[cfg(feature = "server")]
+pub use server::{RegistryServer, ServerResponse};
+#[cfg(feature = "git")]
+pub use git::{GitRepository, Timeline, TimelineEntry};
 
 /// Re-exports of commonly used types
 pub mod prelude {
     pub use crate::{
         SchemaAnalyzer,
+        AnalysisOptions,
+        Policy,
+        Baseline,
+        RenameHints,
+        CompatibilityMode,
+        HistoryReport,
+        DirectoryReport,
+        Registry,
+        CompatibilityLevel,
         Schema,
         SchemaFormat,
         MigrationPlan,
         CompatibilityReport,
+        CompatibilityIssue,
+        IssueSeverity,
         ValidationResult,
+        ReportRenderer,
         SchemaDiffError,
+        SchemaDiff,
+        Location,
+        SchemaChange,
+        ChangeType,
+        ChangeKind,
+        VersionBump,
+        VersionCheck,
         JsonSchemaAnalyzer,
         ProtobufAnalyzer,
         OpenApiAnalyzer,
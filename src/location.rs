@@ -0,0 +1,312 @@
+//! Typed locations of schema elements
+//!
+//! This module provides [`Location`], which identifies the element a change or
+//! issue refers to. JSON-based formats use RFC 6901 JSON Pointers, while
+//! Protobuf and SQL use dotted qualified names.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+/// Notation used to address a schema element
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LocationStyle {
+    /// RFC 6901 JSON Pointer, e.g. `/paths/~1users/get`
+    Pointer,
+    /// Dotted qualified name, e.g. `users.email`
+    QualifiedName,
+}
+
+/// Location of a schema element
+///
+/// Locations serialize to their string form (see [`fmt::Display`]) so reports
+/// keep the same shape as when locations were plain strings. The optional
+/// source position is not part of the serialized form and does not take part
+/// in equality or hashing.
+#[derive(Debug, Clone)]
+pub struct Location {
+    style: LocationStyle,
+    segments: Vec<String>,
+    /// 1-based line of the element in its source document, if known
+    pub line: Option<usize>,
+    /// 1-based column of the element in its source document, if known
+    pub column: Option<usize>,
+}
+
+impl Location {
+    /// Creates a JSON Pointer location from unescaped reference tokens
+    pub fn pointer<I, S>(segments: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::with_style(LocationStyle::Pointer, segments)
+    }
+
+    /// Creates a qualified name location from its name parts
+    pub fn qualified<I, S>(segments: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::with_style(LocationStyle::QualifiedName, segments)
+    }
+
+    /// Creates a location pointing at the whole document
+    pub fn root() -> Self {
+        Self::pointer(Vec::<String>::new())
+    }
+
+    /// Parses a location from its string form
+    ///
+    /// Strings that are empty or start with `/` are parsed as JSON Pointers,
+    /// anything else as a dotted qualified name.
+    pub fn parse(text: &str) -> Self {
+        if text.is_empty() {
+            Self::root()
+        } else if let Some(tokens) = text.strip_prefix('/') {
+            Self::pointer(tokens.split('/').map(unescape_token))
+        } else {
+            Self::qualified(text.split('.'))
+        }
+    }
+
+    fn with_style<I, S>(style: LocationStyle, segments: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            style,
+            segments: segments.into_iter().map(Into::into).collect(),
+            line: None,
+            column: None,
+        }
+    }
+
+    /// Returns the location of a child element
+    pub fn child(&self, segment: impl Into<String>) -> Self {
+        let mut segments = self.segments.clone();
+        segments.push(segment.into());
+        Self::with_style(self.style, segments)
+    }
+
+    /// Returns the location of the parent element, or `None` at the root
+    pub fn parent(&self) -> Option<Self> {
+        let (_, parent) = self.segments.split_last()?;
+        Some(Self::with_style(self.style, parent.iter().cloned()))
+    }
+
+    /// Returns the notation of this location
+    pub fn style(&self) -> LocationStyle {
+        self.style
+    }
+
+    /// Returns the unescaped path segments
+    pub fn segments(&self) -> &[String] {
+        &self.segments
+    }
+
+    /// Returns the last path segment, if any
+    pub fn last_segment(&self) -> Option<&str> {
+        self.segments.last().map(String::as_str)
+    }
+
+    /// Sets the source position of the element
+    pub fn with_position(mut self, line: usize, column: usize) -> Self {
+        self.line = Some(line);
+        self.column = Some(column);
+        self
+    }
+
+    /// Looks up the element's position in its source document
+    ///
+    /// This is a best-effort textual search: each non-numeric segment is
+    /// searched for as a whole word after the previous one. The position is
+    /// left unset when a segment cannot be found.
+    ///
+    /// # Arguments
+    /// * `content` - The source document the location refers to
+    pub fn resolve_position(&mut self, content: &str) {
+        self.resolve_indexed(&SourceIndex::new(content));
+    }
+
+    /// Looks up the element's position in an indexed source document
+    pub(crate) fn resolve_indexed(&mut self, source: &SourceIndex<'_>) {
+        let mut offset = 0;
+        let mut found = None;
+
+        for segment in &self.segments {
+            if segment.is_empty() || segment.chars().all(|c| c.is_ascii_digit()) {
+                continue;
+            }
+            match source.find_word(segment, offset) {
+                Some(start) => {
+                    found = Some(start);
+                    offset = start + segment.len();
+                }
+                None => return,
+            }
+        }
+
+        if let Some(start) = found {
+            let (line, column) = source.position(start);
+            self.line = Some(line);
+            self.column = Some(column);
+        }
+    }
+}
+
+/// Word and line index of a source document
+///
+/// Resolving the positions of many locations in a large document would scan
+/// it once per location. The index records where every identifier and line
+/// starts, so each lookup only costs a binary search.
+pub(crate) struct SourceIndex<'a> {
+    content: &'a str,
+    line_starts: Vec<usize>,
+    words: HashMap<&'a str, Vec<usize>>,
+}
+
+impl<'a> SourceIndex<'a> {
+    /// Indexes the identifiers and lines of a document
+    pub(crate) fn new(content: &'a str) -> Self {
+        let mut line_starts = vec![0];
+        let mut words: HashMap<&str, Vec<usize>> = HashMap::new();
+        let mut word_start = None;
+        for (index, c) in content.char_indices().chain(std::iter::once((content.len(), '\n'))) {
+            match (is_ident(c), word_start) {
+                (true, None) => word_start = Some(index),
+                (false, Some(start)) => {
+                    words.entry(&content[start..index]).or_default().push(start);
+                    word_start = None;
+                }
+                _ => {}
+            }
+            if c == '\n' && index < content.len() {
+                line_starts.push(index + 1);
+            }
+        }
+
+        Self { content, line_starts, words }
+    }
+
+    /// Finds `word` at or after `offset`, not surrounded by identifier characters
+    fn find_word(&self, word: &str, offset: usize) -> Option<usize> {
+        if !word.chars().all(is_ident) {
+            return find_word(self.content, word, offset);
+        }
+
+        let starts = self.words.get(word)?;
+        starts.get(starts.partition_point(|start| *start < offset)).copied()
+    }
+
+    /// Returns the 1-based line and column of a byte offset
+    fn position(&self, offset: usize) -> (usize, usize) {
+        let line = self.line_starts.partition_point(|start| *start <= offset);
+        (line, offset - self.line_starts[line - 1] + 1)
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.style {
+            LocationStyle::Pointer => {
+                for segment in &self.segments {
+                    write!(f, "/{}", escape_token(segment))?;
+                }
+                Ok(())
+            }
+            LocationStyle::QualifiedName => f.write_str(&self.segments.join(".")),
+        }
+    }
+}
+
+impl PartialEq for Location {
+    fn eq(&self, other: &Self) -> bool {
+        self.style == other.style && self.segments == other.segments
+    }
+}
+
+impl Eq for Location {}
+
+impl Hash for Location {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.style.hash(state);
+        self.segments.hash(state);
+    }
+}
+
+impl PartialEq<str> for Location {
+    fn eq(&self, other: &str) -> bool {
+        *self == Location::parse(other)
+    }
+}
+
+impl PartialEq<&str> for Location {
+    fn eq(&self, other: &&str) -> bool {
+        *self == Location::parse(other)
+    }
+}
+
+impl From<&str> for Location {
+    fn from(text: &str) -> Self {
+        Self::parse(text)
+    }
+}
+
+impl From<String> for Location {
+    fn from(text: String) -> Self {
+        Self::parse(&text)
+    }
+}
+
+impl Serialize for Location {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Location {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        Ok(Self::parse(&text))
+    }
+}
+
+/// Escapes a JSON Pointer reference token (RFC 6901, section 3)
+fn escape_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+/// Unescapes a JSON Pointer reference token (RFC 6901, section 4)
+fn unescape_token(token: &str) -> String {
+    token.replace("~1", "/").replace("~0", "~")
+}
+
+/// Checks whether a character can be part of an identifier
+fn is_ident(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Finds `word` in `content` at or after `offset`, not surrounded by identifier characters
+fn find_word(content: &str, word: &str, offset: usize) -> Option<usize> {
+    let mut from = offset;
+
+    while let Some(relative) = content.get(from..)?.find(word) {
+        let start = from + relative;
+        let end = start + word.len();
+        let before_ok = content[..start].chars().next_back().is_none_or(|c| !is_ident(c));
+        let after_ok = content[end..].chars().next().is_none_or(|c| !is_ident(c));
+        if before_ok && after_ok {
+            return Some(start);
+        }
+        from = end;
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,63 @@
+use super::*;
+
+#[test]
+fn test_pointer_escaping() {
+    let location = Location::pointer(["paths", "/users/{id}", "get"]);
+
+    assert_eq!(location.to_string(), "/paths/~1users~1{id}/get");
+    assert_eq!(Location::parse("/paths/~1users~1{id}/get"), location);
+    assert_eq!(Location::pointer(["a~b"]).to_string(), "/a~0b");
+    assert_eq!(Location::root().to_string(), "");
+}
+
+#[test]
+fn test_qualified_names() {
+    let table = Location::qualified(["public", "users"]);
+    let column = table.child("email");
+
+    assert_eq!(column.to_string(), "public.users.email");
+    assert_eq!(column.style(), LocationStyle::QualifiedName);
+    assert_eq!(column.parent(), Some(table));
+    assert_eq!(Location::parse("users.email"), Location::qualified(["users", "email"]));
+}
+
+#[test]
+fn test_serializes_as_string() {
+    let location = Location::pointer(["properties", "name"]).with_position(3, 5);
+
+    let json = serde_json::to_string(&location).unwrap();
+    assert_eq!(json, r#""/properties/name""#);
+
+    let parsed: Location = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, location);
+    assert_eq!(parsed.line, None);
+}
+
+#[test]
+fn test_resolve_position() {
+    let content = "CREATE TABLE users (\n    id INTEGER,\n    user_name TEXT,\n    name TEXT\n);";
+    let mut location = Location::qualified(["users", "name"]);
+
+    location.resolve_position(content);
+
+    assert_eq!(location.line, Some(4));
+    assert_eq!(location.column, Some(5));
+
+    let mut missing = Location::qualified(["orders", "id"]);
+    missing.resolve_position(content);
+    assert_eq!(missing.line, None);
+}
+
+#[test]
+fn test_indexed_resolution_matches_whole_words_after_parent() {
+    let content = "paths:\n  /users/{id}:\n    get: {}\nnames:\n  users_id: 1\n  id: 2\n";
+    let source = SourceIndex::new(content);
+
+    let mut location = Location::qualified(["names", "id"]);
+    location.resolve_indexed(&source);
+    assert_eq!((location.line, location.column), (Some(6), Some(3)));
+
+    let mut path = Location::pointer(["paths", "/users/{id}", "get"]);
+    path.resolve_indexed(&source);
+    assert_eq!((path.line, path.column), (Some(3), Some(5)));
+}
@@ -4,7 +4,7 @@
 //! schema migration plans.
 
 use serde::{Serialize, Deserialize};
-use crate::analyzer::SchemaChange;
+use crate::analyzer::{detect_renames, SchemaChange};
 
 /// Represents a plan for migrating between schema versions
 ///
@@ -31,6 +31,9 @@ pub struct MigrationPlan {
 impl MigrationPlan {
     /// Creates a new migration plan
     ///
+    /// Removals and additions that look like renames are merged into single
+    /// rename changes.
+    ///
     /// # Arguments
     /// * `source_version` - Version identifier of the source schema
     /// * `target_version` - Version identifier of the target schema
@@ -39,6 +42,7 @@ impl MigrationPlan {
     /// # Returns
     /// A new MigrationPlan instance with calculated impact scores
     pub fn new(source_version: String, target_version: String, changes: Vec<SchemaChange>) -> Self {
+        let changes = detect_renames(changes);
         let impact_score = Self::calculate_impact(&changes);
         let is_breaking = Self::detect_breaking_changes(&changes);
 
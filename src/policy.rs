@@ -0,0 +1,158 @@
+//! Team-specific compatibility policies
+//!
+//! This module provides [`Policy`], which adjusts how detected changes are
+//! judged: it can remap the severity of a rule, ignore rules or locations
+//! entirely and set the score a report needs to count as compatible.
+//!
+//! Policies are plain data and can be loaded from TOML, YAML or JSON:
+//!
+//! ```toml
+//! threshold = 90
+//! ignore = ["/paths/~1internal*"]
+//! ignore_rules = ["keyword-added"]
+//!
+//! [severities]
+//! array-length-changed = "error"
+//! column-type-changed = "info"
+//! ```
+
+use crate::analyzer::{issue_for, ChangeKind};
+use crate::error::{Result, SchemaDiffError};
+use crate::location::Location;
+use crate::report::{CompatibilityReport, IssueSeverity};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Rules for judging schema changes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Policy {
+    /// Minimum compatibility score for a report to count as compatible
+    pub threshold: u8,
+    /// Severities overriding the default severity of a rule, keyed by rule ID
+    pub severities: HashMap<ChangeKind, IssueSeverity>,
+    /// Glob patterns of locations whose changes are ignored
+    ///
+    /// Patterns are matched against the string form of a location, e.g.
+    /// `/paths/~1users/get` or `users.email`, and `*` matches any characters.
+    pub ignore: Vec<String>,
+    /// Rules whose changes are ignored
+    pub ignore_rules: Vec<ChangeKind>,
+}
+
+impl Policy {
+    /// Score threshold used when a policy does not set one
+    pub const DEFAULT_THRESHOLD: u8 = 80;
+
+    /// Loads a policy from a file
+    ///
+    /// The format is chosen by the file extension: `.yaml`/`.yml` for YAML,
+    /// `.json` for JSON and TOML otherwise.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)?;
+
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("yaml") | Some("yml") => Self::from_yaml(&content),
+            Some("json") => Self::validated(serde_json::from_str(&content)?),
+            _ => Self::from_toml(&content),
+        }
+    }
+
+    /// Parses a policy from TOML
+    pub fn from_toml(content: &str) -> Result<Self> {
+        let policy = toml::from_str(content).map_err(|e| SchemaDiffError::PolicyError(e.to_string()))?;
+        Self::validated(policy)
+    }
+
+    /// Parses a policy from YAML
+    pub fn from_yaml(content: &str) -> Result<Self> {
+        let policy = serde_yaml::from_str(content).map_err(|e| SchemaDiffError::PolicyError(e.to_string()))?;
+        Self::validated(policy)
+    }
+
+    /// Checks that the policy's ignore patterns are valid globs
+    fn validated(policy: Self) -> Result<Self> {
+        policy.ignore_matcher()?;
+        Ok(policy)
+    }
+
+    /// Returns the severity of a rule under this policy
+    pub fn severity(&self, kind: ChangeKind) -> IssueSeverity {
+        self.severities.get(&kind).copied().unwrap_or_else(|| kind.default_severity())
+    }
+
+    /// Applies this policy to a compatibility report
+    ///
+    /// Ignored changes and their issues are dropped, issue severities are
+    /// remapped, and changes whose rule is overridden with a severity above
+    /// `Info` gain an issue if the analyzer did not report one.
+    /// The score is recomputed from the remaining issues and compared against
+    /// the policy's threshold.
+    ///
+    /// # Arguments
+    /// * `report` - The report produced by an analyzer
+    ///
+    /// # Returns
+    /// The report as judged by this policy, or `SchemaDiffError::PolicyError`
+    /// if an ignore pattern is invalid
+    pub fn apply(&self, mut report: CompatibilityReport) -> Result<CompatibilityReport> {
+        let matcher = self.ignore_matcher()?;
+        let before = report.deductions();
+        let is_ignored = |kind: ChangeKind, location: &Location| {
+            self.ignore_rules.contains(&kind) || matcher.is_match(location.to_string())
+        };
+        report.changes.retain(|change| !is_ignored(change.kind, &change.location));
+        report.issues.retain(|issue| !is_ignored(issue.kind, &issue.location));
+        for issue in &mut report.issues {
+            if let Some(severity) = self.severities.get(&issue.kind) {
+                issue.severity = *severity;
+            }
+        }
+
+        let raised: Vec<_> = report
+            .changes
+            .iter()
+            .filter(|change| {
+                !report
+                    .issues
+                    .iter()
+                    .any(|issue| issue.kind == change.kind && issue.location == change.location)
+            })
+            .filter_map(|change| issue_for(change, *self.severities.get(&change.kind)?))
+            .collect();
+        report.issues.extend(raised);
+
+        report.rescore(self.threshold, before);
+
+        Ok(report)
+    }
+
+    /// Compiles the ignore patterns
+    fn ignore_matcher(&self) -> Result<GlobSet> {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in &self.ignore {
+            let glob = Glob::new(pattern)
+                .map_err(|e| SchemaDiffError::PolicyError(format!("invalid ignore pattern '{}': {}", pattern, e)))?;
+            builder.add(glob);
+        }
+        builder.build().map_err(|e| SchemaDiffError::PolicyError(e.to_string()))
+    }
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Self {
+            threshold: Self::DEFAULT_THRESHOLD,
+            severities: HashMap::new(),
+            ignore: Vec::new(),
+            ignore_rules: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
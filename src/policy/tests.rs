@@ -0,0 +1,81 @@
+use super::*;
+use crate::analyzer::build_report;
+use crate::SchemaChange;
+
+fn report() -> CompatibilityReport {
+    let changes = vec![
+        SchemaChange::from_kind(ChangeKind::PathRemoved, "/paths/~1internal~1health", "Path removed", HashMap::new()),
+        SchemaChange::from_kind(ChangeKind::ArrayLengthChanged, "/properties/status/enum", "Enum grew", HashMap::new()),
+        SchemaChange::from_kind(ChangeKind::PropertyAdded, "/properties/email", "Property added", HashMap::new()),
+    ];
+    build_report(changes, HashMap::new())
+}
+
+#[test]
+fn test_default_policy_keeps_report() {
+    let original = report();
+    let judged = Policy::default().apply(original.clone()).unwrap();
+
+    assert_eq!(judged.compatibility_score, original.compatibility_score);
+    assert_eq!(judged.is_compatible, original.is_compatible);
+    assert_eq!(judged.issues.len(), original.issues.len());
+}
+
+#[test]
+fn test_policy_remaps_ignores_and_sets_threshold() {
+    let policy = Policy::from_toml(
+        r#"
+            threshold = 95
+            ignore = ["/paths/~1internal*"]
+
+            [severities]
+            array-length-changed = "error"
+            property-added = "warning"
+        "#,
+    )
+    .unwrap();
+
+    let judged = policy.apply(report()).unwrap();
+
+    assert_eq!(judged.changes.len(), 2);
+    let severity_of = |kind| judged.issues.iter().find(|issue| issue.kind == kind).map(|issue| issue.severity);
+    assert_eq!(severity_of(ChangeKind::PathRemoved), None);
+    assert_eq!(severity_of(ChangeKind::ArrayLengthChanged), Some(IssueSeverity::Error));
+    assert_eq!(severity_of(ChangeKind::PropertyAdded), Some(IssueSeverity::Warning));
+    assert_eq!(judged.compatibility_score, 65);
+    assert!(!judged.is_compatible);
+}
+
+#[test]
+fn test_policy_from_yaml_ignores_rules() {
+    let policy = Policy::from_yaml("ignore_rules: [path-removed]\nthreshold: 100\n").unwrap();
+    let judged = policy.apply(report()).unwrap();
+
+    assert_eq!(judged.compatibility_score, 90);
+    assert!(!judged.is_compatible);
+    assert!(judged.changes.iter().all(|change| change.kind != ChangeKind::PathRemoved));
+}
+
+#[test]
+fn test_policy_rescores_reports_below_zero() {
+    let changes = (0..5)
+        .map(|index| SchemaChange::from_kind(ChangeKind::PathRemoved, format!("/paths/~1v{}", index), "Path removed", HashMap::new()))
+        .collect();
+    let report = build_report(changes, HashMap::new());
+    assert_eq!(report.compatibility_score, 0);
+
+    let policy = Policy::from_yaml("ignore: ['/paths/~1v0']\n").unwrap();
+    let judged = policy.apply(report.clone()).unwrap();
+    assert_eq!(judged.issues.len(), 4);
+    assert_eq!(judged.compatibility_score, 0);
+    assert!(!judged.is_compatible);
+
+    let policy = Policy::from_toml("[severities]\npath-removed = \"warning\"").unwrap();
+    assert_eq!(policy.apply(report).unwrap().compatibility_score, 50);
+}
+
+#[test]
+fn test_invalid_policy_is_rejected() {
+    assert!(matches!(Policy::from_toml("[severities]\nno-such-rule = \"error\""), Err(SchemaDiffError::PolicyError(_))));
+    assert!(matches!(Policy::from_yaml("ignore: ['/paths/[']"), Err(SchemaDiffError::PolicyError(_))));
+}
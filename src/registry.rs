@@ -0,0 +1,477 @@
+//! Local file-backed schema registry
+//!
+//! This module provides [`Registry`], a store of schema versions grouped by
+//! subject in the spirit of Confluent Schema Registry, without a server. New
+//! versions are only registered if they pass the subject's
+//! [`CompatibilityLevel`], checked with the analyzers of a [`SchemaDiff`].
+//!
+//! The store is a plain directory:
+//!
+//! ```text
+//! registry/
+//! ├── config.json            default compatibility level
+//! ├── next_id                next global schema ID
+//! └── subjects/
+//!     └── users-value/
+//!         ├── config.json    compatibility level of the subject, if set
+//!         ├── next_version   next version number of the subject
+//!         ├── 1.json
+//!         └── 2.json
+//! ```
+//!
+//! Subject directories are named after their subject, with characters other
+//! than ASCII letters, digits, `.`, `_` and `-` percent-encoded.
+//!
+//! The registry does not lock the directory, so it should not be written by
+//! several processes at once.
+
+use crate::compatibility::CompatibilityMode;
+use crate::engine::SchemaDiff;
+use crate::error::{Result, SchemaDiffError};
+use crate::history::{HistoryReport, VersionReport};
+use crate::Schema;
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Characters of subject names that are percent-encoded in directory names
+const SUBJECT_ESCAPES: &AsciiSet = &NON_ALPHANUMERIC.remove(b'.').remove(b'_').remove(b'-');
+
+/// Compatibility a subject requires of new versions, as in Confluent Schema Registry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CompatibilityLevel {
+    /// New versions are not checked
+    None,
+    /// New versions must be backward compatible with the latest version
+    #[default]
+    Backward,
+    /// New versions must be backward compatible with every version
+    BackwardTransitive,
+    /// New versions must be forward compatible with the latest version
+    Forward,
+    /// New versions must be forward compatible with every version
+    ForwardTransitive,
+    /// New versions must be fully compatible with the latest version
+    Full,
+    /// New versions must be fully compatible with every version
+    FullTransitive,
+}
+
+impl CompatibilityLevel {
+    /// Returns the direction new versions are checked in, or `None` if they are not checked
+    pub fn mode(&self) -> Option<CompatibilityMode> {
+        match self {
+            CompatibilityLevel::None => None,
+            CompatibilityLevel::Backward | CompatibilityLevel::BackwardTransitive => Some(CompatibilityMode::Backward),
+            CompatibilityLevel::Forward | CompatibilityLevel::ForwardTransitive => Some(CompatibilityMode::Forward),
+            CompatibilityLevel::Full | CompatibilityLevel::FullTransitive => Some(CompatibilityMode::Full),
+        }
+    }
+
+    /// Checks whether new versions are checked against every version instead of the latest
+    pub fn is_transitive(&self) -> bool {
+        matches!(
+            self,
+            CompatibilityLevel::BackwardTransitive | CompatibilityLevel::ForwardTransitive | CompatibilityLevel::FullTransitive
+        )
+    }
+}
+
+impl fmt::Display for CompatibilityLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            CompatibilityLevel::None => "NONE",
+            CompatibilityLevel::Backward => "BACKWARD",
+            CompatibilityLevel::BackwardTransitive => "BACKWARD_TRANSITIVE",
+            CompatibilityLevel::Forward => "FORWARD",
+            CompatibilityLevel::ForwardTransitive => "FORWARD_TRANSITIVE",
+            CompatibilityLevel::Full => "FULL",
+            CompatibilityLevel::FullTransitive => "FULL_TRANSITIVE",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for CompatibilityLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_uppercase().replace('-', "_").as_str() {
+            "NONE" => Ok(CompatibilityLevel::None),
+            "BACKWARD" => Ok(CompatibilityLevel::Backward),
+            "BACKWARD_TRANSITIVE" => Ok(CompatibilityLevel::BackwardTransitive),
+            "FORWARD" => Ok(CompatibilityLevel::Forward),
+            "FORWARD_TRANSITIVE" => Ok(CompatibilityLevel::ForwardTransitive),
+            "FULL" => Ok(CompatibilityLevel::Full),
+            "FULL_TRANSITIVE" => Ok(CompatibilityLevel::FullTransitive),
+            _ => Err(format!("unknown compatibility level '{}'", s)),
+        }
+    }
+}
+
+/// A schema stored in the registry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegisteredSchema {
+    /// Subject the schema is registered under
+    pub subject: String,
+    /// Version of the schema within its subject, starting at 1
+    pub version: u32,
+    /// Registry-wide ID of the schema
+    pub id: u32,
+    /// The schema itself
+    pub schema: Schema,
+}
+
+/// Configuration of the registry or of a subject
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct Config {
+    compatibility: CompatibilityLevel,
+}
+
+/// File-backed store of schema versions grouped by subject
+pub struct Registry {
+    root: PathBuf,
+    engine: SchemaDiff,
+}
+
+impl Registry {
+    /// Opens the registry stored in a directory, creating it if needed
+    ///
+    /// Compatibility checks use `SchemaDiff::new()`.
+    pub fn open(root: impl Into<PathBuf>) -> Result<Self> {
+        let root = root.into();
+        fs::create_dir_all(root.join("subjects"))?;
+
+        Ok(Self {
+            root,
+            engine: SchemaDiff::new(),
+        })
+    }
+
+    /// Sets the engine used for compatibility checks
+    ///
+    /// The engine's options apply to every check, except that the
+    /// compatibility mode is taken from the subject's level.
+    pub fn with_engine(mut self, engine: SchemaDiff) -> Self {
+        self.engine = engine;
+        self
+    }
+
+    /// Returns the directory the registry is stored in
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Returns the names of all subjects with at least one version, sorted
+    pub fn subjects(&self) -> Result<Vec<String>> {
+        let mut subjects = Vec::new();
+        for entry in fs::read_dir(self.root.join("subjects"))? {
+            let entry = entry?;
+            let Some(name) = entry.file_name().to_str().and_then(|name| percent_decode_str(name).decode_utf8().ok()).map(String::from) else {
+                continue;
+            };
+            if entry.file_type()?.is_dir() && !self.versions_in(&name)?.is_empty() {
+                subjects.push(name);
+            }
+        }
+
+        subjects.sort();
+        Ok(subjects)
+    }
+
+    /// Returns the versions registered under a subject, in ascending order
+    ///
+    /// # Returns
+    /// The versions, or `SchemaDiffError::NotFound` if the subject has none
+    pub fn versions(&self, subject: &str) -> Result<Vec<u32>> {
+        let versions = self.versions_in(subject)?;
+        if versions.is_empty() {
+            return Err(SchemaDiffError::NotFound(format!("subject '{}'", subject)));
+        }
+        Ok(versions)
+    }
+
+    /// Fetches a version of a subject
+    pub fn get(&self, subject: &str, version: u32) -> Result<RegisteredSchema> {
+        let path = self.subject_dir(subject)?.join(format!("{}.json", version));
+        match fs::read_to_string(&path) {
+            Ok(content) => Ok(serde_json::from_str(&content)?),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Err(SchemaDiffError::NotFound(format!(
+                "version {} of subject '{}'",
+                version, subject
+            ))),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Fetches the latest version of a subject
+    pub fn latest(&self, subject: &str) -> Result<RegisteredSchema> {
+        let versions = self.versions(subject)?;
+        self.get(subject, versions[versions.len() - 1])
+    }
+
+    /// Fetches every version of a subject, in ascending order
+    pub fn history(&self, subject: &str) -> Result<Vec<RegisteredSchema>> {
+        self.versions(subject)?
+            .into_iter()
+            .map(|version| self.get(subject, version))
+            .collect()
+    }
+
+    /// Fetches a schema by its registry-wide ID
+    pub fn get_by_id(&self, id: u32) -> Result<RegisteredSchema> {
+        for subject in self.subjects()? {
+            if let Some(registered) = self.history(&subject)?.into_iter().find(|registered| registered.id == id) {
+                return Ok(registered);
+            }
+        }
+        Err(SchemaDiffError::NotFound(format!("schema with ID {}", id)))
+    }
+
+    /// Checks a candidate schema against a subject without registering it
+    ///
+    /// The candidate is compared with the latest version, or with every
+    /// version for transitive levels, in the direction of the subject's
+    /// compatibility level. Subjects without versions or with level `NONE`
+    /// yield a compatible report without any comparisons.
+    pub fn check(&self, subject: &str, schema: &Schema) -> Result<HistoryReport> {
+        self.compare(subject, schema).map(|(_, report)| report)
+    }
+
+    /// Checks a candidate schema against one version of a subject without registering it
+    ///
+    /// The candidate is compared in the direction of the subject's
+    /// compatibility level, regardless of whether the level is transitive.
+    pub fn check_version(&self, subject: &str, version: u32, schema: &Schema) -> Result<HistoryReport> {
+        let registered = self.get(subject, version)?;
+        self.compare_with(self.compatibility(subject)?, &[registered], schema)
+    }
+
+    /// Finds the version of a subject whose schema is identical to `schema`
+    ///
+    /// # Returns
+    /// The registered schema, or `SchemaDiffError::NotFound` if the subject
+    /// has no such version
+    pub fn lookup(&self, subject: &str, schema: &Schema) -> Result<RegisteredSchema> {
+        self.history_or_empty(subject)?
+            .into_iter()
+            .find(|registered| is_identical(&registered.schema, schema))
+            .ok_or_else(|| SchemaDiffError::NotFound(format!("schema in subject '{}'", subject)))
+    }
+
+    /// Registers a schema as the next version of a subject
+    ///
+    /// Registering a schema identical to an existing version of the subject
+    /// returns that version instead. Numbers of deleted versions are not
+    /// reused.
+    ///
+    /// # Returns
+    /// The registered schema, or `SchemaDiffError::IncompatibleSchema` if it
+    /// fails the subject's compatibility check
+    pub fn register(&self, subject: &str, mut schema: Schema) -> Result<RegisteredSchema> {
+        let existing = self.history_or_empty(subject)?;
+        if let Some(registered) = existing.iter().find(|registered| is_identical(&registered.schema, &schema)) {
+            return Ok(registered.clone());
+        }
+
+        let (compared, report) = self.compare(subject, &schema)?;
+        if let Some((registered, _)) = compared
+            .iter()
+            .zip(&report.reports)
+            .find(|(_, entry)| !entry.report.is_compatible)
+        {
+            return Err(SchemaDiffError::IncompatibleSchema(format!(
+                "schema is not {} compatible with version {} of subject '{}'",
+                report.mode, registered.version, subject
+            )));
+        }
+
+        // The source path is meaningless once the schema is stored
+        schema.source = None;
+        let dir = self.subject_dir(subject)?;
+        fs::create_dir_all(&dir)?;
+        let version = increment(&dir.join("next_version"), existing.last().map_or(1, |latest| latest.version + 1))?;
+        let registered = RegisteredSchema {
+            subject: subject.to_string(),
+            version,
+            id: self.next_id()?,
+            schema,
+        };
+        write_json(&dir.join(format!("{}.json", registered.version)), &registered)?;
+
+        Ok(registered)
+    }
+
+    /// Deletes a version of a subject
+    ///
+    /// # Returns
+    /// The deleted schema
+    pub fn delete_version(&self, subject: &str, version: u32) -> Result<RegisteredSchema> {
+        let registered = self.get(subject, version)?;
+        fs::remove_file(self.subject_dir(subject)?.join(format!("{}.json", version)))?;
+        Ok(registered)
+    }
+
+    /// Deletes a subject with all its versions and configuration
+    ///
+    /// # Returns
+    /// The deleted versions
+    pub fn delete_subject(&self, subject: &str) -> Result<Vec<u32>> {
+        let versions = self.versions(subject)?;
+        fs::remove_dir_all(self.subject_dir(subject)?)?;
+        Ok(versions)
+    }
+
+    /// Returns the default compatibility level of subjects without their own
+    pub fn default_compatibility(&self) -> Result<CompatibilityLevel> {
+        Ok(read_config(&self.root.join("config.json"))?.map_or_else(Default::default, |config| config.compatibility))
+    }
+
+    /// Sets the default compatibility level of subjects without their own
+    pub fn set_default_compatibility(&self, level: CompatibilityLevel) -> Result<()> {
+        write_json(&self.root.join("config.json"), &Config { compatibility: level })
+    }
+
+    /// Returns the compatibility level of a subject, falling back to the default
+    pub fn compatibility(&self, subject: &str) -> Result<CompatibilityLevel> {
+        match read_config(&self.subject_dir(subject)?.join("config.json"))? {
+            Some(config) => Ok(config.compatibility),
+            None => self.default_compatibility(),
+        }
+    }
+
+    /// Sets the compatibility level of a subject
+    pub fn set_compatibility(&self, subject: &str, level: CompatibilityLevel) -> Result<()> {
+        let dir = self.subject_dir(subject)?;
+        fs::create_dir_all(&dir)?;
+        write_json(&dir.join("config.json"), &Config { compatibility: level })
+    }
+
+    /// Compares a candidate with the versions its subject's level requires
+    ///
+    /// # Returns
+    /// The compared versions and the report, whose entries are in the same order
+    fn compare(&self, subject: &str, schema: &Schema) -> Result<(Vec<RegisteredSchema>, HistoryReport)> {
+        let level = self.compatibility(subject)?;
+        let mut compared = self.history_or_empty(subject)?;
+        if !level.is_transitive() {
+            compared = compared.split_off(compared.len().saturating_sub(1));
+        }
+
+        let report = self.compare_with(level, &compared, schema)?;
+        Ok((compared, report))
+    }
+
+    /// Compares a candidate with stored versions in the direction of a level
+    fn compare_with(&self, level: CompatibilityLevel, compared: &[RegisteredSchema], schema: &Schema) -> Result<HistoryReport> {
+        let Some(mode) = level.mode() else {
+            return Ok(HistoryReport::new(schema.version.clone(), CompatibilityMode::default(), Vec::new()));
+        };
+
+        let options = self.engine.options().clone().with_mode(mode);
+        let reports = compared
+            .iter()
+            .map(|registered| {
+                Ok(VersionReport {
+                    version: registered.schema.version.clone(),
+                    report: self.engine.analyze_with(&registered.schema, schema, &options)?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(HistoryReport::new(schema.version.clone(), mode, reports))
+    }
+
+    /// Fetches every version of a subject, or nothing for unknown subjects
+    fn history_or_empty(&self, subject: &str) -> Result<Vec<RegisteredSchema>> {
+        self.versions_in(subject)?
+            .into_iter()
+            .map(|version| self.get(subject, version))
+            .collect()
+    }
+
+    /// Lists the version files of a subject
+    fn versions_in(&self, subject: &str) -> Result<Vec<u32>> {
+        let entries = match fs::read_dir(self.subject_dir(subject)?) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut versions = Vec::new();
+        for entry in entries {
+            let name = entry?.file_name();
+            if let Some(version) = name.to_str().and_then(|name| name.strip_suffix(".json")?.parse().ok()) {
+                versions.push(version);
+            }
+        }
+
+        versions.sort_unstable();
+        Ok(versions)
+    }
+
+    /// Returns the directory of a subject, percent-encoding its name into a safe file name
+    fn subject_dir(&self, subject: &str) -> Result<PathBuf> {
+        if subject.is_empty() {
+            return Err(SchemaDiffError::RegistryError("subject name must not be empty".to_string()));
+        }
+
+        let mut name = utf8_percent_encode(subject, SUBJECT_ESCAPES).to_string();
+        if name.starts_with('.') {
+            name.replace_range(..1, "%2E");
+        }
+        Ok(self.root.join("subjects").join(name))
+    }
+
+    /// Allocates the next registry-wide schema ID
+    fn next_id(&self) -> Result<u32> {
+        increment(&self.root.join("next_id"), 1)
+    }
+}
+
+/// Advances a counter file, returning its current value
+///
+/// A missing counter starts at `start`.
+fn increment(path: &Path, start: u32) -> Result<u32> {
+    let value = match fs::read_to_string(path) {
+        Ok(content) => content
+            .trim()
+            .parse()
+            .map_err(|_| SchemaDiffError::RegistryError(format!("invalid counter in '{}'", path.display())))?,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => start,
+        Err(e) => return Err(e.into()),
+    };
+
+    fs::write(path, format!("{}\n", value + 1))?;
+    Ok(value)
+}
+
+/// Checks whether two schemas have the same format and content
+fn is_identical(a: &Schema, b: &Schema) -> bool {
+    a.format == b.format && a.content == b.content
+}
+
+/// Reads a configuration file, returning `None` if it does not exist
+fn read_config(path: &Path) -> Result<Option<Config>> {
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(Some(serde_json::from_str(&content)?)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Writes a value as pretty-printed JSON
+fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    let mut content = serde_json::to_string_pretty(value)?;
+    content.push('\n');
+    fs::write(path, content)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests;
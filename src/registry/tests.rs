@@ -0,0 +1,99 @@
+use super::*;
+use crate::SchemaFormat;
+use semver::Version;
+
+fn registry(name: &str) -> Registry {
+    let root = std::env::temp_dir().join(format!("schema-diff-registry-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    Registry::open(root).unwrap()
+}
+
+fn sql(content: &str, version: &str) -> Schema {
+    Schema::new(SchemaFormat::SqlDDL, content.to_string(), Version::parse(version).unwrap())
+}
+
+#[test]
+fn test_register_list_and_fetch() {
+    let registry = registry("fetch");
+    let first = registry.register("users", sql("CREATE TABLE users (id INT);", "1.0.0")).unwrap();
+    let second = registry.register("users", sql("CREATE TABLE users (id INT, name TEXT);", "1.1.0")).unwrap();
+    registry.register("orders", sql("CREATE TABLE orders (id INT);", "1.0.0")).unwrap();
+
+    assert_eq!((first.version, first.id), (1, 1));
+    assert_eq!((second.version, second.id), (2, 2));
+    assert_eq!(registry.subjects().unwrap(), ["orders", "users"]);
+    assert_eq!(registry.versions("users").unwrap(), [1, 2]);
+    assert_eq!(registry.latest("users").unwrap().schema.content, second.schema.content);
+    assert_eq!(registry.get_by_id(3).unwrap().subject, "orders");
+
+    let again = registry.register("users", sql("CREATE TABLE users (id INT);", "1.0.0")).unwrap();
+    assert_eq!((again.version, again.id), (1, 1));
+
+    fs::remove_dir_all(registry.root()).unwrap();
+}
+
+#[test]
+fn test_register_rejects_incompatible_versions() {
+    let registry = registry("reject");
+    registry.register("users", sql("CREATE TABLE users (id INT);", "1.0.0")).unwrap();
+
+    let not_null = sql("CREATE TABLE users (id INT, email TEXT NOT NULL);", "1.1.0");
+    assert!(!registry.check("users", &not_null).unwrap().is_compatible);
+    assert!(matches!(registry.register("users", not_null.clone()), Err(SchemaDiffError::IncompatibleSchema(_))));
+    assert_eq!(registry.versions("users").unwrap(), [1]);
+
+    registry.set_compatibility("users", CompatibilityLevel::Forward).unwrap();
+    assert_eq!(registry.register("users", not_null).unwrap().version, 2);
+
+    fs::remove_dir_all(registry.root()).unwrap();
+}
+
+#[test]
+fn test_transitive_levels_check_every_version() {
+    let registry = registry("transitive");
+    registry.set_default_compatibility(CompatibilityLevel::Forward).unwrap();
+    registry.register("users", sql("CREATE TABLE users (id INT, name TEXT);", "1.0.0")).unwrap();
+    registry.set_compatibility("users", CompatibilityLevel::None).unwrap();
+    registry.register("users", sql("CREATE TABLE users (id INT);", "1.1.0")).unwrap();
+
+    let candidate = sql("CREATE TABLE users (id INT, email TEXT);", "1.2.0");
+    registry.set_compatibility("users", CompatibilityLevel::Forward).unwrap();
+    assert!(registry.check("users", &candidate).unwrap().is_compatible);
+
+    registry.set_compatibility("users", CompatibilityLevel::ForwardTransitive).unwrap();
+    let report = registry.check("users", &candidate).unwrap();
+    assert_eq!(report.oldest_incompatible, Some(Version::new(1, 0, 0)));
+
+    fs::remove_dir_all(registry.root()).unwrap();
+}
+
+#[test]
+fn test_delete_and_missing_entries() {
+    let registry = registry("delete");
+    registry.register("users", sql("CREATE TABLE users (id INT);", "1.0.0")).unwrap();
+    registry.register("users", sql("CREATE TABLE users (id INT, name TEXT);", "1.1.0")).unwrap();
+
+    assert_eq!(registry.delete_version("users", 1).unwrap().version, 1);
+    assert!(matches!(registry.get("users", 1), Err(SchemaDiffError::NotFound(_))));
+    assert_eq!(registry.delete_subject("users").unwrap(), [2]);
+    assert!(matches!(registry.versions("users"), Err(SchemaDiffError::NotFound(_))));
+    assert!(matches!(registry.versions("../etc"), Err(SchemaDiffError::NotFound(_))));
+    assert!(matches!(registry.versions(""), Err(SchemaDiffError::RegistryError(_))));
+    assert_eq!("backward_transitive".parse(), Ok(CompatibilityLevel::BackwardTransitive));
+
+    fs::remove_dir_all(registry.root()).unwrap();
+}
+
+#[test]
+fn test_deleted_version_numbers_are_not_reused() {
+    let registry = registry("reuse");
+    registry.register("users", sql("CREATE TABLE users (id INT);", "1.0.0")).unwrap();
+    registry.register("users", sql("CREATE TABLE users (id INT, name TEXT);", "1.1.0")).unwrap();
+    registry.delete_version("users", 2).unwrap();
+
+    let registered = registry.register("users", sql("CREATE TABLE users (id INT, email TEXT);", "1.2.0")).unwrap();
+    assert_eq!(registered.version, 3);
+    assert_eq!(registry.versions("users").unwrap(), [1, 3]);
+
+    fs::remove_dir_all(registry.root()).unwrap();
+}
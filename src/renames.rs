@@ -0,0 +1,108 @@
+//! User-supplied rename hints
+//!
+//! This module provides [`RenameHints`], explicit renames for the cases rename
+//! detection cannot recognize. Analyzers compare the old element under its
+//! new name, so a hinted element is reported as one rename plus whatever else
+//! changed about it.
+//!
+//! Hints files list one rename per line; blank lines and `#` comments are
+//! ignored:
+//!
+//! ```text
+//! # SQL columns and tables, Protobuf messages and fields
+//! users.email_address -> users.email
+//! # JSON Schema and OpenAPI members
+//! /properties/userName -> /properties/user_name
+//! /paths/~1user~1{id} -> /paths/~1users~1{id}
+//! ```
+
+use crate::error::{Result, SchemaDiffError};
+use crate::location::Location;
+use std::fs;
+use std::path::Path;
+
+/// Declared renames of schema elements, from old to new location
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RenameHints {
+    renames: Vec<(Location, Location)>,
+}
+
+impl RenameHints {
+    /// Creates an empty set of hints
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a rename
+    ///
+    /// # Arguments
+    /// * `old` - Location of the element in the old schema
+    /// * `new` - Location of the element in the new schema
+    pub fn with_rename(mut self, old: impl Into<Location>, new: impl Into<Location>) -> Self {
+        self.insert(old, new);
+        self
+    }
+
+    /// Adds a rename, replacing any earlier hint for the same old location
+    pub fn insert(&mut self, old: impl Into<Location>, new: impl Into<Location>) {
+        let (old, new) = (old.into(), new.into());
+        self.renames.retain(|(hinted, _)| *hinted != old);
+        self.renames.push((old, new));
+    }
+
+    /// Parses hints from `old -> new` lines
+    ///
+    /// # Returns
+    /// The hints, or `SchemaDiffError::ParseError` naming the first line
+    /// without an arrow or location
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut hints = Self::new();
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (old, new) = line
+                .split_once("->")
+                .map(|(old, new)| (old.trim(), new.trim()))
+                .filter(|(old, new)| !old.is_empty() && !new.is_empty())
+                .ok_or_else(|| {
+                    SchemaDiffError::ParseError(format!(
+                        "invalid rename hint on line {}: expected 'old -> new'",
+                        index + 1
+                    ))
+                })?;
+            hints.insert(old, new);
+        }
+        Ok(hints)
+    }
+
+    /// Loads hints from a file, see [`RenameHints::parse`]
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    /// Returns the new location of a renamed element
+    pub fn renamed(&self, old: &Location) -> Option<&Location> {
+        self.renames.iter().find(|(hinted, _)| hinted == old).map(|(_, new)| new)
+    }
+
+    /// Returns the renames, from old to new location
+    pub fn iter(&self) -> impl Iterator<Item = (&Location, &Location)> {
+        self.renames.iter().map(|(old, new)| (old, new))
+    }
+
+    /// Returns the number of renames
+    pub fn len(&self) -> usize {
+        self.renames.len()
+    }
+
+    /// Checks whether there are no renames
+    pub fn is_empty(&self) -> bool {
+        self.renames.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,44 @@
+use super::*;
+
+#[test]
+fn test_parse_hints_file() {
+    let hints = RenameHints::parse(
+        "# columns\nusers.email_address -> users.email\n\n  /properties/userName ->/properties/user_name  \n",
+    )
+    .unwrap();
+
+    assert_eq!(hints.len(), 2);
+    assert_eq!(hints.renamed(&Location::parse("users.email_address")).unwrap(), &"users.email");
+    assert_eq!(hints.renamed(&Location::parse("/properties/userName")).unwrap(), &"/properties/user_name");
+    assert!(hints.renamed(&Location::parse("users.email")).is_none());
+}
+
+#[test]
+fn test_parse_rejects_line_without_arrow() {
+    let error = RenameHints::parse("a.b -> a.c\na.d a.e\n").unwrap_err();
+
+    assert!(matches!(error, SchemaDiffError::ParseError(message) if message.contains("line 2")));
+    assert!(RenameHints::parse("a.b ->").is_err());
+}
+
+#[test]
+fn test_later_hint_replaces_earlier_one() {
+    let hints = RenameHints::new()
+        .with_rename("users.name", "users.full_name")
+        .with_rename("users.name", "users.display_name");
+
+    assert_eq!(hints.len(), 1);
+    assert_eq!(hints.renamed(&Location::parse("users.name")).unwrap(), &"users.display_name");
+}
+
+#[test]
+fn test_from_path() {
+    let path = std::env::temp_dir().join(format!("schema-diff-renames-{}.txt", std::process::id()));
+    fs::write(&path, "User.name -> User.full_name\n").unwrap();
+
+    let hints = RenameHints::from_path(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(hints.iter().count(), 1);
+    assert!(RenameHints::from_path(&path).is_err());
+}
@@ -1,6 +1,38 @@
+//! Compatibility reports and their renderers
+//!
+//! This module provides the report types produced by the analyzers and the
+//! [`ReportRenderer`] trait with built-in Markdown, HTML, JUnit XML and SARIF
+//! renderers.
+
 use serde::{Serialize, Deserialize};
 use std::collections::HashMap;
-use crate::analyzer::SchemaChange;
+use std::fmt;
+use std::str::FromStr;
+use crate::analyzer::{ChangeKind, SchemaChange};
+use crate::baseline::{fingerprint, Suppression};
+use crate::location::Location;
+use crate::error::Result;
+
+pub mod markdown;
+pub mod html;
+pub mod junit;
+pub mod sarif;
+
+/// Core trait for rendering compatibility reports into other formats
+///
+/// Renderers resolve the file an issue belongs to from the report's
+/// `new_source` metadata, which [`crate::SchemaDiff`] sets for schemas loaded
+/// from files.
+pub trait ReportRenderer {
+    /// Renders a compatibility report
+    ///
+    /// # Arguments
+    /// * `report` - The report to render
+    ///
+    /// # Returns
+    /// The rendered document
+    fn render(&self, report: &CompatibilityReport) -> Result<String>;
+}
 
 /// Represents compatibility analysis results
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,28 +49,139 @@ pub struct CompatibilityReport {
     pub metadata: HashMap<String, String>,
 }
 
+impl CompatibilityReport {
+    /// Returns the file the new schema was loaded from, if known
+    pub fn source_file(&self) -> Option<&str> {
+        self.metadata.get("new_source").map(String::as_str)
+    }
+
+    /// Returns the total deduction of the issues not suppressed by a baseline
+    pub(crate) fn deductions(&self) -> u32 {
+        self.issues
+            .iter()
+            .filter(|issue| !issue.is_suppressed())
+            .map(|issue| issue.severity.deduction())
+            .sum()
+    }
+
+    /// Recomputes the score and verdict after the report's issues were judged
+    ///
+    /// If the deductions of the issues differ from `before`, the score is
+    /// recomputed as 100 minus the deductions, clamped at 0. Otherwise the
+    /// analyzer's own score is kept. The verdict is always recomputed.
+    ///
+    /// # Arguments
+    /// * `threshold` - Minimum score for the report to count as compatible
+    /// * `before` - The deductions of the issues before they were judged
+    pub(crate) fn rescore(&mut self, threshold: u8, before: u32) {
+        let deductions = self.deductions();
+        if deductions != before {
+            self.compatibility_score = 100u32.saturating_sub(deductions) as u8;
+        }
+        self.is_compatible = self.compatibility_score >= threshold;
+    }
+}
+
 /// Represents a specific compatibility issue
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CompatibilityIssue {
     /// Severity level of the issue
     pub severity: IssueSeverity,
+    /// Kind of the change that caused the issue
+    pub kind: ChangeKind,
     /// Description of the issue
     pub description: String,
     /// Location of the affected element
-    pub location: String,
+    pub location: Location,
+    /// Baseline entry that accepted the issue, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suppression: Option<Suppression>,
+}
+
+impl CompatibilityIssue {
+    /// Returns the stable fingerprint of this issue
+    ///
+    /// The fingerprint hashes the rule ID and the location only, so it
+    /// survives reordering of the schema and rewording of descriptions.
+    pub fn fingerprint(&self) -> String {
+        fingerprint(self.kind, &self.location)
+    }
+
+    /// Checks whether the issue was accepted by a baseline
+    pub fn is_suppressed(&self) -> bool {
+        self.suppression.is_some()
+    }
 }
 
 /// Represents the severity of a compatibility issue
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum IssueSeverity {
     /// Breaking changes that must be addressed
+    #[serde(alias = "error")]
     Error,
     /// Potentially problematic changes that should be reviewed
+    #[serde(alias = "warning")]
     Warning,
     /// Informational changes that are generally safe
+    #[serde(alias = "info")]
     Info,
 }
 
+impl IssueSeverity {
+    /// Checks whether this severity is at least as serious as `threshold`
+    ///
+    /// # Arguments
+    /// * `threshold` - The minimum severity to compare against
+    ///
+    /// # Returns
+    /// true if this severity is equal to or more serious than `threshold`
+    pub fn is_at_least(&self, threshold: IssueSeverity) -> bool {
+        self.rank() >= threshold.rank()
+    }
+
+    /// Points deducted from the compatibility score for an issue of this severity
+    pub fn deduction(&self) -> u32 {
+        match self {
+            IssueSeverity::Error => 25,
+            IssueSeverity::Warning => 10,
+            IssueSeverity::Info => 0,
+        }
+    }
+
+    /// Numeric rank of the severity, higher is more serious
+    fn rank(&self) -> u8 {
+        match self {
+            IssueSeverity::Error => 2,
+            IssueSeverity::Warning => 1,
+            IssueSeverity::Info => 0,
+        }
+    }
+}
+
+impl fmt::Display for IssueSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            IssueSeverity::Error => "error",
+            IssueSeverity::Warning => "warning",
+            IssueSeverity::Info => "info",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for IssueSeverity {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "error" => Ok(IssueSeverity::Error),
+            "warning" | "warn" => Ok(IssueSeverity::Warning),
+            "info" => Ok(IssueSeverity::Info),
+            other => Err(format!("unknown severity '{}'", other)),
+        }
+    }
+}
+
 /// Represents validation results for schema changes
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ValidationResult {
@@ -55,17 +198,27 @@ pub struct ValidationResult {
 pub struct ValidationError {
     /// Error message
     pub message: String,
-    /// Path to the invalid element
-    pub path: String,
-    /// Error code for programmatic handling
+    /// Location of the invalid element
+    pub path: Location,
+    /// Error code for programmatic handling, the rule ID of the change kind
     pub code: String,
 }
 
-/// Represents a migration plan between schema versions
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct MigrationPlan {
-    /// List of migration steps
-    pub steps: Vec<String>,
-    /// Additional metadata about the migration
-    pub metadata: HashMap<String, String>,
-} 
\ No newline at end of file
+/// Escapes text for inclusion in XML or HTML documents
+pub(crate) fn escape_markup(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests;
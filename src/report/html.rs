@@ -0,0 +1,104 @@
+//! HTML report renderer
+//!
+//! Produces a standalone HTML page that can be published as a build artifact.
+
+use crate::error::Result;
+use crate::report::{escape_markup, CompatibilityReport, IssueSeverity, ReportRenderer};
+use std::fmt::Write;
+
+/// Renders compatibility reports as a standalone HTML document
+pub struct HtmlRenderer;
+
+const STYLE: &str = "body{font-family:sans-serif;margin:2em;color:#24292f}\
+table{border-collapse:collapse;width:100%;margin-bottom:2em}\
+th,td{border:1px solid #d0d7de;padding:6px 10px;text-align:left;vertical-align:top}\
+th{background:#f6f8fa}code{font-size:90%}\
+.error{color:#cf222e;font-weight:bold}.warning{color:#9a6700;font-weight:bold}.info{color:#0969da}\
+.compatible{color:#1a7f37}.incompatible{color:#cf222e}\
+.suppressed{color:#57606a;text-decoration:line-through}";
+
+impl ReportRenderer for HtmlRenderer {
+    fn render(&self, report: &CompatibilityReport) -> Result<String> {
+        let mut out = String::new();
+        let (verdict_class, verdict) = if report.is_compatible {
+            ("compatible", "Compatible")
+        } else {
+            ("incompatible", "Incompatible")
+        };
+
+        let _ = writeln!(out, "<!DOCTYPE html>");
+        let _ = writeln!(out, "<html lang=\"en\">");
+        let _ = writeln!(out, "<head>");
+        let _ = writeln!(out, "<meta charset=\"utf-8\">");
+        let _ = writeln!(out, "<title>Schema compatibility report</title>");
+        let _ = writeln!(out, "<style>{}</style>", STYLE);
+        let _ = writeln!(out, "</head>");
+        let _ = writeln!(out, "<body>");
+        let _ = writeln!(
+            out,
+            "<h1>Schema compatibility: <span class=\"{}\">{}</span></h1>",
+            verdict_class, verdict
+        );
+        if let Some(source) = report.source_file() {
+            let _ = writeln!(out, "<p>File: <code>{}</code></p>", escape_markup(source));
+        }
+        let _ = writeln!(out, "<p>Score: {}/100</p>", report.compatibility_score);
+
+        let _ = writeln!(out, "<h2>Issues ({})</h2>", report.issues.len());
+        if !report.issues.is_empty() {
+            let _ = writeln!(out, "<table>");
+            let _ = writeln!(out, "<tr><th>Severity</th><th>Location</th><th>Description</th></tr>");
+            for issue in &report.issues {
+                let class = if issue.is_suppressed() { "suppressed" } else { severity_class(issue.severity) };
+                let mut description = escape_markup(&issue.description);
+                if let Some(suppression) = &issue.suppression {
+                    description.push_str(" <em>(suppressed by baseline");
+                    if let Some(justification) = &suppression.justification {
+                        description.push_str(": ");
+                        description.push_str(&escape_markup(justification));
+                    }
+                    description.push_str(")</em>");
+                }
+                let _ = writeln!(
+                    out,
+                    "<tr><td class=\"{}\">{}</td><td><code>{}</code></td><td>{}</td></tr>",
+                    class,
+                    issue.severity,
+                    escape_markup(&issue.location.to_string()),
+                    description
+                );
+            }
+            let _ = writeln!(out, "</table>");
+        }
+
+        let _ = writeln!(out, "<h2>Changes ({})</h2>", report.changes.len());
+        if !report.changes.is_empty() {
+            let _ = writeln!(out, "<table>");
+            let _ = writeln!(out, "<tr><th>Type</th><th>Location</th><th>Description</th></tr>");
+            for change in &report.changes {
+                let _ = writeln!(
+                    out,
+                    "<tr><td>{:?}</td><td><code>{}</code></td><td>{}</td></tr>",
+                    change.change_type,
+                    escape_markup(&change.location.to_string()),
+                    escape_markup(&change.description)
+                );
+            }
+            let _ = writeln!(out, "</table>");
+        }
+
+        let _ = writeln!(out, "</body>");
+        let _ = writeln!(out, "</html>");
+
+        Ok(out)
+    }
+}
+
+/// Maps a severity to the CSS class used to highlight it
+fn severity_class(severity: IssueSeverity) -> &'static str {
+    match severity {
+        IssueSeverity::Error => "error",
+        IssueSeverity::Warning => "warning",
+        IssueSeverity::Info => "info",
+    }
+}
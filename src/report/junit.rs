@@ -0,0 +1,87 @@
+//! JUnit XML report renderer
+//!
+//! Produces one test case per compatibility issue so CI systems can display
+//! breaking changes as failed tests.
+
+use crate::error::Result;
+use crate::report::{escape_markup, CompatibilityReport, IssueSeverity, ReportRenderer};
+use std::fmt::Write;
+
+/// Renders compatibility reports as JUnit XML
+///
+/// `Error` issues become failed test cases. `Warning` and `Info` issues are
+/// passing test cases whose description is attached as `system-out`, and
+/// issues suppressed by a baseline are skipped test cases.
+pub struct JunitRenderer;
+
+impl ReportRenderer for JunitRenderer {
+    fn render(&self, report: &CompatibilityReport) -> Result<String> {
+        let mut out = String::new();
+        let suite = report.source_file().unwrap_or("schema");
+        let failures = report
+            .issues
+            .iter()
+            .filter(|issue| issue.severity == IssueSeverity::Error && !issue.is_suppressed())
+            .count();
+        let skipped = report.issues.iter().filter(|issue| issue.is_suppressed()).count();
+
+        let _ = writeln!(out, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>");
+        let _ = writeln!(
+            out,
+            "<testsuites name=\"schema-compatibility\" tests=\"{}\" failures=\"{}\">",
+            report.issues.len(),
+            failures
+        );
+        let _ = writeln!(
+            out,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"{}\">",
+            escape_markup(suite),
+            report.issues.len(),
+            failures,
+            skipped
+        );
+
+        for issue in &report.issues {
+            let _ = writeln!(
+                out,
+                "    <testcase classname=\"{}\" name=\"{}\">",
+                escape_markup(suite),
+                escape_markup(&issue.location.to_string())
+            );
+            if let Some(suppression) = &issue.suppression {
+                let _ = writeln!(
+                    out,
+                    "      <skipped message=\"{}\"/>",
+                    escape_markup(suppression.justification.as_deref().unwrap_or("suppressed by baseline"))
+                );
+                let _ = writeln!(out, "    </testcase>");
+                continue;
+            }
+            match issue.severity {
+                IssueSeverity::Error => {
+                    let _ = writeln!(
+                        out,
+                        "      <failure type=\"{}\" message=\"{}\">{}</failure>",
+                        issue.severity,
+                        escape_markup(&issue.description),
+                        escape_markup(&issue.description)
+                    );
+                }
+                IssueSeverity::Warning | IssueSeverity::Info => {
+                    let _ = writeln!(
+                        out,
+                        "      <system-out>[{}] {}</system-out>",
+                        issue.severity,
+                        escape_markup(&issue.description)
+                    );
+                }
+            }
+            let _ = writeln!(out, "    </testcase>");
+        }
+
+        let _ = writeln!(out, "  </testsuite>");
+        let _ = writeln!(out, "</testsuites>");
+
+        Ok(out)
+    }
+}
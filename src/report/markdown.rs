@@ -0,0 +1,87 @@
+//! Markdown report renderer
+//!
+//! Produces a summary suitable for pull request comments.
+
+use crate::error::Result;
+use crate::report::{CompatibilityReport, IssueSeverity, ReportRenderer};
+use std::fmt::Write;
+
+/// Renders compatibility reports as GitHub-flavoured Markdown
+pub struct MarkdownRenderer;
+
+impl ReportRenderer for MarkdownRenderer {
+    fn render(&self, report: &CompatibilityReport) -> Result<String> {
+        let mut out = String::new();
+
+        let verdict = if report.is_compatible { "Compatible" } else { "Incompatible" };
+        let _ = writeln!(out, "## Schema compatibility: {}", verdict);
+        let _ = writeln!(out);
+        if let Some(source) = report.source_file() {
+            let _ = writeln!(out, "**File:** `{}`  ", source);
+        }
+        let _ = writeln!(out, "**Score:** {}/100  ", report.compatibility_score);
+        let _ = writeln!(
+            out,
+            "**Changes:** {} · **Issues:** {}",
+            report.changes.len(),
+            report.issues.len()
+        );
+
+        if !report.issues.is_empty() {
+            let _ = writeln!(out);
+            let _ = writeln!(out, "| Severity | Location | Description |");
+            let _ = writeln!(out, "| --- | --- | --- |");
+            for issue in &report.issues {
+                let mut description = escape_cell(&issue.description);
+                if let Some(suppression) = &issue.suppression {
+                    description.push_str(" _(suppressed by baseline");
+                    if let Some(justification) = &suppression.justification {
+                        description.push_str(": ");
+                        description.push_str(&escape_cell(justification));
+                    }
+                    description.push_str(")_");
+                }
+                let _ = writeln!(
+                    out,
+                    "| {} | `{}` | {} |",
+                    severity_label(issue.severity),
+                    escape_cell(&issue.location.to_string()),
+                    description
+                );
+            }
+        }
+
+        if !report.changes.is_empty() {
+            let _ = writeln!(out);
+            let _ = writeln!(out, "<details><summary>All changes</summary>");
+            let _ = writeln!(out);
+            for change in &report.changes {
+                let _ = writeln!(
+                    out,
+                    "- **{:?}** `{}`: {}",
+                    change.change_type,
+                    escape_cell(&change.location.to_string()),
+                    change.description
+                );
+            }
+            let _ = writeln!(out);
+            let _ = writeln!(out, "</details>");
+        }
+
+        Ok(out)
+    }
+}
+
+/// Maps a severity to its Markdown label
+fn severity_label(severity: IssueSeverity) -> &'static str {
+    match severity {
+        IssueSeverity::Error => ":x: Error",
+        IssueSeverity::Warning => ":warning: Warning",
+        IssueSeverity::Info => ":information_source: Info",
+    }
+}
+
+/// Escapes characters that would break a Markdown table cell
+fn escape_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
@@ -0,0 +1,86 @@
+//! SARIF 2.1.0 report renderer
+//!
+//! Produces a static analysis log that code-scanning dashboards can ingest.
+
+use crate::error::Result;
+use crate::report::{CompatibilityReport, IssueSeverity, ReportRenderer};
+use serde_json::{json, Value};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Renders compatibility reports as SARIF 2.1.0 JSON
+///
+/// Severities map to the SARIF levels `error`, `warning` and `note`, and
+/// each result's `ruleId` is the rule ID of the issue's change kind. Each
+/// result points at the new schema file, with a region when the issue's
+/// source position is known, and carries the issue location as a logical
+/// location. Issues suppressed by a baseline carry an external suppression.
+pub struct SarifRenderer;
+
+impl ReportRenderer for SarifRenderer {
+    fn render(&self, report: &CompatibilityReport) -> Result<String> {
+        let artifact = report.source_file().unwrap_or("schema");
+
+        let results: Vec<Value> = report
+            .issues
+            .iter()
+            .map(|issue| {
+                let mut physical_location = json!({
+                    "artifactLocation": { "uri": artifact }
+                });
+                if let Some(line) = issue.location.line {
+                    physical_location["region"] = json!({
+                        "startLine": line,
+                        "startColumn": issue.location.column.unwrap_or(1),
+                    });
+                }
+
+                let mut result = json!({
+                    "ruleId": issue.kind.rule_id(),
+                    "level": sarif_level(issue.severity),
+                    "message": { "text": issue.description },
+                    "locations": [{
+                        "physicalLocation": physical_location,
+                        "logicalLocations": [{
+                            "fullyQualifiedName": issue.location
+                        }]
+                    }]
+                });
+                if let Some(suppression) = &issue.suppression {
+                    let mut entry = json!({ "kind": "external" });
+                    if let Some(justification) = &suppression.justification {
+                        entry["justification"] = json!(justification);
+                    }
+                    result["suppressions"] = json!([entry]);
+                }
+                result
+            })
+            .collect();
+
+        let log = json!({
+            "$schema": SARIF_SCHEMA,
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": env!("CARGO_PKG_NAME"),
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "informationUri": env!("CARGO_PKG_HOMEPAGE"),
+                    }
+                },
+                "results": results,
+            }]
+        });
+
+        Ok(serde_json::to_string_pretty(&log)?)
+    }
+}
+
+/// Maps a severity to its SARIF result level
+fn sarif_level(severity: IssueSeverity) -> &'static str {
+    match severity {
+        IssueSeverity::Error => "error",
+        IssueSeverity::Warning => "warning",
+        IssueSeverity::Info => "note",
+    }
+}
@@ -0,0 +1,90 @@
+use super::*;
+use crate::analyzer::{ChangeKind, ChangeType};
+use crate::report::html::HtmlRenderer;
+use crate::report::junit::JunitRenderer;
+use crate::report::markdown::MarkdownRenderer;
+use crate::report::sarif::SarifRenderer;
+
+fn create_report() -> CompatibilityReport {
+    let mut metadata = HashMap::new();
+    metadata.insert("new_source".to_string(), "schemas/user.sql".to_string());
+
+    CompatibilityReport {
+        changes: vec![
+            SchemaChange::new(ChangeType::Removal, Location::qualified(["users", "name"]), "Column 'name' was removed", HashMap::new()),
+            SchemaChange::new(ChangeType::Addition, Location::qualified(["users", "email"]), "New column 'email' was added", HashMap::new()),
+        ],
+        compatibility_score: 80,
+        is_compatible: false,
+        issues: vec![
+            CompatibilityIssue {
+                severity: IssueSeverity::Error,
+                kind: ChangeKind::ColumnRemoved,
+                description: "Breaking change: Column 'name' was removed".to_string(),
+                location: Location::qualified(["users", "name"]),
+                suppression: None,
+            },
+            CompatibilityIssue {
+                severity: IssueSeverity::Warning,
+                kind: ChangeKind::ColumnTypeChanged,
+                description: "Type of <email> widened".to_string(),
+                location: Location::qualified(["users", "email"]),
+                suppression: None,
+            },
+        ],
+        metadata,
+    }
+}
+
+#[test]
+fn test_markdown_renderer() {
+    let output = MarkdownRenderer.render(&create_report()).unwrap();
+
+    assert!(output.starts_with("## Schema compatibility: Incompatible"));
+    assert!(output.contains("`schemas/user.sql`"));
+    assert!(output.contains("| :x: Error | `users.name` | Breaking change: Column 'name' was removed |"));
+}
+
+#[test]
+fn test_html_renderer_escapes_content() {
+    let output = HtmlRenderer.render(&create_report()).unwrap();
+
+    assert!(output.starts_with("<!DOCTYPE html>"));
+    assert!(output.contains("Type of &lt;email&gt; widened"));
+    assert!(!output.contains("<email>"));
+}
+
+#[test]
+fn test_junit_renderer_fails_on_errors_only() {
+    let output = JunitRenderer.render(&create_report()).unwrap();
+
+    assert!(output.contains("tests=\"2\" failures=\"1\""));
+    assert_eq!(output.matches("<testcase ").count(), 2);
+    assert_eq!(output.matches("<failure ").count(), 1);
+}
+
+#[test]
+fn test_sarif_renderer() {
+    let output = SarifRenderer.render(&create_report()).unwrap();
+    let log: serde_json::Value = serde_json::from_str(&output).unwrap();
+
+    assert_eq!(log["version"], "2.1.0");
+    let results = log["runs"][0]["results"].as_array().unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0]["level"], "error");
+    assert_eq!(results[1]["level"], "warning");
+    assert_eq!(results[0]["ruleId"], "column-removed");
+    assert_eq!(
+        results[0]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+        "schemas/user.sql"
+    );
+    assert_eq!(results[0]["locations"][0]["logicalLocations"][0]["fullyQualifiedName"], "users.name");
+}
+
+#[test]
+fn test_severity_threshold() {
+    assert!(IssueSeverity::Error.is_at_least(IssueSeverity::Warning));
+    assert!(IssueSeverity::Warning.is_at_least(IssueSeverity::Warning));
+    assert!(!IssueSeverity::Info.is_at_least(IssueSeverity::Warning));
+    assert_eq!("warn".parse::<IssueSeverity>(), Ok(IssueSeverity::Warning));
+}
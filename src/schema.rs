@@ -1,7 +1,12 @@
 use serde::{Serialize, Deserialize};
 use semver::Version;
+use serde_json::Value;
+use crate::error::{Result, SchemaDiffError};
+use std::fmt;
+use std::io::Read;
+use std::path::{Path, PathBuf};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum SchemaFormat {
     JsonSchema,
     Protobuf,
@@ -10,11 +15,84 @@ pub enum SchemaFormat {
     RustStruct,
 }
 
+impl SchemaFormat {
+    /// Detects the schema format from its content
+    ///
+    /// Looks for format markers such as an `openapi` key, a `$schema` keyword,
+    /// a `syntax = "proto3"` declaration or a `CREATE TABLE` statement.
+    ///
+    /// # Returns
+    /// The detected format, or `None` if the content is not recognized
+    pub fn detect(content: &str) -> Option<SchemaFormat> {
+        if let Some(document) = parse_document(content) {
+            if let Some(object) = document.as_object() {
+                if object.contains_key("openapi") || object.contains_key("swagger") {
+                    return Some(SchemaFormat::OpenAPI);
+                }
+
+                const JSON_SCHEMA_KEYS: [&str; 7] =
+                    ["$schema", "$id", "$defs", "definitions", "properties", "type", "$ref"];
+                if JSON_SCHEMA_KEYS.iter().any(|key| object.contains_key(*key)) {
+                    return Some(SchemaFormat::JsonSchema);
+                }
+            }
+        }
+
+        let is_proto = content.lines().map(str::trim_start).any(|line| {
+            line.starts_with("syntax = \"proto")
+                || line.starts_with("syntax=\"proto")
+                || line.starts_with("message_type {")
+                || (line.starts_with("package ") && line.ends_with(';'))
+        });
+        if is_proto {
+            return Some(SchemaFormat::Protobuf);
+        }
+
+        let upper = content.to_uppercase();
+        if upper.contains("CREATE TABLE") || upper.contains("ALTER TABLE") {
+            return Some(SchemaFormat::SqlDDL);
+        }
+
+        None
+    }
+
+    /// Guesses the schema format from a file extension
+    ///
+    /// # Returns
+    /// The format conventionally stored with the extension, if any
+    pub fn from_extension(path: &Path) -> Option<SchemaFormat> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "proto" | "textproto" | "pbtxt" => Some(SchemaFormat::Protobuf),
+            "sql" | "ddl" => Some(SchemaFormat::SqlDDL),
+            "json" => Some(SchemaFormat::JsonSchema),
+            "rs" => Some(SchemaFormat::RustStruct),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for SchemaFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            SchemaFormat::JsonSchema => "JSON Schema",
+            SchemaFormat::Protobuf => "Protobuf",
+            SchemaFormat::OpenAPI => "OpenAPI",
+            SchemaFormat::SqlDDL => "SQL DDL",
+            SchemaFormat::RustStruct => "Rust struct",
+        };
+        f.write_str(name)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Schema {
     pub format: SchemaFormat,
     pub content: String,
     pub version: Version,
+    /// File the schema was loaded from, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<PathBuf>,
 }
 
 impl Schema {
@@ -23,6 +101,129 @@ impl Schema {
             format,
             content,
             version,
+            source: None,
         }
     }
-} 
\ No newline at end of file
+
+    /// Loads a schema from a file, detecting its format and version
+    ///
+    /// The format is sniffed from the content, falling back to the file
+    /// extension. The version is taken from the schema itself where the format
+    /// has one (OpenAPI `info.version`, JSON Schema `version` or `$id`) and
+    /// defaults to `0.0.0` otherwise.
+    ///
+    /// # Arguments
+    /// * `path` - Path of the schema file
+    ///
+    /// # Returns
+    /// A schema whose `source` points at `path`
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)?;
+        Self::from_content(content, path)
+    }
+
+    /// Builds a schema from the content of a file, detecting its format and version
+    ///
+    /// Like [`Schema::from_path`], but for content that was read elsewhere,
+    /// e.g. from a git revision.
+    pub(crate) fn from_content(content: String, path: &Path) -> Result<Self> {
+        let format = SchemaFormat::detect(&content)
+            .or_else(|| SchemaFormat::from_extension(path))
+            .ok_or_else(|| {
+                SchemaDiffError::InvalidFormat(format!(
+                    "could not detect schema format of '{}'",
+                    path.display()
+                ))
+            })?;
+
+        Ok(Self::from_detected(format, content).with_source(path))
+    }
+
+    /// Reads a schema from a reader, detecting its format and version
+    ///
+    /// # Arguments
+    /// * `reader` - Source of the schema content, e.g. stdin
+    ///
+    /// # Returns
+    /// A schema without a `source` path
+    pub fn from_reader<R: Read>(mut reader: R) -> Result<Self> {
+        let mut content = String::new();
+        reader.read_to_string(&mut content)?;
+        let format = SchemaFormat::detect(&content).ok_or_else(|| {
+            SchemaDiffError::InvalidFormat("could not detect schema format".to_string())
+        })?;
+
+        Ok(Self::from_detected(format, content))
+    }
+
+    /// Sets the file the schema was loaded from
+    pub fn with_source(mut self, path: impl Into<PathBuf>) -> Self {
+        self.source = Some(path.into());
+        self
+    }
+
+    /// Builds a schema of a known format, extracting its version from the content
+    pub(crate) fn from_detected(format: SchemaFormat, content: String) -> Self {
+        let version = detect_version(format, &content).unwrap_or_else(|| Version::new(0, 0, 0));
+        Self::new(format, content, version)
+    }
+}
+
+/// Parses JSON or YAML content into a generic document
+fn parse_document(content: &str) -> Option<Value> {
+    serde_json::from_str(content)
+        .ok()
+        .or_else(|| serde_yaml::from_str(content).ok())
+        .filter(Value::is_object)
+}
+
+/// Extracts the version declared by a schema, if its format has one
+fn detect_version(format: SchemaFormat, content: &str) -> Option<Version> {
+    let document = parse_document(content)?;
+    match format {
+        SchemaFormat::OpenAPI => document
+            .pointer("/info/version")
+            .and_then(Value::as_str)
+            .and_then(parse_lenient_version),
+        SchemaFormat::JsonSchema => document
+            .get("version")
+            .and_then(Value::as_str)
+            .and_then(parse_lenient_version)
+            .or_else(|| {
+                let id = document.get("$id").and_then(Value::as_str)?;
+                id.split(['/', '-', '_', '#', '?', '='])
+                    .find_map(parse_id_version)
+            }),
+        _ => None,
+    }
+}
+
+/// Parses a segment of an `$id` that is shaped like a version
+///
+/// Segments such as `v1`, `v1.2` or `1.2.3` are versions; a bare number such
+/// as `2024` is more likely a date or an ID and is skipped.
+fn parse_id_version(segment: &str) -> Option<Version> {
+    let is_version_shaped = segment.starts_with(['v', 'V']) || segment.contains('.');
+    is_version_shaped.then(|| parse_lenient_version(segment)).flatten()
+}
+
+/// Parses a version, accepting a `v` prefix and missing minor/patch parts
+fn parse_lenient_version(text: &str) -> Option<Version> {
+    let text = text.trim();
+    let text = text.strip_prefix(['v', 'V']).unwrap_or(text);
+    if let Ok(version) = Version::parse(text) {
+        return Some(version);
+    }
+
+    let mut parts = text.split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next().map_or(Some(0), |part| part.parse().ok())?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some(Version::new(major, minor, 0))
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,56 @@
+use super::*;
+use std::io::Cursor;
+
+#[test]
+fn test_detects_formats() {
+    let openapi = "openapi: 3.0.0\ninfo:\n  title: Test\n  version: 1.2.0\npaths: {}\n";
+    let json_schema = r#"{"$schema": "https://json-schema.org/draft/2020-12/schema", "type": "object"}"#;
+    let proto = "syntax = \"proto3\";\n\nmessage User {\n  int32 id = 1;\n}\n";
+    let sql = "create table users (id integer primary key);";
+
+    assert_eq!(SchemaFormat::detect(openapi), Some(SchemaFormat::OpenAPI));
+    assert_eq!(SchemaFormat::detect(json_schema), Some(SchemaFormat::JsonSchema));
+    assert_eq!(SchemaFormat::detect(proto), Some(SchemaFormat::Protobuf));
+    assert_eq!(SchemaFormat::detect(sql), Some(SchemaFormat::SqlDDL));
+    assert_eq!(SchemaFormat::detect("hello world"), None);
+}
+
+#[test]
+fn test_extracts_versions() {
+    let openapi = r#"{"openapi": "3.0.0", "info": {"title": "Test", "version": "2.1"}, "paths": {}}"#;
+    let schema = Schema::from_reader(Cursor::new(openapi)).unwrap();
+    assert_eq!(schema.format, SchemaFormat::OpenAPI);
+    assert_eq!(schema.version, Version::new(2, 1, 0));
+
+    let json_schema = r#"{"$id": "https://example.com/schemas/v1.4.2/user.json", "type": "object"}"#;
+    let schema = Schema::from_reader(Cursor::new(json_schema)).unwrap();
+    assert_eq!(schema.version, Version::new(1, 4, 2));
+
+    let dated = r#"{"$id": "https://example.com/2024/schemas/v3/user.json", "type": "object"}"#;
+    assert_eq!(Schema::from_reader(Cursor::new(dated)).unwrap().version, Version::new(3, 0, 0));
+    let unversioned = r#"{"$id": "https://example.com/2024/user.json", "type": "object"}"#;
+    assert_eq!(Schema::from_reader(Cursor::new(unversioned)).unwrap().version, Version::new(0, 0, 0));
+
+    let sql = "CREATE TABLE users (id INTEGER);";
+    let schema = Schema::from_reader(Cursor::new(sql)).unwrap();
+    assert_eq!(schema.version, Version::new(0, 0, 0));
+    assert!(schema.source.is_none());
+}
+
+#[test]
+fn test_from_path_records_source() {
+    let path = std::env::temp_dir().join(format!("schema-diff-{}.proto", std::process::id()));
+    std::fs::write(&path, "message_type {\n  name: \"User\"\n}\n").unwrap();
+
+    let schema = Schema::from_path(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(schema.format, SchemaFormat::Protobuf);
+    assert_eq!(schema.source.as_deref(), Some(path.as_path()));
+}
+
+#[test]
+fn test_undetectable_content_is_rejected() {
+    let result = Schema::from_reader(Cursor::new("just some text"));
+    assert!(matches!(result, Err(SchemaDiffError::InvalidFormat(_))));
+}
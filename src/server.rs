@@ -0,0 +1,368 @@
+//! Confluent-compatible schema registry HTTP API
+//!
+//! This module provides [`RegistryServer`], which serves a [`Registry`] over
+//! the Confluent Schema Registry REST protocol so it can stand in for a real
+//! registry in tests and development environments. It is enabled by the
+//! `server` feature and used by the `schema-registry` binary.
+//!
+//! Supported endpoints:
+//!
+//! - `GET /subjects`, `POST /subjects/{subject}`, `DELETE /subjects/{subject}`
+//! - `GET|POST /subjects/{subject}/versions`
+//! - `GET|DELETE /subjects/{subject}/versions/{version}` and `GET .../schema`
+//! - `GET /schemas/ids/{id}`, `GET /schemas/ids/{id}/schema`, `GET /schemas/types`
+//! - `POST /compatibility/subjects/{subject}/versions[/{version}]`
+//! - `GET|PUT /config` and `GET|PUT /config/{subject}`
+//!
+//! Versions may be given as `latest` or `-1`, and subjects may be
+//! percent-encoded. Schemas are posted as
+//! `{"schema": "...", "schemaType": "JSON"}`; besides `JSON` and `PROTOBUF`
+//! the types `OPENAPI` and `SQL` are accepted. As in Confluent Schema
+//! Registry, schemas without a type are `AVRO`, which is not supported.
+
+use crate::error::{Result, SchemaDiffError};
+use crate::history::HistoryReport;
+use crate::registry::{CompatibilityLevel, RegisteredSchema, Registry};
+use crate::{Schema, SchemaFormat};
+use percent_encoding::percent_decode_str;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::io;
+use tiny_http::{Header, Response, Server};
+
+const CONTENT_TYPE: &str = "application/vnd.schemaregistry.v1+json";
+
+/// Response to a registry API request
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerResponse {
+    /// HTTP status code
+    pub status: u16,
+    /// Response body, JSON except for the raw schema endpoints
+    pub body: String,
+}
+
+/// Serves a schema registry over the Confluent REST protocol
+pub struct RegistryServer {
+    registry: Registry,
+}
+
+/// Error reported to API clients as `{"error_code": ..., "message": ...}`
+struct ApiError {
+    status: u16,
+    code: u32,
+    message: String,
+}
+
+impl ApiError {
+    fn new(status: u16, code: u32, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            code,
+            message: message.into(),
+        }
+    }
+
+    fn subject_not_found(subject: &str) -> Self {
+        Self::new(404, 40401, format!("Subject '{}' not found.", subject))
+    }
+
+    fn invalid_schema(message: impl Into<String>) -> Self {
+        Self::new(422, 42201, message)
+    }
+}
+
+impl From<SchemaDiffError> for ApiError {
+    fn from(err: SchemaDiffError) -> Self {
+        match err {
+            SchemaDiffError::NotFound(message) => Self::new(404, 40403, format!("Not found: {}", message)),
+            SchemaDiffError::IncompatibleSchema(message) => Self::new(409, 409, message),
+            SchemaDiffError::RegistryError(message) => Self::new(422, 422, message),
+            SchemaDiffError::ParseError(_)
+            | SchemaDiffError::InvalidFormat(_)
+            | SchemaDiffError::JsonError(_)
+            | SchemaDiffError::ProtobufError(_) => Self::invalid_schema(err.to_string()),
+            err => Self::new(500, 50001, err.to_string()),
+        }
+    }
+}
+
+/// Body of requests that carry a schema
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SchemaRequest {
+    schema: String,
+    #[serde(default)]
+    schema_type: Option<String>,
+}
+
+/// Body of configuration updates
+#[derive(Deserialize)]
+struct ConfigRequest {
+    compatibility: String,
+}
+
+/// Successful result of a request handler
+enum Reply {
+    Json(Value),
+    Raw(String),
+}
+
+impl RegistryServer {
+    /// Creates a server for a registry
+    pub fn new(registry: Registry) -> Self {
+        Self { registry }
+    }
+
+    /// Listens on an address and serves requests until the process exits
+    ///
+    /// Requests are handled one at a time, so the registry directory is
+    /// never written concurrently.
+    ///
+    /// # Errors
+    /// Fails if the address cannot be bound or a response cannot be sent for
+    /// another reason than the client closing the connection.
+    pub fn serve(&self, addr: &str) -> Result<()> {
+        let server = Server::http(addr).map_err(|e| io::Error::other(e.to_string()))?;
+
+        for mut request in server.incoming_requests() {
+            let mut body = String::new();
+            let response = match request.as_reader().read_to_string(&mut body) {
+                Ok(_) => self.handle(request.method().as_str(), request.url(), &body),
+                Err(e) => error_response(ApiError::new(400, 400, e.to_string())),
+            };
+
+            let header = Header::from_bytes("Content-Type", CONTENT_TYPE).expect("static header is valid");
+            let reply = Response::from_string(response.body)
+                .with_status_code(response.status)
+                .with_header(header);
+            match request.respond(reply) {
+                Err(e) if !matches!(e.kind(), io::ErrorKind::BrokenPipe | io::ErrorKind::ConnectionReset) => return Err(e.into()),
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Handles a single API request
+    ///
+    /// # Arguments
+    /// * `method` - The HTTP method, e.g. `GET`
+    /// * `url` - The request path with percent-encoded segments, optionally with a query string
+    /// * `body` - The request body
+    pub fn handle(&self, method: &str, url: &str, body: &str) -> ServerResponse {
+        let (path, query) = url.split_once('?').unwrap_or((url, ""));
+        let decoded = path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| percent_decode_str(segment).decode_utf8())
+            .collect::<std::result::Result<Vec<_>, _>>();
+        let decoded = match decoded {
+            Ok(decoded) => decoded,
+            Err(e) => return error_response(ApiError::new(400, 400, format!("Invalid path: {}", e))),
+        };
+        let segments: Vec<&str> = decoded.iter().map(AsRef::as_ref).collect();
+        let verbose = query.split('&').any(|param| param == "verbose=true");
+
+        match self.route(method, &segments, body, verbose) {
+            Ok(Reply::Json(value)) => ServerResponse {
+                status: 200,
+                body: value.to_string(),
+            },
+            Ok(Reply::Raw(text)) => ServerResponse { status: 200, body: text },
+            Err(err) => error_response(err),
+        }
+    }
+
+    fn route(&self, method: &str, segments: &[&str], body: &str, verbose: bool) -> std::result::Result<Reply, ApiError> {
+        let registry = &self.registry;
+
+        let reply = match (method, segments) {
+            ("GET", []) => Reply::Json(json!({})),
+            ("GET", ["schemas", "types"]) => Reply::Json(json!(["JSON", "PROTOBUF", "OPENAPI", "SQL"])),
+            ("GET", ["schemas", "ids", id]) => {
+                let registered = self.by_id(id)?;
+                Reply::Json(json!({
+                    "schema": registered.schema.content,
+                    "schemaType": schema_type(registered.schema.format),
+                }))
+            }
+            ("GET", ["schemas", "ids", id, "schema"]) => Reply::Raw(self.by_id(id)?.schema.content),
+            ("GET", ["subjects"]) => Reply::Json(json!(registry.subjects()?)),
+            ("POST", ["subjects", subject]) => {
+                self.versions(subject)?;
+                let schema = parse_schema(body)?;
+                let registered = registry
+                    .lookup(subject, &schema)
+                    .map_err(|_| ApiError::new(404, 40403, "Schema not found."))?;
+                Reply::Json(registered_json(&registered))
+            }
+            ("DELETE", ["subjects", subject]) => {
+                self.versions(subject)?;
+                Reply::Json(json!(registry.delete_subject(subject)?))
+            }
+            ("GET", ["subjects", subject, "versions"]) => Reply::Json(json!(self.versions(subject)?)),
+            ("POST", ["subjects", subject, "versions"]) => {
+                let registered = registry.register(subject, parse_schema(body)?)?;
+                Reply::Json(json!({ "id": registered.id }))
+            }
+            ("GET", ["subjects", subject, "versions", version]) => {
+                Reply::Json(registered_json(&self.version(subject, version)?))
+            }
+            ("GET", ["subjects", subject, "versions", version, "schema"]) => {
+                Reply::Raw(self.version(subject, version)?.schema.content)
+            }
+            ("DELETE", ["subjects", subject, "versions", version]) => {
+                let registered = self.version(subject, version)?;
+                registry.delete_version(subject, registered.version)?;
+                Reply::Json(json!(registered.version))
+            }
+            ("POST", ["compatibility", "subjects", subject, "versions"]) => {
+                let report = registry.check(subject, &parse_schema(body)?)?;
+                Reply::Json(compatibility_json(&report, verbose))
+            }
+            ("POST", ["compatibility", "subjects", subject, "versions", version]) => {
+                let registered = self.version(subject, version)?;
+                let report = registry.check_version(subject, registered.version, &parse_schema(body)?)?;
+                Reply::Json(compatibility_json(&report, verbose))
+            }
+            ("GET", ["config"]) => Reply::Json(json!({ "compatibilityLevel": registry.default_compatibility()? })),
+            ("PUT", ["config"]) => {
+                let level = parse_level(body)?;
+                registry.set_default_compatibility(level)?;
+                Reply::Json(json!({ "compatibility": level }))
+            }
+            ("GET", ["config", subject]) => Reply::Json(json!({ "compatibilityLevel": registry.compatibility(subject)? })),
+            ("PUT", ["config", subject]) => {
+                let level = parse_level(body)?;
+                registry.set_compatibility(subject, level)?;
+                Reply::Json(json!({ "compatibility": level }))
+            }
+            _ => return Err(ApiError::new(404, 404, "HTTP 404 Not Found")),
+        };
+
+        Ok(reply)
+    }
+
+    /// Lists the versions of a subject, reporting unknown subjects as `40401`
+    fn versions(&self, subject: &str) -> std::result::Result<Vec<u32>, ApiError> {
+        match self.registry.versions(subject) {
+            Err(SchemaDiffError::NotFound(_)) => Err(ApiError::subject_not_found(subject)),
+            result => Ok(result?),
+        }
+    }
+
+    /// Fetches a version given as a number, `latest` or `-1`
+    fn version(&self, subject: &str, version: &str) -> std::result::Result<RegisteredSchema, ApiError> {
+        let versions = self.versions(subject)?;
+        let number = match version {
+            "latest" | "-1" => versions[versions.len() - 1],
+            _ => version
+                .parse()
+                .ok()
+                .filter(|number| *number > 0)
+                .ok_or_else(|| ApiError::new(422, 42202, format!("Invalid version '{}'.", version)))?,
+        };
+
+        match self.registry.get(subject, number) {
+            Err(SchemaDiffError::NotFound(_)) => Err(ApiError::new(404, 40402, format!("Version {} not found.", number))),
+            result => Ok(result?),
+        }
+    }
+
+    /// Fetches a schema by ID, reporting unknown IDs as `40403`
+    fn by_id(&self, id: &str) -> std::result::Result<RegisteredSchema, ApiError> {
+        let not_found = || ApiError::new(404, 40403, "Schema not found.");
+        let id = id.parse().map_err(|_| not_found())?;
+        match self.registry.get_by_id(id) {
+            Err(SchemaDiffError::NotFound(_)) => Err(not_found()),
+            result => Ok(result?),
+        }
+    }
+}
+
+/// Builds the response for an API error
+fn error_response(err: ApiError) -> ServerResponse {
+    ServerResponse {
+        status: err.status,
+        body: json!({ "error_code": err.code, "message": err.message }).to_string(),
+    }
+}
+
+/// Parses the schema of a request body as its declared type
+///
+/// Requests without a type declare `AVRO`, which is rejected like content
+/// that is not a schema of any supported format.
+fn parse_schema(body: &str) -> std::result::Result<Schema, ApiError> {
+    let request: SchemaRequest =
+        serde_json::from_str(body).map_err(|e| ApiError::invalid_schema(format!("Invalid request: {}", e)))?;
+
+    let format = match request.schema_type.as_deref().unwrap_or("AVRO").to_ascii_uppercase().as_str() {
+        "JSON" => SchemaFormat::JsonSchema,
+        "PROTOBUF" => SchemaFormat::Protobuf,
+        "OPENAPI" => SchemaFormat::OpenAPI,
+        "SQL" => SchemaFormat::SqlDDL,
+        other => return Err(ApiError::invalid_schema(format!("Unsupported schema type '{}'.", other))),
+    };
+    if SchemaFormat::detect(&request.schema).is_none() {
+        return Err(ApiError::invalid_schema("Could not detect the schema format."));
+    }
+
+    Ok(Schema::from_detected(format, request.schema))
+}
+
+/// Parses the compatibility level of a configuration update
+fn parse_level(body: &str) -> std::result::Result<CompatibilityLevel, ApiError> {
+    let request: ConfigRequest = serde_json::from_str(body)
+        .map_err(|e| ApiError::new(422, 42203, format!("Invalid compatibility level: {}", e)))?;
+    request
+        .compatibility
+        .parse()
+        .map_err(|e: String| ApiError::new(422, 42203, e))
+}
+
+/// Maps a schema format to its `schemaType`
+fn schema_type(format: SchemaFormat) -> &'static str {
+    match format {
+        SchemaFormat::JsonSchema => "JSON",
+        SchemaFormat::Protobuf => "PROTOBUF",
+        SchemaFormat::OpenAPI => "OPENAPI",
+        SchemaFormat::SqlDDL => "SQL",
+        SchemaFormat::RustStruct => "RUST",
+    }
+}
+
+/// Describes a registered schema as the version endpoints do
+fn registered_json(registered: &RegisteredSchema) -> Value {
+    json!({
+        "subject": registered.subject,
+        "id": registered.id,
+        "version": registered.version,
+        "schemaType": schema_type(registered.schema.format),
+        "schema": registered.schema.content,
+    })
+}
+
+/// Builds a compatibility response, listing the issues of failed comparisons if `verbose`
+fn compatibility_json(report: &HistoryReport, verbose: bool) -> Value {
+    let mut value = json!({ "is_compatible": report.is_compatible });
+    if verbose {
+        let messages: Vec<String> = report
+            .reports
+            .iter()
+            .filter(|entry| !entry.report.is_compatible)
+            .flat_map(|entry| {
+                entry
+                    .report
+                    .issues
+                    .iter()
+                    .map(move |issue| format!("{} (against {}): {}", issue.location, entry.version, issue.description))
+            })
+            .collect();
+        value["messages"] = json!(messages);
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests;
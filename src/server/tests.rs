@@ -0,0 +1,110 @@
+use super::*;
+
+fn server(name: &str) -> RegistryServer {
+    let root = std::env::temp_dir().join(format!("schema-diff-server-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&root);
+    RegistryServer::new(Registry::open(root).unwrap())
+}
+
+fn schema_body(schema: &str) -> String {
+    json!({ "schema": schema, "schemaType": "JSON" }).to_string()
+}
+
+fn json_body(response: &ServerResponse) -> Value {
+    serde_json::from_str(&response.body).unwrap()
+}
+
+#[test]
+fn test_register_and_fetch_versions() {
+    let server = server("fetch");
+    let v1 = r#"{"type": "object", "properties": {"id": {"type": "integer"}}}"#;
+
+    let response = server.handle("POST", "/subjects/users-value/versions", &schema_body(v1));
+    assert_eq!(response.status, 200);
+    assert_eq!(json_body(&response), json!({ "id": 1 }));
+
+    assert_eq!(json_body(&server.handle("GET", "/subjects", "")), json!(["users-value"]));
+    assert_eq!(json_body(&server.handle("GET", "/subjects/users-value/versions", "")), json!([1]));
+
+    let latest = json_body(&server.handle("GET", "/subjects/users-value/versions/latest", ""));
+    assert_eq!(latest["version"], 1);
+    assert_eq!(latest["schemaType"], "JSON");
+    assert_eq!(server.handle("GET", "/schemas/ids/1/schema", "").body, v1);
+
+    let lookup = json_body(&server.handle("POST", "/subjects/users-value", &schema_body(v1)));
+    assert_eq!(lookup["id"], 1);
+
+    std::fs::remove_dir_all(server.registry.root()).unwrap();
+}
+
+#[test]
+fn test_compatibility_and_config() {
+    let server = server("compat");
+    let v1 = r#"{"type": "object", "properties": {"id": {"type": "integer"}, "name": {"type": "string"}}}"#;
+    let v2 = r#"{"type": "object", "properties": {"id": {"type": "integer"}}}"#;
+    server.handle("POST", "/subjects/users/versions", &schema_body(v1));
+
+    let response = server.handle("PUT", "/config/users", r#"{"compatibility": "FORWARD"}"#);
+    assert_eq!(json_body(&response), json!({ "compatibility": "FORWARD" }));
+    assert_eq!(json_body(&server.handle("GET", "/config", "")), json!({ "compatibilityLevel": "BACKWARD" }));
+
+    let check = json_body(&server.handle("POST", "/compatibility/subjects/users/versions/latest?verbose=true", &schema_body(v2)));
+    assert_eq!(check["is_compatible"], false);
+    assert_eq!(check["messages"].as_array().unwrap().len(), 1);
+
+    let rejected = server.handle("POST", "/subjects/users/versions", &schema_body(v2));
+    assert_eq!(rejected.status, 409);
+
+    server.handle("PUT", "/config/users", r#"{"compatibility": "BACKWARD"}"#);
+    assert_eq!(json_body(&server.handle("POST", "/subjects/users/versions", &schema_body(v2))), json!({ "id": 2 }));
+
+    std::fs::remove_dir_all(server.registry.root()).unwrap();
+}
+
+#[test]
+fn test_error_codes() {
+    let server = server("errors");
+    server.handle("POST", "/subjects/users/versions", &schema_body(r#"{"type": "object"}"#));
+
+    let error_code = |method, url, body| json_body(&server.handle(method, url, body))["error_code"].clone();
+    assert_eq!(error_code("GET", "/subjects/orders/versions", ""), 40401);
+    assert_eq!(error_code("GET", "/subjects/users/versions/7", ""), 40402);
+    assert_eq!(error_code("GET", "/schemas/ids/99", ""), 40403);
+    assert_eq!(error_code("POST", "/subjects/users/versions", r#"{"schema": "not a schema"}"#), 42201);
+    assert_eq!(error_code("PUT", "/config", r#"{"compatibility": "SIDEWAYS"}"#), 42203);
+
+    assert_eq!(json_body(&server.handle("DELETE", "/subjects/users/versions/1", "")), json!(1));
+    assert_eq!(server.handle("GET", "/nowhere", "").status, 404);
+
+    std::fs::remove_dir_all(server.registry.root()).unwrap();
+}
+
+#[test]
+fn test_declared_schema_type_is_kept() {
+    let server = server("types");
+    let openapi = r#"{"openapi": "3.0.0", "info": {"title": "Users", "version": "1.0.0"}, "paths": {}}"#;
+
+    let error_code = |body: Value| json_body(&server.handle("POST", "/subjects/users/versions", &body.to_string()))["error_code"].clone();
+    assert_eq!(error_code(json!({ "schema": openapi })), 42201);
+    assert_eq!(error_code(json!({ "schema": openapi, "schemaType": "AVRO" })), 42201);
+    assert_eq!(error_code(json!({ "schema": "not a schema", "schemaType": "JSON" })), 42201);
+
+    server.handle("POST", "/subjects/users/versions", &schema_body(openapi));
+    assert_eq!(json_body(&server.handle("GET", "/schemas/ids/1", ""))["schemaType"], "JSON");
+
+    std::fs::remove_dir_all(server.registry.root()).unwrap();
+}
+
+#[test]
+fn test_subjects_are_percent_decoded() {
+    let server = server("encoded");
+    let response = server.handle("POST", "/subjects/orders-value%2Fv1/versions", &schema_body(r#"{"type": "object"}"#));
+    assert_eq!(json_body(&response), json!({ "id": 1 }));
+
+    assert_eq!(json_body(&server.handle("GET", "/subjects", "")), json!(["orders-value/v1"]));
+    let latest = json_body(&server.handle("GET", "/subjects/orders-value%2Fv1/versions/latest", ""));
+    assert_eq!(latest["subject"], "orders-value/v1");
+    assert_eq!(server.handle("GET", "/subjects/%FF/versions", "").status, 400);
+
+    std::fs::remove_dir_all(server.registry.root()).unwrap();
+}
@@ -0,0 +1,164 @@
+//! Semantic version bump recommendation and verification
+//!
+//! This module derives the minimal version bump a set of detected changes
+//! requires and checks that the version of a new schema is consistent with it.
+//!
+//! Versions below 1.0.0 follow Cargo's interpretation of SemVer: in `0.y.z`
+//! the minor component acts as the major one, and in `0.0.z` every release
+//! may be breaking. Pre-releases of the same version may differ arbitrarily.
+
+use crate::analyzer::ChangeType;
+use crate::report::{CompatibilityReport, IssueSeverity};
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Size of a version bump, ordered from smallest to largest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VersionBump {
+    /// The version does not need to change
+    None,
+    /// Backwards compatible fixes
+    Patch,
+    /// Backwards compatible additions
+    Minor,
+    /// Breaking changes
+    Major,
+}
+
+impl VersionBump {
+    /// Returns the minimal bump required by the changes in a report
+    ///
+    /// Error issues require a major bump, additions and warning issues a
+    /// minor bump, and any other change a patch bump.
+    ///
+    /// # Arguments
+    /// * `report` - The compatibility report between two schema versions
+    pub fn required(report: &CompatibilityReport) -> VersionBump {
+        if report.issues.iter().any(|issue| issue.severity == IssueSeverity::Error) {
+            VersionBump::Major
+        } else if report.changes.iter().any(|change| change.change_type == ChangeType::Addition)
+            || report.issues.iter().any(|issue| issue.severity == IssueSeverity::Warning)
+        {
+            VersionBump::Minor
+        } else if !report.changes.is_empty() {
+            VersionBump::Patch
+        } else {
+            VersionBump::None
+        }
+    }
+
+    /// Returns the kind of changes a version increment is allowed to carry
+    ///
+    /// Versions that do not increase yield [`VersionBump::None`]. Moving from a
+    /// pre-release to a later pre-release or the release of the same version
+    /// counts as a major bump.
+    ///
+    /// # Arguments
+    /// * `old` - The version of the original schema
+    /// * `new` - The version of the new schema
+    pub fn between(old: &Version, new: &Version) -> VersionBump {
+        if new <= old {
+            return VersionBump::None;
+        }
+        if !old.pre.is_empty() && (old.major, old.minor, old.patch) == (new.major, new.minor, new.patch) {
+            return VersionBump::Major;
+        }
+
+        if old.major != new.major || (old.major == 0 && (old.minor == 0 || old.minor != new.minor)) {
+            VersionBump::Major
+        } else if old.major == 0 || old.minor != new.minor {
+            VersionBump::Minor
+        } else {
+            VersionBump::Patch
+        }
+    }
+
+    /// Applies this bump to a version
+    ///
+    /// A pre-release version is promoted to its release, which is allowed to
+    /// carry any change. Build metadata is dropped.
+    ///
+    /// # Arguments
+    /// * `version` - The version to bump
+    ///
+    /// # Returns
+    /// The smallest version that satisfies the bump
+    pub fn apply(&self, version: &Version) -> Version {
+        if *self == VersionBump::None {
+            return version.clone();
+        }
+
+        let mut next = Version::new(version.major, version.minor, version.patch);
+        if !version.pre.is_empty() {
+            return next;
+        }
+
+        match (self, version.major, version.minor) {
+            (VersionBump::Major, 0, 0) | (VersionBump::Minor, 0, _) | (VersionBump::Patch, _, _) => {
+                next.patch += 1;
+            }
+            (VersionBump::Major, 0, _) | (VersionBump::Minor, _, _) => {
+                next.minor += 1;
+                next.patch = 0;
+            }
+            (VersionBump::Major, _, _) => {
+                next.major += 1;
+                next.minor = 0;
+                next.patch = 0;
+            }
+            (VersionBump::None, _, _) => unreachable!("handled above"),
+        }
+        next
+    }
+}
+
+impl fmt::Display for VersionBump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            VersionBump::None => "none",
+            VersionBump::Patch => "patch",
+            VersionBump::Minor => "minor",
+            VersionBump::Major => "major",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Result of checking a new schema version against the detected changes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VersionCheck {
+    /// Minimal bump required by the detected changes
+    pub required: VersionBump,
+    /// Bump actually made between the two versions
+    pub actual: VersionBump,
+    /// Smallest version the new schema could have been released as
+    pub recommended: Version,
+}
+
+impl VersionCheck {
+    /// Checks the version of a new schema against a compatibility report
+    ///
+    /// # Arguments
+    /// * `old` - The version of the original schema
+    /// * `new` - The version of the new schema
+    /// * `report` - The compatibility report between the two schemas
+    pub fn new(old: &Version, new: &Version, report: &CompatibilityReport) -> Self {
+        let required = VersionBump::required(report);
+
+        Self {
+            required,
+            actual: VersionBump::between(old, new),
+            recommended: required.apply(old),
+        }
+    }
+
+    /// Checks whether the new version is large enough for the detected changes
+    pub fn is_sufficient(&self) -> bool {
+        self.actual >= self.required
+    }
+}
+
+#[cfg(test)]
+mod tests;
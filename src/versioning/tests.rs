@@ -0,0 +1,73 @@
+use super::*;
+use crate::analyzer::build_report;
+use crate::{ChangeKind, CompatibilityMode, Policy, SchemaChange};
+use std::collections::HashMap;
+
+fn version(text: &str) -> Version {
+    Version::parse(text).unwrap()
+}
+
+fn report(kinds: &[ChangeKind]) -> CompatibilityReport {
+    let changes = kinds
+        .iter()
+        .map(|kind| SchemaChange::from_kind(*kind, "/properties/id", kind.rule_id(), HashMap::new()))
+        .collect();
+    build_report(changes, HashMap::new())
+}
+
+#[test]
+fn test_required_bump() {
+    assert_eq!(VersionBump::required(&report(&[])), VersionBump::None);
+    let widened = CompatibilityMode::Backward.apply(report(&[ChangeKind::ColumnTypeWidened]), Policy::DEFAULT_THRESHOLD);
+    assert_eq!(VersionBump::required(&widened), VersionBump::Patch);
+    assert_eq!(VersionBump::required(&report(&[ChangeKind::ColumnTypeWidened])), VersionBump::Major);
+    assert_eq!(VersionBump::required(&report(&[ChangeKind::PropertyAdded])), VersionBump::Minor);
+    assert_eq!(
+        VersionBump::required(&report(&[ChangeKind::PropertyAdded, ChangeKind::PropertyRemoved])),
+        VersionBump::Major
+    );
+}
+
+#[test]
+fn test_bump_between_versions() {
+    assert_eq!(VersionBump::between(&version("1.2.3"), &version("2.0.0")), VersionBump::Major);
+    assert_eq!(VersionBump::between(&version("1.2.3"), &version("1.3.0")), VersionBump::Minor);
+    assert_eq!(VersionBump::between(&version("1.2.3"), &version("1.2.4")), VersionBump::Patch);
+    assert_eq!(VersionBump::between(&version("1.2.3"), &version("1.2.3")), VersionBump::None);
+    assert_eq!(VersionBump::between(&version("1.2.3"), &version("1.0.0")), VersionBump::None);
+
+    assert_eq!(VersionBump::between(&version("0.3.1"), &version("0.4.0")), VersionBump::Major);
+    assert_eq!(VersionBump::between(&version("0.3.1"), &version("0.3.2")), VersionBump::Minor);
+    assert_eq!(VersionBump::between(&version("0.0.3"), &version("0.0.4")), VersionBump::Major);
+
+    assert_eq!(VersionBump::between(&version("2.0.0-rc.1"), &version("2.0.0-rc.2")), VersionBump::Major);
+    assert_eq!(VersionBump::between(&version("2.0.0-rc.1"), &version("2.0.0")), VersionBump::Major);
+    assert_eq!(VersionBump::between(&version("1.2.3"), &version("1.3.0-beta.1")), VersionBump::Minor);
+}
+
+#[test]
+fn test_apply_bump() {
+    assert_eq!(VersionBump::Major.apply(&version("1.2.3")), version("2.0.0"));
+    assert_eq!(VersionBump::Minor.apply(&version("1.2.3")), version("1.3.0"));
+    assert_eq!(VersionBump::Patch.apply(&version("1.2.3+build.5")), version("1.2.4"));
+    assert_eq!(VersionBump::Major.apply(&version("0.3.1")), version("0.4.0"));
+    assert_eq!(VersionBump::Minor.apply(&version("0.3.1")), version("0.3.2"));
+    assert_eq!(VersionBump::Major.apply(&version("0.0.3")), version("0.0.4"));
+    assert_eq!(VersionBump::Major.apply(&version("2.0.0-rc.1")), version("2.0.0"));
+    assert_eq!(VersionBump::None.apply(&version("1.2.3")), version("1.2.3"));
+}
+
+#[test]
+fn test_version_check_flags_breaking_minor_release() {
+    let breaking = report(&[ChangeKind::PropertyRemoved]);
+
+    let check = VersionCheck::new(&version("1.2.0"), &version("1.3.0"), &breaking);
+    assert!(!check.is_sufficient());
+    assert_eq!(check.required, VersionBump::Major);
+    assert_eq!(check.actual, VersionBump::Minor);
+    assert_eq!(check.recommended, version("2.0.0"));
+
+    assert!(VersionCheck::new(&version("1.2.0"), &version("2.0.0"), &breaking).is_sufficient());
+    assert!(VersionCheck::new(&version("0.2.0"), &version("0.3.0"), &breaking).is_sufficient());
+    assert!(VersionCheck::new(&version("1.2.0"), &version("1.2.0"), &report(&[])).is_sufficient());
+}
@@ -0,0 +1,220 @@
+#![cfg(feature = "cli")]
+
+use std::path::PathBuf;
+use std::process::Command;
+
+fn write_fixture(name: &str, content: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("schema-diff-cli-{}", std::process::id()));
+    let path = dir.join(name);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
+fn schema_diff(args: &[&std::ffi::OsStr]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_schema-diff"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_check_gates_on_breaking_changes() {
+    let old = write_fixture("old.sql", "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT);");
+    let new = write_fixture("new.sql", "CREATE TABLE users (id INTEGER PRIMARY KEY);");
+
+    let breaking = schema_diff(&["check".as_ref(), old.as_os_str(), new.as_os_str()]);
+    assert_eq!(breaking.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&breaking.stdout).starts_with("FAIL"));
+
+    let additive = schema_diff(&["check".as_ref(), new.as_os_str(), old.as_os_str()]);
+    assert_eq!(additive.status.code(), Some(0));
+}
+
+#[test]
+fn test_diff_prints_json_report() {
+    let old = write_fixture("old.json", r#"{"type": "object", "properties": {"name": {"type": "string"}}}"#);
+    let new = write_fixture("new.json", r#"{"type": "object", "properties": {"name": {"type": "string"}, "age": {"type": "integer"}}}"#);
+
+    let output = schema_diff(&["diff".as_ref(), old.as_os_str(), new.as_os_str(), "--format".as_ref(), "json".as_ref()]);
+    assert_eq!(output.status.code(), Some(0));
+
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["is_compatible"], true);
+}
+
+#[test]
+fn test_unreadable_input_is_an_error() {
+    let output = schema_diff(&["diff".as_ref(), "does-not-exist.sql".as_ref(), "also-missing.sql".as_ref()]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_check_version_fails_on_too_small_bump() {
+    let old = write_fixture("v1.json", r#"{"version": "1.2.0", "type": "object", "properties": {}}"#);
+    let patch = write_fixture("v1-patch.json", r#"{"version": "1.2.1", "type": "object", "properties": {"name": {"type": "string"}}}"#);
+    let breaking = write_fixture("v2.json", r#"{"version": "2.0.0", "type": "object", "properties": {}}"#);
+
+    let output = schema_diff(&["check".as_ref(), old.as_os_str(), patch.as_os_str(), "--check-version".as_ref()]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("patch bump, minor required (release as 1.3.0 or later)"));
+
+    let output = schema_diff(&["diff".as_ref(), patch.as_os_str(), breaking.as_os_str(), "--check-version".as_ref()]);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Version 1.2.1 -> 2.0.0: major bump, major required\n"));
+}
+
+#[test]
+fn test_policy_downgrades_breaking_change() {
+    let old = write_fixture("policy-old.sql", "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT);");
+    let new = write_fixture("policy-new.sql", "CREATE TABLE users (id INTEGER PRIMARY KEY);");
+    let policy = write_fixture("policy.toml", "[severities]\ncolumn-removed = \"warning\"\n");
+
+    let output = schema_diff(&["check".as_ref(), old.as_os_str(), new.as_os_str(), "--policy".as_ref(), policy.as_os_str()]);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("[warning] users.name"));
+}
+
+#[test]
+fn test_baseline_suppresses_accepted_breaking_change() {
+    let old = write_fixture("baseline-old.sql", "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT);");
+    let new = write_fixture("baseline-new.sql", "CREATE TABLE users (id INTEGER PRIMARY KEY);");
+    let baseline = std::env::temp_dir()
+        .join(format!("schema-diff-cli-{}", std::process::id()))
+        .join("baseline.json");
+
+    let output = schema_diff(&[
+        "check".as_ref(), old.as_os_str(), new.as_os_str(),
+        "--write-baseline".as_ref(), baseline.as_os_str(),
+    ]);
+    assert_eq!(output.status.code(), Some(0));
+
+    let output = schema_diff(&["check".as_ref(), old.as_os_str(), new.as_os_str(), "--baseline".as_ref(), baseline.as_os_str()]);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("[error] users.name: Breaking change: Column 'name' was removed (suppressed)"));
+}
+
+#[test]
+fn test_mode_selects_compatibility_direction() {
+    let old = write_fixture("mode-old.sql", "CREATE TABLE users (id INTEGER PRIMARY KEY);");
+    let new = write_fixture("mode-new.sql", "CREATE TABLE users (id INTEGER PRIMARY KEY, email TEXT NOT NULL);");
+
+    let backward = schema_diff(&["check".as_ref(), old.as_os_str(), new.as_os_str(), "--mode".as_ref(), "backward".as_ref()]);
+    assert_eq!(backward.status.code(), Some(1));
+
+    let forward = schema_diff(&["check".as_ref(), old.as_os_str(), new.as_os_str(), "--mode".as_ref(), "forward".as_ref()]);
+    assert_eq!(forward.status.code(), Some(0));
+}
+
+#[test]
+fn test_forward_mode_fails_on_loosened_constraint() {
+    let old = write_fixture("loosened-old.json", r#"{"type": "object", "properties": {"code": {"type": "string", "maxLength": 50}}}"#);
+    let new = write_fixture("loosened-new.json", r#"{"type": "object", "properties": {"code": {"type": "string", "maxLength": 100}}}"#);
+
+    let forward = schema_diff(&["check".as_ref(), old.as_os_str(), new.as_os_str(), "--mode".as_ref(), "forward".as_ref()]);
+    assert_eq!(forward.status.code(), Some(1));
+
+    let backward = schema_diff(&["check".as_ref(), old.as_os_str(), new.as_os_str(), "--mode".as_ref(), "backward".as_ref()]);
+    assert_eq!(backward.status.code(), Some(0));
+}
+
+#[cfg(feature = "git")]
+#[test]
+fn test_git_compares_revisions() {
+    let repo = std::env::temp_dir().join(format!("schema-diff-cli-git-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&repo);
+    std::fs::create_dir_all(&repo).unwrap();
+    let git = |args: &[&str]| {
+        let status = Command::new("git")
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(&repo)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success());
+    };
+
+    git(&["init", "-q"]);
+    std::fs::write(repo.join("users.sql"), "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT);").unwrap();
+    git(&["add", "users.sql"]);
+    git(&["commit", "-q", "-m", "Add users"]);
+    std::fs::write(repo.join("users.sql"), "CREATE TABLE users (id INTEGER PRIMARY KEY);").unwrap();
+
+    let output = schema_diff(&[
+        "check".as_ref(), "HEAD:users.sql".as_ref(), repo.join("users.sql").as_os_str(),
+        "--git".as_ref(), "--repo".as_ref(), repo.as_os_str(),
+    ]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("users.name"));
+
+    std::fs::remove_dir_all(&repo).unwrap();
+}
+
+#[cfg(feature = "git")]
+#[test]
+fn test_timeline_lists_schema_history() {
+    let repo = std::env::temp_dir().join(format!("schema-diff-cli-timeline-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&repo);
+    std::fs::create_dir_all(&repo).unwrap();
+    let commit = |content: &str, message: &str| {
+        std::fs::write(repo.join("users.sql"), content).unwrap();
+        for args in [&["add", "users.sql"][..], &["commit", "-q", "-m", message][..]] {
+            let status = Command::new("git")
+                .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+                .args(args)
+                .current_dir(&repo)
+                .status()
+                .unwrap();
+            assert!(status.success());
+        }
+    };
+
+    assert!(Command::new("git").args(["init", "-q"]).current_dir(&repo).status().unwrap().success());
+    commit("CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT);", "Add users");
+    commit("CREATE TABLE users (id INTEGER PRIMARY KEY);", "Drop name");
+
+    let output = schema_diff(&["timeline".as_ref(), "users.sql".as_ref(), "--repo".as_ref(), repo.as_os_str(), "--format".as_ref(), "json".as_ref()]);
+    assert_eq!(output.status.code(), Some(0));
+    let timeline: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(timeline["entries"].as_array().unwrap().len(), 2);
+    assert_eq!(timeline["entries"][1]["version_check"]["required"], "major");
+
+    std::fs::remove_dir_all(&repo).unwrap();
+}
+
+#[test]
+fn test_diff_dir_reports_each_file() {
+    let old = write_fixture("dir-old/users.sql", "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT);");
+    let new = write_fixture("dir-new/users.sql", "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT, email TEXT);");
+    write_fixture("dir-new/orders.sql", "CREATE TABLE orders (id INTEGER PRIMARY KEY);");
+    let (old, new) = (old.parent().unwrap(), new.parent().unwrap());
+
+    let output = schema_diff(&["diff-dir".as_ref(), old.as_os_str(), new.as_os_str()]);
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("added orders.sql"));
+    assert!(stdout.contains("== users.sql =="));
+
+    let output = schema_diff(&["diff-dir".as_ref(), new.as_os_str(), old.as_os_str(), "--format".as_ref(), "json".as_ref()]);
+    assert_eq!(output.status.code(), Some(1));
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["removed"][0], "orders.sql");
+}
+
+#[test]
+fn test_renames_file_declares_rename() {
+    let old = write_fixture("renames-old.sql", "CREATE TABLE users (id INTEGER PRIMARY KEY, surname TEXT);");
+    let new = write_fixture("renames-new.sql", "CREATE TABLE users (id INTEGER PRIMARY KEY, family TEXT);");
+    let renames = write_fixture("renames.txt", "# columns\nusers.surname -> users.family\n");
+
+    let output = schema_diff(&[
+        "diff".as_ref(), old.as_os_str(), new.as_os_str(),
+        "--format".as_ref(), "json".as_ref(),
+        "--renames".as_ref(), renames.as_os_str(),
+    ]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let changes = report["changes"].as_array().unwrap();
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0]["kind"], "column-renamed");
+    assert_eq!(changes[0]["metadata"]["old_location"], "users.surname");
+}